@@ -53,6 +53,215 @@ where
     intersections_against_line(aabb, ray_org, ray_dir).filter(|(_tmin, tmax)| *tmax >= Real::zero())
 }
 
+/// ray with the reciprocal of the direction and its signs precomputed
+/// so that the slab test against many boxes (e.g., in the BVH traversal) does not divide.
+/// See [`crate::aabb3::PrecomputedRay3`] for the 3D case.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecomputedRay<Real, const NDIM: usize> {
+    pub org: [Real; NDIM],
+    pub dir: [Real; NDIM],
+    /// `1 / dir`, which is infinite for the zero component
+    pub inv_dir: [Real; NDIM],
+    /// `1` if the direction is negative, `0` otherwise
+    pub sign: [usize; NDIM],
+}
+
+impl<Real, const NDIM: usize> PrecomputedRay<Real, NDIM>
+where
+    Real: num_traits::Float,
+{
+    pub fn new(org: &[Real; NDIM], dir: &[Real; NDIM]) -> Self {
+        let inv_dir = dir.map(|v| Real::one() / v);
+        Self {
+            org: *org,
+            dir: *dir,
+            inv_dir,
+            sign: inv_dir.map(|v| if v < Real::zero() { 1 } else { 0 }),
+        }
+    }
+}
+
+/// the same as [`intersections_against_ray`] but without the division
+pub fn intersections_against_ray_precomputed<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    ray: &PrecomputedRay<Real, NDIM>,
+) -> Option<(Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let mut tmin = Real::min_value();
+    let mut tmax = Real::max_value();
+    for i_dim in 0..NDIM {
+        let near = aabb[i_dim + ray.sign[i_dim] * NDIM];
+        let far = aabb[i_dim + (1 - ray.sign[i_dim]) * NDIM];
+        // `min` and `max` ignore the NaN from `0 * inf` when the origin is on the slab
+        tmin = tmin.max((near - ray.org[i_dim]) * ray.inv_dir[i_dim]);
+        tmax = tmax.min((far - ray.org[i_dim]) * ray.inv_dir[i_dim]);
+    }
+    if tmax >= tmin && tmax >= Real::zero() {
+        Some((tmin, tmax))
+    } else {
+        None
+    }
+}
+
+/// [`intersections_against_ray_precomputed`] for four boxes at a time.
+/// The loop over the boxes is innermost so that the compiler can vectorize it.
+pub fn intersections_against_ray_precomputed_x4<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabbs: &[[Real; SIZE_AABB]; 4],
+    ray: &PrecomputedRay<Real, NDIM>,
+) -> [Option<(Real, Real)>; 4]
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let mut tmin = [Real::min_value(); 4];
+    let mut tmax = [Real::max_value(); 4];
+    for i_dim in 0..NDIM {
+        let i_near = i_dim + ray.sign[i_dim] * NDIM;
+        let i_far = i_dim + (1 - ray.sign[i_dim]) * NDIM;
+        for j in 0..4 {
+            tmin[j] = tmin[j].max((aabbs[j][i_near] - ray.org[i_dim]) * ray.inv_dir[i_dim]);
+            tmax[j] = tmax[j].min((aabbs[j][i_far] - ray.org[i_dim]) * ray.inv_dir[i_dim]);
+        }
+    }
+    std::array::from_fn(|j| {
+        if tmax[j] >= tmin[j] && tmax[j] >= Real::zero() {
+            Some((tmin[j], tmax[j]))
+        } else {
+            None
+        }
+    })
+}
+
+#[test]
+fn test_intersections_against_ray_precomputed() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [-1.0f64, -0.5, 0.0, 1.0, 0.5, 2.0];
+    let mut num_hit = 0;
+    for _iter in 0..1000 {
+        let org: [f64; 3] = std::array::from_fn(|_| 6. * rng.random::<f64>() - 3.);
+        let mut dir: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        if rng.random::<f64>() < 0.2 {
+            dir[rng.random_range(0..3)] = 0.0;
+        }
+        let ray = PrecomputedRay::new(&org, &dir);
+        let res0 = intersections_against_ray::<f64, 3, 6>(&aabb, &org, &dir);
+        let res1 = intersections_against_ray_precomputed(&aabb, &ray);
+        match (res0, res1) {
+            (Some((t0, t1)), Some((s0, s1))) => {
+                assert!((t0 - s0).abs() < 1.0e-10 && (t1 - s1).abs() < 1.0e-10);
+                num_hit += 1;
+            }
+            (None, None) => {}
+            _ => panic!("{:?} {:?}", res0, res1),
+        }
+        let aabbs = [
+            aabb,
+            expand::<f64, 3, 6>(&aabb, 0.5),
+            [0.; 6],
+            [1., 1., 1., 0., 0., 0.],
+        ];
+        let res4 = intersections_against_ray_precomputed_x4(&aabbs, &ray);
+        for j in 0..4 {
+            assert_eq!(
+                res4[j],
+                intersections_against_ray_precomputed(&aabbs[j], &ray)
+            );
+        }
+    }
+    assert!(num_hit > 50);
+}
+
+/// clip the segment `p0`-`p1` inside the AABB by the Liang–Barsky algorithm.
+/// The end points inside the AABB are kept exactly, and the clipped end points are clamped onto the AABB.
+/// * Return `None` if the segment does not overlap the AABB
+pub fn clip_edge<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    p0: &[Real; NDIM],
+    p1: &[Real; NDIM],
+) -> Option<([Real; NDIM], [Real; NDIM])>
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let (mut t0, mut t1) = (Real::zero(), Real::one());
+    for i_dim in 0..NDIM {
+        let d = p1[i_dim] - p0[i_dim];
+        // `d * t <= q` for the lower and upper side of the slab
+        for (p, q) in [
+            (-d, p0[i_dim] - aabb[i_dim]),
+            (d, aabb[i_dim + NDIM] - p0[i_dim]),
+        ] {
+            if p.is_zero() {
+                if q < Real::zero() {
+                    return None;
+                }
+            } else if p < Real::zero() {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let clip = |t: Real, p: &[Real; NDIM]| -> [Real; NDIM] {
+        std::array::from_fn(|i| {
+            let v = p0[i] + (p1[i] - p0[i]) * t;
+            if t.is_zero() || t.is_one() {
+                p[i]
+            } else {
+                v.max(aabb[i]).min(aabb[i + NDIM])
+            }
+        })
+    };
+    Some((clip(t0, p0), clip(t1, p1)))
+}
+
+#[test]
+fn test_clip_edge() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [-1f64, -0.5, 0., 1., 0.5, 2.];
+    let mut num_hit = 0;
+    for _iter in 0..1000 {
+        let p0: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let p1: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let res = clip_edge::<f64, 3, 6>(&aabb, &p0, &p1);
+        // compare with the intersection against the line
+        let dir = std::array::from_fn(|i| p1[i] - p0[i]);
+        let range = intersections_against_line::<f64, 3, 6>(&aabb, &p0, &dir)
+            .map(|(t0, t1)| (t0.max(0.), t1.min(1.)))
+            .filter(|(t0, t1)| t0 <= t1);
+        assert_eq!(res.is_some(), range.is_some());
+        let (Some((q0, q1)), Some((t0, t1))) = (res, range) else {
+            continue;
+        };
+        num_hit += 1;
+        assert!(is_include_point::<f64, 3, 6>(&aabb, &q0));
+        assert!(is_include_point::<f64, 3, 6>(&aabb, &q1));
+        for i in 0..3 {
+            assert!((q0[i] - (p0[i] + t0 * dir[i])).abs() < 1.0e-10);
+            assert!((q1[i] - (p0[i] + t1 * dir[i])).abs() < 1.0e-10);
+        }
+        if is_include_point::<f64, 3, 6>(&aabb, &p0) {
+            assert_eq!(q0, p0);
+        }
+    }
+    assert!(num_hit > 100);
+    // 2D segment parallel to the axis
+    let aabb = [0f64, 0., 1., 1.];
+    let res = clip_edge::<f64, 2, 4>(&aabb, &[-1., 0.5], &[2., 0.5]);
+    assert_eq!(res, Some(([0., 0.5], [1., 0.5])));
+    assert_eq!(clip_edge::<f64, 2, 4>(&aabb, &[-1., 1.5], &[2., 1.5]), None);
+}
+
 pub fn is_include_point<Real, const NDIM: usize, const SIZE_AABB: usize>(
     aabb: &[Real; SIZE_AABB],
     point: &[Real; NDIM],
@@ -78,16 +287,300 @@ where
     std::array::from_fn::<_, NDIM, _>(|i| (aabb[i] + aabb[i + NDIM]) * half)
 }
 
+/// the box is valid (active) if `min <= max` for all the axes.
+/// An empty box is represented by `min > max`.
+pub fn is_valid<Real, const NDIM: usize, const SIZE_AABB: usize>(aabb: &[Real; SIZE_AABB]) -> bool
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    (0..NDIM).all(|i| aabb[i] <= aabb[i + NDIM])
+}
+
+/// the smallest box containing the two boxes. An invalid (empty) box is ignored.
+pub fn union<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    a: &[Real; SIZE_AABB],
+    b: &[Real; SIZE_AABB],
+) -> [Real; SIZE_AABB]
+where
+    Real: num_traits::Float,
+{
+    if !is_valid::<Real, NDIM, SIZE_AABB>(a) {
+        return *b;
+    }
+    if !is_valid::<Real, NDIM, SIZE_AABB>(b) {
+        return *a;
+    }
+    std::array::from_fn(|i| {
+        if i < NDIM {
+            a[i].min(b[i])
+        } else {
+            a[i].max(b[i])
+        }
+    })
+}
+
+/// enlarge the box by `margin` in all the directions
+pub fn expand<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    margin: Real,
+) -> [Real; SIZE_AABB]
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    std::array::from_fn(|i| {
+        if i < NDIM {
+            aabb[i] - margin
+        } else {
+            aabb[i] + margin
+        }
+    })
+}
+
+/// the smallest box containing the points.
+/// Returns the invalid (empty) box `[inf,...,-inf,...]` if there is no point.
+pub fn from_points<'a, Real, const NDIM: usize, const SIZE_AABB: usize>(
+    points: impl IntoIterator<Item = &'a [Real; NDIM]>,
+) -> [Real; SIZE_AABB]
+where
+    Real: num_traits::Float + 'a,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let mut aabb: [Real; SIZE_AABB] = std::array::from_fn(|i| {
+        if i < NDIM {
+            Real::infinity()
+        } else {
+            Real::neg_infinity()
+        }
+    });
+    for p in points {
+        for i in 0..NDIM {
+            aabb[i] = aabb[i].min(p[i]);
+            aabb[i + NDIM] = aabb[i + NDIM].max(p[i]);
+        }
+    }
+    aabb
+}
+
+/// volume of the box (area in 2D). Zero for the invalid box.
+pub fn volume<Real, const NDIM: usize, const SIZE_AABB: usize>(aabb: &[Real; SIZE_AABB]) -> Real
+where
+    Real: num_traits::Float,
+{
+    if !is_valid::<Real, NDIM, SIZE_AABB>(aabb) {
+        return Real::zero();
+    }
+    (0..NDIM).fold(Real::one(), |v, i| v * (aabb[i + NDIM] - aabb[i]))
+}
+
+/// area of the boundary of the box (perimeter in 2D). Zero for the invalid box.
+/// This is the cost used in the surface area heuristic (SAH) for the BVH construction.
+pub fn surface_area<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+) -> Real
+where
+    Real: num_traits::Float,
+{
+    if !is_valid::<Real, NDIM, SIZE_AABB>(aabb) {
+        return Real::zero();
+    }
+    let two = Real::one() + Real::one();
+    let area = (0..NDIM)
+        .map(|i| {
+            (0..NDIM)
+                .filter(|&j| j != i)
+                .fold(Real::one(), |v, j| v * (aabb[j + NDIM] - aabb[j]))
+        })
+        .fold(Real::zero(), |s, v| s + v);
+    area * two
+}
+
+/// squared distance from the point to the box. Zero if the point is inside.
+pub fn sq_distance_to_point<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    point: &[Real; NDIM],
+) -> Real
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    (0..NDIM).fold(Real::zero(), |sum, i| {
+        let d = (aabb[i] - point[i])
+            .max(point[i] - aabb[i + NDIM])
+            .max(Real::zero());
+        sum + d * d
+    })
+}
+
+/// distance from the point to the box. Zero if the point is inside.
+pub fn distance_to_point<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    point: &[Real; NDIM],
+) -> Real
+where
+    Real: num_traits::Float,
+{
+    sq_distance_to_point::<Real, NDIM, SIZE_AABB>(aabb, point).sqrt()
+}
+
+/// squared distance between the two boxes. Zero if they overlap.
+/// This is the lower bound of the squared distance between any pair of the points in the boxes.
+pub fn sq_distance_aabb_aabb<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    a: &[Real; SIZE_AABB],
+    b: &[Real; SIZE_AABB],
+) -> Real
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    (0..NDIM).fold(Real::zero(), |sum, i| {
+        let d = (a[i] - b[i + NDIM])
+            .max(b[i] - a[i + NDIM])
+            .max(Real::zero());
+        sum + d * d
+    })
+}
+
+/// the two boxes overlap (touching boundaries count as overlap).
+/// Always false if either box is invalid.
+pub fn is_intersect<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    a: &[Real; SIZE_AABB],
+    b: &[Real; SIZE_AABB],
+) -> bool
+where
+    Real: num_traits::Float,
+{
+    is_valid::<Real, NDIM, SIZE_AABB>(a)
+        && is_valid::<Real, NDIM, SIZE_AABB>(b)
+        && (0..NDIM).all(|i| a[i] <= b[i + NDIM] && b[i] <= a[i + NDIM])
+}
+
+/// the box shared by the two boxes. `None` if they do not overlap.
+pub fn intersection<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    a: &[Real; SIZE_AABB],
+    b: &[Real; SIZE_AABB],
+) -> Option<[Real; SIZE_AABB]>
+where
+    Real: num_traits::Float,
+{
+    if !is_intersect::<Real, NDIM, SIZE_AABB>(a, b) {
+        return None;
+    }
+    Some(std::array::from_fn(|i| {
+        if i < NDIM {
+            a[i].max(b[i])
+        } else {
+            a[i].min(b[i])
+        }
+    }))
+}
+
+/// index of the axis along which the box is the longest
+pub fn longest_axis<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+) -> usize
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    (1..NDIM).fold(0, |i_max, i| {
+        if aabb[i + NDIM] - aabb[i] > aabb[i_max + NDIM] - aabb[i_max] {
+            i
+        } else {
+            i_max
+        }
+    })
+}
+
+/// split the box with the plane perpendicular to `axis` at the coordinate `value`.
+/// The `value` is clamped inside the box.
+/// # Returns
+/// `(lower, upper)` boxes below and above the plane
+pub fn split_at<Real, const NDIM: usize, const SIZE_AABB: usize>(
+    aabb: &[Real; SIZE_AABB],
+    axis: usize,
+    value: Real,
+) -> ([Real; SIZE_AABB], [Real; SIZE_AABB])
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    assert!(axis < NDIM);
+    let value = value.max(aabb[axis]).min(aabb[axis + NDIM]);
+    let mut lower = *aabb;
+    let mut upper = *aabb;
+    lower[axis + NDIM] = value;
+    upper[axis] = value;
+    (lower, upper)
+}
+
+/// corners of the box. `NCORNER` should be `2^NDIM`.
+/// The `i_dim`-th coordinate of the `i_corner`-th corner is the maximum
+/// if the `i_dim`-th bit of `i_corner` is set and the minimum otherwise.
+/// Note that the order is different from [`crate::aabb3::xyz_from_hex_index`].
+pub fn corners<Real, const NDIM: usize, const SIZE_AABB: usize, const NCORNER: usize>(
+    aabb: &[Real; SIZE_AABB],
+) -> [[Real; NDIM]; NCORNER]
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    assert_eq!(1 << NDIM, NCORNER);
+    std::array::from_fn(|i_corner| {
+        std::array::from_fn(|i_dim| {
+            if (i_corner >> i_dim) & 1 == 0 {
+                aabb[i_dim]
+            } else {
+                aabb[i_dim + NDIM]
+            }
+        })
+    })
+}
+
 // -----------------------------
 #[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
 pub struct AABB<Real, const NDIM: usize, const SIZE_AABB: usize> {
     pub aabb: [Real; SIZE_AABB],
 }
 
+// SAFETY: transparent wrapper of an array of `Pod`
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Zeroable, const NDIM: usize, const SIZE_AABB: usize> bytemuck::Zeroable
+    for AABB<Real, NDIM, SIZE_AABB>
+{
+}
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Pod, const NDIM: usize, const SIZE_AABB: usize> bytemuck::Pod
+    for AABB<Real, NDIM, SIZE_AABB>
+{
+}
+
 impl<Real, const NDIM: usize, const SIZE_AABB: usize> AABB<Real, NDIM, SIZE_AABB>
 where
     Real: num_traits::Float,
 {
+    /// empty box with the inverted bounds `[inf,...,-inf,...]` to accumulate the points/boxes
+    pub fn new_empty() -> Self {
+        Self {
+            aabb: from_points::<Real, NDIM, SIZE_AABB>(&[]),
+        }
+    }
+
+    /// enlarge the box to include the point
+    pub fn insert_point(&mut self, point: &[Real; NDIM]) {
+        for i in 0..NDIM {
+            self.aabb[i] = self.aabb[i].min(point[i]);
+            self.aabb[i + NDIM] = self.aabb[i + NDIM].max(point[i]);
+        }
+    }
+
+    /// enlarge the box to include the other box. An invalid (empty) box is ignored.
+    pub fn insert_aabb(&mut self, other: &Self) {
+        self.aabb = union::<Real, NDIM, SIZE_AABB>(&self.aabb, &other.aabb);
+    }
+
     pub fn is_include_point(&self, point: &[Real; NDIM]) -> bool {
         is_include_point::<Real, NDIM, SIZE_AABB>(&self.aabb, point)
     }
@@ -100,6 +593,13 @@ where
         intersections_against_ray::<Real, NDIM, SIZE_AABB>(&self.aabb, ray_org, ray_dir)
     }
 
+    pub fn intersections_against_ray_precomputed(
+        &self,
+        ray: &PrecomputedRay<Real, NDIM>,
+    ) -> Option<(Real, Real)> {
+        intersections_against_ray_precomputed::<Real, NDIM, SIZE_AABB>(&self.aabb, ray)
+    }
+
     pub fn intersections_against_line(
         &self,
         line_org: &[Real; NDIM],
@@ -111,4 +611,245 @@ where
     pub fn center(&self) -> [Real; NDIM] {
         center(&self.aabb)
     }
+
+    pub fn is_valid(&self) -> bool {
+        is_valid::<Real, NDIM, SIZE_AABB>(&self.aabb)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            aabb: union::<Real, NDIM, SIZE_AABB>(&self.aabb, &other.aabb),
+        }
+    }
+
+    pub fn expand(&self, margin: Real) -> Self {
+        Self {
+            aabb: expand::<Real, NDIM, SIZE_AABB>(&self.aabb, margin),
+        }
+    }
+
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a [Real; NDIM]>) -> Self
+    where
+        Real: 'a,
+    {
+        Self {
+            aabb: from_points(points),
+        }
+    }
+
+    pub fn volume(&self) -> Real {
+        volume::<Real, NDIM, SIZE_AABB>(&self.aabb)
+    }
+
+    pub fn surface_area(&self) -> Real {
+        surface_area::<Real, NDIM, SIZE_AABB>(&self.aabb)
+    }
+
+    pub fn sq_distance_to_point(&self, point: &[Real; NDIM]) -> Real {
+        sq_distance_to_point::<Real, NDIM, SIZE_AABB>(&self.aabb, point)
+    }
+
+    pub fn distance_to_point(&self, point: &[Real; NDIM]) -> Real {
+        distance_to_point::<Real, NDIM, SIZE_AABB>(&self.aabb, point)
+    }
+
+    pub fn sq_distance_aabb_aabb(&self, other: &Self) -> Real {
+        sq_distance_aabb_aabb::<Real, NDIM, SIZE_AABB>(&self.aabb, &other.aabb)
+    }
+
+    pub fn is_intersect(&self, other: &Self) -> bool {
+        is_intersect::<Real, NDIM, SIZE_AABB>(&self.aabb, &other.aabb)
+    }
+
+    pub fn longest_axis(&self) -> usize {
+        longest_axis::<Real, NDIM, SIZE_AABB>(&self.aabb)
+    }
+
+    pub fn split_at(&self, axis: usize, value: Real) -> (Self, Self) {
+        let (lower, upper) = split_at::<Real, NDIM, SIZE_AABB>(&self.aabb, axis, value);
+        (Self { aabb: lower }, Self { aabb: upper })
+    }
+
+    /// see [`corners`]
+    pub fn corners<const NCORNER: usize>(&self) -> [[Real; NDIM]; NCORNER] {
+        corners::<Real, NDIM, SIZE_AABB, NCORNER>(&self.aabb)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        intersection::<Real, NDIM, SIZE_AABB>(&self.aabb, &other.aabb).map(|aabb| Self { aabb })
+    }
+
+    pub fn clip_edge(
+        &self,
+        p0: &[Real; NDIM],
+        p1: &[Real; NDIM],
+    ) -> Option<([Real; NDIM], [Real; NDIM])> {
+        clip_edge::<Real, NDIM, SIZE_AABB>(&self.aabb, p0, p1)
+    }
+}
+
+impl<Real, const NDIM: usize, const SIZE_AABB: usize> Default for AABB<Real, NDIM, SIZE_AABB>
+where
+    Real: num_traits::Float,
+{
+    /// empty box. See [`AABB::new_empty`]
+    fn default() -> Self {
+        Self::new_empty()
+    }
+}
+
+impl<Real, const NDIM: usize, const SIZE_AABB: usize> FromIterator<[Real; NDIM]>
+    for AABB<Real, NDIM, SIZE_AABB>
+where
+    Real: num_traits::Float,
+{
+    fn from_iter<I: IntoIterator<Item = [Real; NDIM]>>(iter: I) -> Self {
+        let mut aabb = Self::new_empty();
+        iter.into_iter().for_each(|p| aabb.insert_point(&p));
+        aabb
+    }
+}
+
+#[test]
+fn test_builder() {
+    let ps = [[0.5f64, -1.0], [1.5, 1.0], [1.0, 0.0]];
+    let mut a = AABB::<f64, 2, 4>::default();
+    assert!(!a.is_valid());
+    ps.iter().for_each(|p| a.insert_point(p));
+    assert_eq!(a.aabb, AABB::<f64, 2, 4>::from_points(&ps).aabb);
+    let b: AABB<f64, 2, 4> = ps.iter().map(|p| [p[0] + 3.0, p[1]]).collect();
+    assert_eq!(b.aabb, [3.5, -1.0, 4.5, 1.0]);
+    a.insert_aabb(&b);
+    assert_eq!(a.aabb, [0.5, -1.0, 4.5, 1.0]);
+    a.insert_aabb(&AABB::new_empty());
+    assert_eq!(a.aabb, [0.5, -1.0, 4.5, 1.0]);
+}
+
+#[test]
+fn test_set_operations() {
+    let ps = [[0.5f64, -1.0, 2.0], [1.5, 1.0, -2.0], [1.0, 0.0, 0.0]];
+    let a = AABB::<f64, 3, 6>::from_points(&ps);
+    assert_eq!(a.aabb, [0.5, -1.0, -2.0, 1.5, 1.0, 2.0]);
+    assert!(ps.iter().all(|p| a.is_include_point(p)));
+    assert_eq!(a.volume(), 8.0);
+    assert_eq!(a.surface_area(), 2.0 * (2.0 + 8.0 + 4.0));
+    let b = a.expand(0.5);
+    assert_eq!(b.aabb, [0.0, -1.5, -2.5, 2.0, 1.5, 2.5]);
+    let c = AABB::<f64, 3, 6> {
+        aabb: [3.0, 0.0, 0.0, 4.0, 0.5, 0.5],
+    };
+    assert_eq!(a.union(&c).aabb, [0.5, -1.0, -2.0, 4.0, 1.0, 2.0]);
+    // empty box
+    let e = AABB::<f64, 3, 6>::from_points(&[]);
+    assert!(!e.is_valid());
+    assert_eq!(e.volume(), 0.0);
+    assert_eq!(e.union(&a).aabb, a.aabb);
+    assert_eq!(a.union(&e).aabb, a.aabb);
+    // perimeter and area in 2D
+    let d = from_points::<f64, 2, 4>(&[[0., 0.], [2., 3.]]);
+    assert_eq!(surface_area::<f64, 2, 4>(&d), 10.0);
+    assert_eq!(volume::<f64, 2, 4>(&d), 6.0);
+}
+
+#[test]
+fn test_distance() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let a = AABB::<f64, 3, 6> {
+        aabb: [-1.0, -0.5, 0.0, 1.0, 0.5, 2.0],
+    };
+    assert_eq!(a.distance_to_point(&[0.5, 0.0, 1.0]), 0.0);
+    assert_eq!(a.distance_to_point(&[3.0, 0.0, 1.0]), 2.0);
+    assert_eq!(a.sq_distance_to_point(&[2.0, 1.5, -1.0]), 3.0);
+    for _iter in 0..100 {
+        let p0: [f64; 3] = std::array::from_fn(|_| 6. * rng.random::<f64>() - 3.);
+        let p1: [f64; 3] = std::array::from_fn(|_| 6. * rng.random::<f64>() - 3.);
+        let b = AABB::<f64, 3, 6>::from_points(&[p0, p1]);
+        let d2 = a.sq_distance_aabb_aabb(&b);
+        assert_eq!(d2, b.sq_distance_aabb_aabb(&a));
+        // lower bound of the distance between the points in the boxes
+        for _ in 0..10 {
+            let q: [f64; 3] = std::array::from_fn(|i| {
+                let r = rng.random::<f64>();
+                b.aabb[i] + r * (b.aabb[i + 3] - b.aabb[i])
+            });
+            assert!(d2 <= a.sq_distance_to_point(&q) + 1.0e-12);
+        }
+    }
+}
+
+#[test]
+fn test_intersection() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let mut random_aabb = || {
+            let p0: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+            let p1: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+            AABB::<f64, 2, 4>::from_points(&[p0, p1])
+        };
+        let (a, b) = (random_aabb(), random_aabb());
+        assert_eq!(a.is_intersect(&b), a.sq_distance_aabb_aabb(&b) == 0.0);
+        match a.intersection(&b) {
+            Some(c) => {
+                assert!(c.is_valid());
+                assert_eq!(c.union(&a).aabb, a.aabb);
+                assert_eq!(c.union(&b).aabb, b.aabb);
+            }
+            None => assert!(!a.is_intersect(&b)),
+        }
+    }
+    // touching boxes
+    let a = [0f64, 0., 1., 1.];
+    let b = [1f64, 0.5, 2., 2.];
+    assert_eq!(intersection::<f64, 2, 4>(&a, &b), Some([1., 0.5, 1., 1.]));
+    let e = [1f64, 1., 0., 0.];
+    assert!(!is_intersect::<f64, 2, 4>(&a, &e));
+}
+
+#[test]
+fn test_structural_queries() {
+    let a = AABB::<f64, 3, 6> {
+        aabb: [0.0, -1.0, 0.0, 1.0, 2.0, 2.0],
+    };
+    assert_eq!(a.longest_axis(), 1);
+    let (lower, upper) = a.split_at(1, 0.5);
+    assert_eq!(lower.aabb, [0.0, -1.0, 0.0, 1.0, 0.5, 2.0]);
+    assert_eq!(upper.aabb, [0.0, 0.5, 0.0, 1.0, 2.0, 2.0]);
+    assert_eq!(lower.union(&upper).aabb, a.aabb);
+    assert_eq!(lower.volume() + upper.volume(), a.volume());
+    // value outside the box is clamped
+    let (lower, upper) = a.split_at(0, 3.0);
+    assert_eq!(lower.aabb, a.aabb);
+    assert_eq!(upper.volume(), 0.0);
+    //
+    let cs: [[f64; 3]; 8] = a.corners();
+    assert_eq!(cs[0], [0.0, -1.0, 0.0]);
+    assert_eq!(cs[5], [1.0, -1.0, 2.0]);
+    assert_eq!(cs[7], [1.0, 2.0, 2.0]);
+    assert_eq!(AABB::<f64, 3, 6>::from_points(&cs).aabb, a.aabb);
+    // the same set of corners as `xyz_from_hex_index`
+    for i_vtx in 0..8 {
+        let p = crate::aabb3::xyz_from_hex_index(&a.aabb, i_vtx);
+        assert!(cs.contains(&p));
+    }
+    let cs: [[f64; 2]; 4] = corners::<f64, 2, 4, 4>(&[0., 1., 2., 3.]);
+    assert_eq!(cs, [[0., 1.], [2., 1.], [0., 3.], [2., 3.]]);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck() {
+    let aabbs = [
+        AABB::<f32, 2, 4> {
+            aabb: [0., 1., 2., 3.],
+        },
+        AABB::<f32, 2, 4> {
+            aabb: [4., 5., 6., 7.],
+        },
+    ];
+    let s: &[f32] = bytemuck::cast_slice(&aabbs);
+    assert_eq!(s, &[0., 1., 2., 3., 4., 5., 6., 7.]);
 }
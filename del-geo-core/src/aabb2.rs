@@ -36,6 +36,14 @@ where
     o
 }
 
+/// the smallest box containing the points. Invalid (empty) box if there is no point.
+pub fn from_points<'a, T>(points: impl IntoIterator<Item = &'a [T; 2]>) -> [T; 4]
+where
+    T: num_traits::Float + 'a,
+{
+    crate::aabb::from_points::<T, 2, 4>(points)
+}
+
 // above: from method
 // -----------------------
 
@@ -50,6 +58,86 @@ where
     aabb2[3] = aabb2[3].max(p1 + rad);
 }
 
+/// `min <= max` for both the axes
+pub fn is_valid<T>(aabb: &[T; 4]) -> bool
+where
+    T: num_traits::Float,
+{
+    crate::aabb::is_valid::<T, 2, 4>(aabb)
+}
+
+/// the smallest box containing the two boxes. Unlike [`from_two_aabbs`], an invalid box is ignored.
+pub fn union<T>(a: &[T; 4], b: &[T; 4]) -> [T; 4]
+where
+    T: num_traits::Float,
+{
+    crate::aabb::union::<T, 2, 4>(a, b)
+}
+
+pub fn expand<T>(aabb: &[T; 4], margin: T) -> [T; 4]
+where
+    T: num_traits::Float,
+{
+    crate::aabb::expand::<T, 2, 4>(aabb, margin)
+}
+
+/// area of the box (the 2D counterpart of the volume). Zero for the invalid box.
+pub fn area<T>(aabb: &[T; 4]) -> T
+where
+    T: num_traits::Float,
+{
+    crate::aabb::volume::<T, 2, 4>(aabb)
+}
+
+/// perimeter of the box (the 2D counterpart of the surface area). Zero for the invalid box.
+pub fn perimeter<T>(aabb: &[T; 4]) -> T
+where
+    T: num_traits::Float,
+{
+    crate::aabb::surface_area::<T, 2, 4>(aabb)
+}
+
+/// the two boxes overlap (touching boundaries count as overlap)
+pub fn is_intersect<T>(a: &[T; 4], b: &[T; 4]) -> bool
+where
+    T: num_traits::Float,
+{
+    crate::aabb::is_intersect::<T, 2, 4>(a, b)
+}
+
+/// the box shared by the two boxes (e.g., clipping to the viewport tile).
+/// `None` if they do not overlap
+pub fn intersection<T>(a: &[T; 4], b: &[T; 4]) -> Option<[T; 4]>
+where
+    T: num_traits::Float,
+{
+    crate::aabb::intersection::<T, 2, 4>(a, b)
+}
+
+/// clip the segment inside the AABB. See [`crate::aabb::clip_edge`]
+pub fn clip_edge<T>(aabb: &[T; 4], p0: &[T; 2], p1: &[T; 2]) -> Option<([T; 2], [T; 2])>
+where
+    T: num_traits::Float,
+{
+    crate::aabb::clip_edge::<T, 2, 4>(aabb, p0, p1)
+}
+
+#[test]
+fn test_set_operations() {
+    let a = from_points(&[[0f64, 1.], [2., -1.], [1., 3.]]);
+    assert_eq!(a, [0., -1., 2., 3.]);
+    assert_eq!(area(&a), 8.0);
+    assert_eq!(perimeter(&a), 12.0);
+    assert_eq!(expand(&a, 0.5), [-0.5, -1.5, 2.5, 3.5]);
+    let b = [3., 3., 4., 4.];
+    assert_eq!(union(&a, &b), from_two_aabbs(&a, &b));
+    assert!(!is_valid(&from_points::<f64>(&[])));
+    assert_eq!(union(&from_points(&[]), &b), b);
+    assert!(!is_intersect(&a, &b));
+    assert_eq!(intersection(&a, &b), None);
+    assert_eq!(intersection(&a, &[1., 1., 4., 4.]), Some([1., 1., 2., 3.]));
+}
+
 pub fn rasterize<T>(aabb: &[T; 4], img_size: &(usize, usize)) -> [usize; 4]
 where
     T: num_traits::Float + num_traits::AsPrimitive<usize> + 'static + Copy,
@@ -0,0 +1,134 @@
+//! methods for the 2D integer rectangle in the pixel space
+//! the data structure `[i32;4] = [min_x, min_y, max_x, max_y]` with the exclusive max,
+//! i.e., the pixel `(ix, iy)` is in the rectangle if `min_x <= ix < max_x` and `min_y <= iy < max_y`.
+//! The pixel `(ix, iy)` covers the square `[ix, ix+1] x [iy, iy+1]` in the pixel coordinate
+//! (see [`crate::mat4_col_major::from_transform_ndc2pix`]).
+
+/// rectangle from the inclusive range of pixels `[min_x, max_x] x [min_y, max_y]`
+pub fn from_inclusive(min_x: i32, min_y: i32, max_x: i32, max_y: i32) -> [i32; 4] {
+    [min_x, min_y, max_x + 1, max_y + 1]
+}
+
+/// smallest rectangle of the pixels whose squares overlap the floating-point AABB.
+/// The max is rounded up with `ceil`, so a box ending exactly at the pixel boundary does not include the next pixel.
+pub fn from_aabb2_outer<T>(aabb: &[T; 4]) -> [i32; 4]
+where
+    T: num_traits::Float + num_traits::AsPrimitive<i32>,
+{
+    [
+        aabb[0].floor().as_(),
+        aabb[1].floor().as_(),
+        aabb[2].ceil().as_(),
+        aabb[3].ceil().as_(),
+    ]
+}
+
+/// rectangle of the pixels whose centers `(ix+0.5, iy+0.5)` are inside the floating-point AABB.
+/// This is the same as [`crate::aabb2::rasterize`] without the clamping to the image.
+pub fn from_aabb2_pixel_centers<T>(aabb: &[T; 4]) -> [i32; 4]
+where
+    T: num_traits::Float + num_traits::AsPrimitive<i32>,
+{
+    let half = T::one() / (T::one() + T::one());
+    [
+        (aabb[0] - half).ceil().as_(),
+        (aabb[1] - half).ceil().as_(),
+        (aabb[2] - half).ceil().as_(),
+        (aabb[3] - half).ceil().as_(),
+    ]
+}
+
+// above: from method
+// -----------------------
+
+/// inclusive range of pixels `[min_x, min_y, max_x, max_y]`. `None` if the rectangle is empty.
+pub fn to_inclusive(rect: &[i32; 4]) -> Option<[i32; 4]> {
+    if is_empty(rect) {
+        return None;
+    }
+    Some([rect[0], rect[1], rect[2] - 1, rect[3] - 1])
+}
+
+pub fn is_empty(rect: &[i32; 4]) -> bool {
+    rect[0] >= rect[2] || rect[1] >= rect[3]
+}
+
+pub fn width(rect: &[i32; 4]) -> usize {
+    (rect[2] - rect[0]).max(0) as usize
+}
+
+pub fn height(rect: &[i32; 4]) -> usize {
+    (rect[3] - rect[1]).max(0) as usize
+}
+
+pub fn num_pixels(rect: &[i32; 4]) -> usize {
+    width(rect) * height(rect)
+}
+
+pub fn is_include_pixel(rect: &[i32; 4], &[ix, iy]: &[i32; 2]) -> bool {
+    rect[0] <= ix && ix < rect[2] && rect[1] <= iy && iy < rect[3]
+}
+
+/// pixels shared by the two rectangles. The result may be empty.
+pub fn intersection(a: &[i32; 4], b: &[i32; 4]) -> [i32; 4] {
+    [
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    ]
+}
+
+/// clamp the rectangle inside the image of `img_shape = (width, height)`
+pub fn clamp_to_image(rect: &[i32; 4], img_shape: (usize, usize)) -> [i32; 4] {
+    intersection(rect, &[0, 0, img_shape.0 as i32, img_shape.1 as i32])
+}
+
+/// iterate the pixels `[ix, iy]` in the rectangle in the row-major order (x is the inner loop)
+pub fn pixels(rect: &[i32; 4]) -> impl Iterator<Item = [i32; 2]> + use<> {
+    let [x0, y0, x1, y1] = *rect;
+    (y0..y1).flat_map(move |iy| (x0..x1).map(move |ix| [ix, iy]))
+}
+
+/// iterate the indices `iy * width + ix` of the pixels in the rectangle clamped inside the image
+pub fn pixel_indices(
+    rect: &[i32; 4],
+    img_shape: (usize, usize),
+) -> impl Iterator<Item = usize> + use<> {
+    let rect = clamp_to_image(rect, img_shape);
+    pixels(&rect).map(move |[ix, iy]| iy as usize * img_shape.0 + ix as usize)
+}
+
+#[test]
+fn test_aabb2_i() {
+    let rect = from_aabb2_outer(&[-1.5f32, 0.2, 3.0, 2.7]);
+    assert_eq!(rect, [-2, 0, 3, 3]);
+    assert_eq!(num_pixels(&rect), 15);
+    assert_eq!(to_inclusive(&rect), Some([-2, 0, 2, 2]));
+    assert_eq!(from_inclusive(-2, 0, 2, 2), rect);
+    assert!(is_include_pixel(&rect, &[2, 2]));
+    assert!(!is_include_pixel(&rect, &[3, 2]));
+    let rect = clamp_to_image(&rect, (2, 8));
+    assert_eq!(rect, [0, 0, 2, 3]);
+    assert_eq!(
+        pixels(&rect).collect::<Vec<_>>(),
+        [[0, 0], [1, 0], [0, 1], [1, 1], [0, 2], [1, 2]]
+    );
+    let idxs: Vec<usize> = pixel_indices(&[-2, 1, 3, 3], (2, 8)).collect();
+    assert_eq!(idxs, [2, 3, 4, 5]);
+    // empty rectangle
+    let e = intersection(&[0, 0, 2, 2], &[3, 0, 4, 2]);
+    assert!(is_empty(&e));
+    assert_eq!(to_inclusive(&e), None);
+    assert_eq!(num_pixels(&e), 0);
+    assert_eq!(pixels(&e).count(), 0);
+    // consistent with `aabb2::rasterize` inside the image
+    let aabb = [0.3f32, 1.6, 4.5, 3.4];
+    let r0 = from_aabb2_pixel_centers(&aabb);
+    let r1 = crate::aabb2::rasterize(&aabb, &(10, 10));
+    assert_eq!(r0, r1.map(|v| v as i32));
+    assert!(pixels(&r0).all(|[ix, iy]| {
+        let c = [ix as f32 + 0.5, iy as f32 + 0.5];
+        crate::aabb2::is_include_point2(&aabb, &c)
+    }));
+}
@@ -14,6 +14,13 @@ pub trait AABB3Trait<T> {
     fn max_edge_size(&self) -> T;
     fn is_active(&self) -> bool;
     fn is_intersect(&self, i1: &Self) -> bool;
+    fn intersection(&self, i1: &Self) -> Option<Self>
+    where
+        Self: Sized;
+    fn is_valid(&self) -> bool;
+    fn union(&self, other: &Self) -> Self;
+    fn expand(&self, margin: T) -> Self;
+    fn surface_area(&self) -> T;
     fn set_as_cube(&mut self, xyz: &[T; 3], eps: T);
     fn add_point(&mut self, xyz: &[T; 3], eps: T);
     fn is_possible_distance_to_aabb2_smaller_than_threshold(
@@ -58,6 +65,21 @@ where
     fn is_intersect(&self, i1: &Self) -> bool {
         is_intersect(self, i1)
     }
+    fn intersection(&self, i1: &Self) -> Option<Self> {
+        intersection(self, i1)
+    }
+    fn is_valid(&self) -> bool {
+        is_valid(self)
+    }
+    fn union(&self, other: &Self) -> Self {
+        union(self, other)
+    }
+    fn expand(&self, margin: T) -> Self {
+        expand(self, margin)
+    }
+    fn surface_area(&self) -> T {
+        surface_area(self)
+    }
     fn set_as_cube(&mut self, xyz: &[T; 3], eps: T) {
         set_as_cube(self, xyz, eps)
     }
@@ -91,6 +113,88 @@ where
     o
 }
 
+/// the smallest box containing the points. Invalid (empty) box if there is no point.
+pub fn from_points<'a, T>(points: impl IntoIterator<Item = &'a [T; 3]>) -> [T; 6]
+where
+    T: num_traits::Float + 'a,
+{
+    crate::aabb::from_points::<T, 3, 6>(points)
+}
+
+/// box enclosing the trajectories of the `N` points moving linearly from `ps_start` to `ps_end`.
+/// Since the trajectory of a point in the primitive is the blend of the trajectories of its vertices,
+/// the box of the vertices at both ends of the motion encloses the whole swept volume.
+fn from_moving_points<T, const N: usize>(
+    ps_start: &[[T; 3]; N],
+    ps_end: &[[T; 3]; N],
+    margin: T,
+) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let aabb = from_points(ps_start.iter().chain(ps_end.iter()));
+    crate::aabb::expand::<T, 3, 6>(&aabb, margin)
+}
+
+/// box enclosing the point moving linearly from `p_start` to `p_end` (e.g., for the CCD broad phase)
+pub fn from_moving_point<T>(p_start: &[T; 3], p_end: &[T; 3], margin: T) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    from_moving_points(&[*p_start], &[*p_end], margin)
+}
+
+/// box enclosing the edge whose end points move linearly
+pub fn from_moving_edge<T>(ps_start: &[[T; 3]; 2], ps_end: &[[T; 3]; 2], margin: T) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    from_moving_points(ps_start, ps_end, margin)
+}
+
+/// box enclosing the triangle whose corner points move linearly
+pub fn from_moving_triangle<T>(ps_start: &[[T; 3]; 3], ps_end: &[[T; 3]; 3], margin: T) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    from_moving_points(ps_start, ps_end, margin)
+}
+
+#[test]
+fn test_from_moving_triangle() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_tri = || -> [[f64; 3]; 3] {
+        std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.))
+    };
+    for _iter in 0..100 {
+        let (s, e) = (random_tri(), random_tri());
+        let aabb = from_moving_triangle(&s, &e, 0.1);
+        let aabb_edge = from_moving_edge(&[s[0], s[1]], &[e[0], e[1]], 0.1);
+        let aabb_point = from_moving_point(&s[0], &e[0], 0.1);
+        for i in 0..=10 {
+            let t = i as f64 / 10.;
+            let p: [[f64; 3]; 3] = std::array::from_fn(|k| crate::vec3::lerp(&s[k], &e[k], t));
+            let q = crate::tri3::position_from_barycentric_coords(
+                &p[0],
+                &p[1],
+                &p[2],
+                &[0.2, 0.3, 0.5],
+            );
+            assert!(crate::aabb::is_include_point::<f64, 3, 6>(&aabb, &q));
+            let q = crate::vec3::lerp(&p[0], &p[1], 0.7);
+            assert!(crate::aabb::is_include_point::<f64, 3, 6>(&aabb_edge, &q));
+            assert!(crate::aabb::is_include_point::<f64, 3, 6>(
+                &aabb_point,
+                &p[0]
+            ));
+        }
+        let aabb_point0 = from_points(&[s[0], e[0]]);
+        assert!((0..6).all(|i| ((aabb_point[i] - aabb_point0[i]).abs() - 0.1).abs() < 1.0e-10));
+    }
+}
+
 // Above: from method
 // ----------------------------------
 // Below: to method
@@ -140,6 +244,37 @@ where
     (aabb[3] - aabb[0]) * (aabb[4] - aabb[1]) * (aabb[5] - aabb[2])
 }
 
+/// area of the six faces
+pub fn surface_area<T>(aabb: &[T; 6]) -> T
+where
+    T: num_traits::Float,
+{
+    crate::aabb::surface_area::<T, 3, 6>(aabb)
+}
+
+/// `min <= max` for all the axes. See also [`is_active`] checking only the x-axis.
+pub fn is_valid<T>(aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    crate::aabb::is_valid::<T, 3, 6>(aabb)
+}
+
+/// the smallest box containing the two boxes. Unlike [`from_two_aabbs`], an invalid box is ignored.
+pub fn union<T>(a: &[T; 6], b: &[T; 6]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    crate::aabb::union::<T, 3, 6>(a, b)
+}
+
+pub fn expand<T>(aabb: &[T; 6], margin: T) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    crate::aabb::expand::<T, 3, 6>(aabb, margin)
+}
+
 pub fn xyz_from_hex_index<Real>(aabb: &[Real; 6], i_vtx: usize) -> [Real; 3]
 where
     Real: num_traits::Float,
@@ -180,37 +315,118 @@ where
     i0[0] <= i0[3]
 }
 
+/// the two boxes overlap (touching boundaries count as overlap).
+/// Always false if either box is invalid. See [`crate::aabb::is_intersect`]
 pub fn is_intersect<T>(i0: &[T; 6], i1: &[T; 6]) -> bool
 where
-    T: PartialOrd,
+    T: num_traits::Float,
 {
-    assert_eq!(i0.len(), 6);
-    assert_eq!(i1.len(), 6);
-    if !is_active(i0) {
-        return false;
-    }
-    if !is_active(i1) {
-        return false;
-    }
-    if i0[0] > i1[3] {
-        return false;
-    }
-    if i0[1] > i1[4] {
-        return false;
-    }
-    if i0[2] > i1[5] {
-        return false;
-    }
-    if i0[3] < i1[0] {
-        return false;
+    crate::aabb::is_intersect::<T, 3, 6>(i0, i1)
+}
+
+/// the box shared by the two boxes. `None` if they do not overlap
+pub fn intersection<T>(i0: &[T; 6], i1: &[T; 6]) -> Option<[T; 6]>
+where
+    T: num_traits::Float,
+{
+    crate::aabb::intersection::<T, 3, 6>(i0, i1)
+}
+
+/// clip the segment inside the AABB. See [`crate::aabb::clip_edge`]
+pub fn clip_edge<T>(aabb: &[T; 6], p0: &[T; 3], p1: &[T; 3]) -> Option<([T; 3], [T; 3])>
+where
+    T: num_traits::Float,
+{
+    crate::aabb::clip_edge::<T, 3, 6>(aabb, p0, p1)
+}
+
+#[test]
+fn test_intersection() {
+    let a = [0f64, 0., 0., 2., 2., 2.];
+    let b = [1f64, -1., 1.5, 3., 1., 4.];
+    assert!(a.is_intersect(&b));
+    assert_eq!(a.intersection(&b), Some([1., 0., 1.5, 2., 1., 2.]));
+    let c = [2.5f64, 0., 0., 3., 1., 1.];
+    assert!(!a.is_intersect(&c));
+    assert_eq!(a.intersection(&c), None);
+    // valid on x but invalid on y
+    let d = [0f64, 1., 0., 2., 0., 2.];
+    assert!(!is_intersect(&a, &d));
+    assert_eq!(is_intersect(&a, &d), intersection(&a, &d).is_some());
+}
+
+/// side of the plane where the AABB is. See [`classify_against_plane`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaneSide {
+    /// entirely in the positive side of the plane
+    Front,
+    /// entirely in the negative side of the plane
+    Back,
+    /// crossing or touching the plane
+    Straddling,
+}
+
+/// classify the AABB against the plane `[a, b, c, d]` where `a*x + b*y + c*z + d = 0`,
+/// by comparing the signed distance of the center with the projected radius of the box.
+/// The normal `[a, b, c]` does not need to be unit.
+pub fn classify_against_plane<T>(aabb: &[T; 6], plane: &[T; 4]) -> PlaneSide
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    let mut s = plane[3];
+    let mut r = T::zero();
+    for i in 0..3 {
+        let c = (aabb[i] + aabb[i + 3]) * half;
+        let e = (aabb[i + 3] - aabb[i]) * half;
+        s = s + plane[i] * c;
+        r = r + plane[i].abs() * e;
     }
-    if i0[4] < i1[1] {
-        return false;
+    if s > r {
+        PlaneSide::Front
+    } else if s < -r {
+        PlaneSide::Back
+    } else {
+        PlaneSide::Straddling
     }
-    if i0[5] < i1[2] {
-        return false;
+}
+
+#[test]
+fn test_classify_against_plane() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..1000 {
+        let aabb = from_points(&[
+            std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.),
+            std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.),
+        ]);
+        let plane: [f64; 4] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let dists = (0..8).map(|i| {
+            let p = xyz_from_hex_index(&aabb, i);
+            plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3]
+        });
+        let (d_min, d_max) = dists.fold((f64::MAX, f64::MIN), |(a, b), d| (a.min(d), b.max(d)));
+        let side = match (d_min > 0., d_max < 0.) {
+            (true, _) => PlaneSide::Front,
+            (_, true) => PlaneSide::Back,
+            _ => PlaneSide::Straddling,
+        };
+        assert_eq!(classify_against_plane(&aabb, &plane), side);
     }
-    true
+    let aabb = [0f64, 0., 0., 1., 1., 1.];
+    assert_eq!(
+        classify_against_plane(&aabb, &[1., 0., 0., 1.]),
+        PlaneSide::Front
+    );
+    assert_eq!(
+        classify_against_plane(&aabb, &[0., -2., 0., -2.1]),
+        PlaneSide::Back
+    );
+    assert_eq!(
+        classify_against_plane(&aabb, &[0., 0., 1., -1.]),
+        PlaneSide::Straddling
+    );
 }
 
 /// return a vec3 sampled inside a aabb
@@ -283,10 +499,101 @@ where
     true
 }
 
+/// tight AABB of the AABB transformed by the 3x4 affine matrix (column major)
+/// using the method of Arvo, "Transforming Axis-Aligned Bounding Boxes", Graphics Gems (1990)
+pub fn transformed_by_mat3x4_col_major<Real>(aabb: &[Real; 6], m: &[Real; 12]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let mut o = [m[9], m[10], m[11], m[9], m[10], m[11]];
+    for i in 0..3 {
+        for j in 0..3 {
+            let a = m[i + 3 * j] * aabb[j];
+            let b = m[i + 3 * j] * aabb[j + 3];
+            o[i] = o[i] + a.min(b);
+            o[i + 3] = o[i + 3] + a.max(b);
+        }
+    }
+    o
+}
+
+/// AABB of the AABB transformed by the 4x4 homogeneous matrix (column major).
+/// If the matrix is affine, the result is computed with Arvo's method.
+/// Otherwise, the eight corners are projectively transformed,
+/// which is valid only if all the corners are in front of the projection plane `w=0`.
+pub fn transformed<Real>(aabb: &[Real; 6], m: &[Real; 16]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    if m[3] == zero && m[7] == zero && m[11] == zero && m[15] == Real::one() {
+        let m = crate::mat3x4_col_major::from_mat4_col_major(m);
+        return transformed_by_mat3x4_col_major(aabb, &m);
+    }
+    let mut o = [
+        Real::infinity(),
+        Real::infinity(),
+        Real::infinity(),
+        Real::neg_infinity(),
+        Real::neg_infinity(),
+        Real::neg_infinity(),
+    ];
+    for i_vtx in 0..8 {
+        let p = xyz_from_hex_index(aabb, i_vtx);
+        if let Some(q) = crate::mat4_col_major::transform_homogeneous(m, &p) {
+            add_point(&mut o, &q, zero);
+        }
+    }
+    o
+}
+
+#[test]
+fn test_transformed() {
+    use crate::mat4_col_major::Mat4ColMajor;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [-1.0f64, 0.5, 0.3, 2.0, 1.5, 0.9];
+    for _iter in 0..100 {
+        let r: [f64; 9] = crate::mat3_col_major::sample_uniform_rotation(&mut rng);
+        let mut m = crate::mat4_col_major::from_mat3_col_major_adding_w(&r, 1.0);
+        m[12] = 0.3;
+        m[13] = -1.2;
+        m[14] = 2.0;
+        let aabb1 = transformed(&aabb, &m);
+        // the corners are inside and the box touches the corners
+        let mut aabb2 = [f64::MAX, f64::MAX, f64::MAX, f64::MIN, f64::MIN, f64::MIN];
+        for i_vtx in 0..8 {
+            let p = m
+                .transform_homogeneous(&xyz_from_hex_index(&aabb, i_vtx))
+                .unwrap();
+            add_point(&mut aabb2, &p, 0.);
+        }
+        for i in 0..6 {
+            assert!((aabb1[i] - aabb2[i]).abs() < 1.0e-10);
+        }
+    }
+}
+
+#[test]
+fn test_set_operations() {
+    let a = from_points(&[[0f64, 0., 0.], [1., 2., 3.]]);
+    assert!(a.is_valid());
+    assert_eq!(a.surface_area(), 22.0);
+    assert_eq!(a.expand(1.0).volume(), 60.0);
+    let b = [-1., -1., -1., 0.5, 0.5, 0.5];
+    assert_eq!(a.union(&b), from_two_aabbs(&a, &b));
+    let e = from_points::<f64>(&[]);
+    assert!(!e.is_valid());
+    assert_eq!(e.union(&a), a);
+}
+
 // --------------------------
 
 pub type AABB3<Real> = crate::aabb::AABB<Real, 3, 6>;
 
+/// 3D ray for [`crate::aabb::intersections_against_ray_precomputed`]
+pub type PrecomputedRay3<Real> = crate::aabb::PrecomputedRay<Real, 3>;
+
 pub fn from_slice<Real>(s: &[Real]) -> AABB3<Real>
 where
     Real: num_traits::Float,
@@ -0,0 +1,310 @@
+//! batched operations over the slices of points.
+//! The points are processed in the blocks of four lanes in the SoA (structure of arrays) layout
+//! so that the compiler can vectorize the arithmetic.
+
+/// number of the points processed at once
+const LANE: usize = 4;
+
+/// transpose a block of points into the SoA layout `[xs, ys, zs]`
+fn to_soa(chunk: &[[f32; 3]]) -> [[f32; LANE]; 3] {
+    std::array::from_fn(|i_dim| std::array::from_fn(|i_lane| chunk[i_lane][i_dim]))
+}
+
+/// apply the 4x4 homogeneous transformation (column major) to each point in place.
+/// Unlike [`crate::mat4_col_major::transform_homogeneous`],
+/// the point is not checked for `w=0` and becomes non-finite there.
+pub fn transform_points_mat4(points: &mut [[f32; 3]], m: &[f32; 16]) {
+    let mut chunks = points.chunks_exact_mut(LANE);
+    for chunk in chunks.by_ref() {
+        let [x, y, z] = to_soa(chunk);
+        let row = |i: usize| -> [f32; LANE] {
+            std::array::from_fn(|k| m[i] * x[k] + m[i + 4] * y[k] + m[i + 8] * z[k] + m[i + 12])
+        };
+        let (y0, y1, y2, y3) = (row(0), row(1), row(2), row(3));
+        let winv: [f32; LANE] = std::array::from_fn(|k| 1. / y3[k]);
+        for k in 0..LANE {
+            chunk[k] = [y0[k] * winv[k], y1[k] * winv[k], y2[k] * winv[k]];
+        }
+    }
+    for p in chunks.into_remainder() {
+        let row = |i: usize| m[i] * p[0] + m[i + 4] * p[1] + m[i + 8] * p[2] + m[i + 12];
+        let winv = 1. / row(3);
+        *p = [row(0) * winv, row(1) * winv, row(2) * winv];
+    }
+}
+
+/// multiply the 3x3 matrix (column major) to each point in place
+pub fn transform_points_mat3(points: &mut [[f32; 3]], m: &[f32; 9]) {
+    let mut chunks = points.chunks_exact_mut(LANE);
+    for chunk in chunks.by_ref() {
+        let [x, y, z] = to_soa(chunk);
+        let row = |i: usize| -> [f32; LANE] {
+            std::array::from_fn(|k| m[i] * x[k] + m[i + 3] * y[k] + m[i + 6] * z[k])
+        };
+        let (y0, y1, y2) = (row(0), row(1), row(2));
+        for k in 0..LANE {
+            chunk[k] = [y0[k], y1[k], y2[k]];
+        }
+    }
+    for p in chunks.into_remainder() {
+        *p = crate::mat3_col_major::mult_vec(m, p);
+    }
+}
+
+/// AABB `[min_x, min_y, min_z, max_x, max_y, max_z]` of the points.
+/// Returns an inactive AABB (see [`crate::aabb3::is_active`]) if the slice is empty.
+pub fn aabb3_from_points(points: &[[f32; 3]]) -> [f32; 6] {
+    let mut lo = [[f32::MAX; LANE]; 3];
+    let mut hi = [[f32::MIN; LANE]; 3];
+    let mut chunks = points.chunks_exact(LANE);
+    for chunk in chunks.by_ref() {
+        let xyz = to_soa(chunk);
+        for i_dim in 0..3 {
+            for k in 0..LANE {
+                lo[i_dim][k] = lo[i_dim][k].min(xyz[i_dim][k]);
+                hi[i_dim][k] = hi[i_dim][k].max(xyz[i_dim][k]);
+            }
+        }
+    }
+    let mut aabb = [f32::MAX, f32::MAX, f32::MAX, f32::MIN, f32::MIN, f32::MIN];
+    for i_dim in 0..3 {
+        for k in 0..LANE {
+            aabb[i_dim] = aabb[i_dim].min(lo[i_dim][k]);
+            aabb[i_dim + 3] = aabb[i_dim + 3].max(hi[i_dim][k]);
+        }
+    }
+    for p in chunks.remainder() {
+        crate::aabb3::add_point(&mut aabb, p, 0.);
+    }
+    aabb
+}
+
+/// the edge nearest to the point `p`.
+/// * Returns `(i_edge, dist, ratio)` or `None` if the slice is empty.
+///   See [`crate::edge3::nearest_to_point3`] for `dist` and `ratio`
+pub fn nearest_edge3_to_point3(edges: &[[[f32; 3]; 2]], p: &[f32; 3]) -> Option<(usize, f32, f32)> {
+    edges
+        .iter()
+        .enumerate()
+        .map(|(i_edge, e)| {
+            let (dist, ratio) = crate::edge3::nearest_to_point3(&e[0], &e[1], p);
+            (i_edge, dist, ratio)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// the triangle nearest to the point `p`.
+/// * Returns `(i_tri, dist, pos)` or `None` if the slice is empty,
+///   where `pos` is the nearest position on the triangle
+pub fn nearest_tri3_to_point3(
+    tris: &[[[f32; 3]; 3]],
+    p: &[f32; 3],
+) -> Option<(usize, f32, [f32; 3])> {
+    tris.iter()
+        .enumerate()
+        .map(|(i_tri, t)| {
+            let (pos, _r0, _r1) = crate::tri3::nearest_to_point3(&t[0], &t[1], &t[2], p);
+            (i_tri, crate::vec3::distance(&pos, p), pos)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+// ---------------------------------
+// below: parallel versions
+
+/// number of the points processed in a task of the parallel versions
+#[cfg(feature = "parallel")]
+const CHUNK_SIZE_PARALLEL: usize = 4096;
+
+/// parallel version of [`transform_points_mat4`]
+#[cfg(feature = "parallel")]
+pub fn par_transform_points_mat4(points: &mut [[f32; 3]], m: &[f32; 16]) {
+    use rayon::prelude::*;
+    points
+        .par_chunks_mut(CHUNK_SIZE_PARALLEL)
+        .for_each(|chunk| transform_points_mat4(chunk, m));
+}
+
+/// parallel version of [`transform_points_mat3`]
+#[cfg(feature = "parallel")]
+pub fn par_transform_points_mat3(points: &mut [[f32; 3]], m: &[f32; 9]) {
+    use rayon::prelude::*;
+    points
+        .par_chunks_mut(CHUNK_SIZE_PARALLEL)
+        .for_each(|chunk| transform_points_mat3(chunk, m));
+}
+
+/// parallel version of [`aabb3_from_points`]
+#[cfg(feature = "parallel")]
+pub fn par_aabb3_from_points(points: &[[f32; 3]]) -> [f32; 6] {
+    use rayon::prelude::*;
+    points
+        .par_chunks(CHUNK_SIZE_PARALLEL)
+        .map(aabb3_from_points)
+        .reduce(
+            || [f32::MAX, f32::MAX, f32::MAX, f32::MIN, f32::MIN, f32::MIN],
+            |a, b| crate::aabb3::from_two_aabbs(&a, &b),
+        )
+}
+
+/// parallel version of [`nearest_edge3_to_point3`]
+#[cfg(feature = "parallel")]
+pub fn par_nearest_edge3_to_point3(
+    edges: &[[[f32; 3]; 2]],
+    p: &[f32; 3],
+) -> Option<(usize, f32, f32)> {
+    use rayon::prelude::*;
+    edges
+        .par_iter()
+        .enumerate()
+        .map(|(i_edge, e)| {
+            let (dist, ratio) = crate::edge3::nearest_to_point3(&e[0], &e[1], p);
+            (i_edge, dist, ratio)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+}
+
+/// parallel version of [`nearest_tri3_to_point3`]
+#[cfg(feature = "parallel")]
+pub fn par_nearest_tri3_to_point3(
+    tris: &[[[f32; 3]; 3]],
+    p: &[f32; 3],
+) -> Option<(usize, f32, [f32; 3])> {
+    use rayon::prelude::*;
+    tris.par_iter()
+        .enumerate()
+        .map(|(i_tri, t)| {
+            let (pos, _r0, _r1) = crate::tri3::nearest_to_point3(&t[0], &t[1], &t[2], p);
+            (i_tri, crate::vec3::distance(&pos, p), pos)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+}
+
+#[test]
+fn test_batch() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    use crate::mat4_col_major::{DepthRange, Handedness};
+    // the points are in front of the camera after the translation
+    let m4 = crate::mat4_col_major::mult_mat_col_major(
+        &crate::mat4_col_major::perspective_fov(
+            1.0,
+            1.3,
+            0.5,
+            10.,
+            Handedness::Right,
+            DepthRange::NegOneToOne,
+        ),
+        &crate::mat4_col_major::from_translate(&[0.1, -0.2, -3.0]),
+    );
+    let m3: [f32; 9] = std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.);
+    for num_point in [0, 1, 3, 4, 7, 100] {
+        let points: Vec<[f32; 3]> = (0..num_point)
+            .map(|_| std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.))
+            .collect();
+        {
+            let mut ps = points.clone();
+            transform_points_mat4(&mut ps, &m4);
+            for (p, q) in points.iter().zip(ps.iter()) {
+                let q0 = crate::mat4_col_major::transform_homogeneous(&m4, p).unwrap();
+                assert!(crate::vec3::distance(q, &q0) < 1.0e-5);
+            }
+        }
+        {
+            let mut ps = points.clone();
+            transform_points_mat3(&mut ps, &m3);
+            for (p, q) in points.iter().zip(ps.iter()) {
+                let q0 = crate::mat3_col_major::mult_vec(&m3, p);
+                assert!(crate::vec3::distance(q, &q0) < 1.0e-5);
+            }
+        }
+        {
+            let aabb = aabb3_from_points(&points);
+            assert_eq!(crate::aabb3::is_active(&aabb), num_point > 0);
+            let mut aabb0 = [f32::MAX, f32::MAX, f32::MAX, f32::MIN, f32::MIN, f32::MIN];
+            points
+                .iter()
+                .for_each(|p| crate::aabb3::add_point(&mut aabb0, p, 0.));
+            assert_eq!(aabb, aabb0);
+        }
+    }
+}
+
+#[test]
+fn test_nearest_to_point3() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_vec = || -> [f32; 3] { std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.) };
+    let edges: Vec<[[f32; 3]; 2]> = (0..100).map(|_| [random_vec(), random_vec()]).collect();
+    let tris: Vec<[[f32; 3]; 3]> = (0..100)
+        .map(|_| [random_vec(), random_vec(), random_vec()])
+        .collect();
+    assert!(nearest_edge3_to_point3(&[], &[0.; 3]).is_none());
+    assert!(nearest_tri3_to_point3(&[], &[0.; 3]).is_none());
+    for _itr in 0..10 {
+        let p = random_vec();
+        {
+            let (i_edge, dist, ratio) = nearest_edge3_to_point3(&edges, &p).unwrap();
+            let e = &edges[i_edge];
+            assert_eq!(
+                crate::edge3::nearest_to_point3(&e[0], &e[1], &p),
+                (dist, ratio)
+            );
+            assert!(
+                edges
+                    .iter()
+                    .all(|e| crate::edge3::nearest_to_point3(&e[0], &e[1], &p).0 >= dist)
+            );
+        }
+        {
+            let (i_tri, dist, pos) = nearest_tri3_to_point3(&tris, &p).unwrap();
+            let t = &tris[i_tri];
+            assert_eq!(
+                crate::tri3::nearest_to_point3(&t[0], &t[1], &t[2], &p).0,
+                pos
+            );
+            assert!(tris.iter().all(|t| {
+                let pos = crate::tri3::nearest_to_point3(&t[0], &t[1], &t[2], &p).0;
+                crate::vec3::distance(&pos, &p) >= dist
+            }));
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_vec = || -> [f32; 3] { std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.) };
+    let points: Vec<[f32; 3]> = (0..10000).map(|_| random_vec()).collect();
+    let m4 = crate::mat4_col_major::from_translate(&[0.1, -0.2, 0.3]);
+    let m3 = crate::mat3_col_major::from_diagonal(&[1.0, 2.0, -1.0]);
+    {
+        let (mut ps0, mut ps1) = (points.clone(), points.clone());
+        transform_points_mat4(&mut ps0, &m4);
+        par_transform_points_mat4(&mut ps1, &m4);
+        assert_eq!(ps0, ps1);
+        transform_points_mat3(&mut ps0, &m3);
+        par_transform_points_mat3(&mut ps1, &m3);
+        assert_eq!(ps0, ps1);
+    }
+    assert_eq!(aabb3_from_points(&points), par_aabb3_from_points(&points));
+    let edges: Vec<[[f32; 3]; 2]> = (0..1000).map(|_| [random_vec(), random_vec()]).collect();
+    let tris: Vec<[[f32; 3]; 3]> = (0..1000)
+        .map(|_| [random_vec(), random_vec(), random_vec()])
+        .collect();
+    for p in points.iter().take(10) {
+        assert_eq!(
+            nearest_edge3_to_point3(&edges, p),
+            par_nearest_edge3_to_point3(&edges, p)
+        );
+        assert_eq!(
+            nearest_tri3_to_point3(&tris, p),
+            par_nearest_tri3_to_point3(&tris, p)
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! methods for 2D circle.
+//! The circle is represented as `(radius: Real, center: &[Real;2])` in the same way as [`crate::sphere`]
+
+use crate::vec2::Vec2;
+
+/// circle whose diameter is the segment between the two points
+/// * Return `(radius, center)`
+pub fn from_two_points<T>(p0: &[T; 2], p1: &[T; 2]) -> (T, [T; 2])
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    (p1.sub(p0).norm() * half, p0.add(p1).scale(half))
+}
+
+/// circumcircle of the three points. `None` if the points are collinear.
+/// * Return `(radius, center)`
+pub fn from_three_points<T>(p0: &[T; 2], p1: &[T; 2], p2: &[T; 2]) -> Option<(T, [T; 2])>
+where
+    T: num_traits::Float,
+{
+    let a = p1.sub(p0);
+    let b = p2.sub(p0);
+    if a.cross(&b).abs() <= T::epsilon() * a.squared_norm().max(b.squared_norm()) {
+        return None;
+    }
+    let c = crate::tri2::circumcenter(p0, p1, p2);
+    Some((c.sub(p0).norm(), c))
+}
+
+pub fn is_include_point<T>(rad: T, center: &[T; 2], p: &[T; 2]) -> bool
+where
+    T: num_traits::Float,
+{
+    p.sub(center).squared_norm() <= rad * rad
+}
+
+/// intersection points of the two circles.
+/// The two points are the same if the circles touch.
+/// `None` if the circles do not intersect or they are concentric.
+pub fn intersection_circle_circle<T>(
+    rad0: T,
+    center0: &[T; 2],
+    rad1: T,
+    center1: &[T; 2],
+) -> Option<[[T; 2]; 2]>
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let d = center1.sub(center0);
+    let dd = d.squared_norm();
+    if dd.is_zero() {
+        return None;
+    }
+    // distance from `center0` to the chord along `d` divided by `|d|`
+    let s = (dd + rad0 * rad0 - rad1 * rad1) / (two * dd);
+    let hh = rad0 * rad0 / dd - s * s;
+    if hh < T::zero() {
+        return None;
+    }
+    let m = center0.add(&d.scale(s));
+    let h = d.perp().scale(hh.sqrt());
+    Some([m.add(&h), m.sub(&h)])
+}
+
+/// smallest circle through the points on its boundary (at most three).
+/// For the collinear three points, the circle of the farthest pair is returned.
+fn from_boundary_points<T>(ps: &[[T; 2]]) -> (T, [T; 2])
+where
+    T: num_traits::Float,
+{
+    match ps.len() {
+        1 => (T::zero(), ps[0]),
+        2 => from_two_points(&ps[0], &ps[1]),
+        3 => from_three_points(&ps[0], &ps[1], &ps[2]).unwrap_or_else(|| {
+            [(0, 1), (1, 2), (2, 0)]
+                .map(|(i, j)| from_two_points(&ps[i], &ps[j]))
+                .into_iter()
+                .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+                .unwrap()
+        }),
+        _ => unreachable!("at most 3 boundary points"),
+    }
+}
+
+/// smallest enclosing circle of the points by Welzl's algorithm.
+/// See [`crate::sphere::from_points_welzl`] for the 3D version.
+/// Returns `None` if there is no point.
+/// * Return `(radius, center)`
+pub fn from_points_welzl<T>(points: &[[T; 2]]) -> Option<(T, [T; 2])>
+where
+    T: num_traits::Float,
+{
+    crate::sphere::welzl(points, 3, &from_boundary_points)
+}
+
+#[test]
+fn test_from_three_points() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let ps: [[f64; 2]; 3] = std::array::from_fn(|_| [rng.random(), rng.random()]);
+        let (r, c) = from_three_points(&ps[0], &ps[1], &ps[2]).unwrap();
+        for p in ps.iter() {
+            assert!((p.sub(&c).norm() - r).abs() < 1.0e-8 * r.max(1.));
+        }
+        let c1 = crate::tri2::circumcenter(&ps[0], &ps[1], &ps[2]);
+        assert!(c.sub(&c1).norm() < 1.0e-8 * r.max(1.));
+    }
+    assert_eq!(from_three_points(&[0., 0.], &[1., 1.], &[2., 2.]), None);
+}
+
+#[test]
+fn test_intersection_circle_circle() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..100 {
+        let c0: [f64; 2] = [rng.random(), rng.random()];
+        let c1: [f64; 2] = [rng.random(), rng.random()];
+        let (r0, r1) = (rng.random::<f64>(), rng.random::<f64>());
+        let d = c0.sub(&c1).norm();
+        let res = intersection_circle_circle(r0, &c0, r1, &c1);
+        assert_eq!(res.is_some(), d <= r0 + r1 && d >= (r0 - r1).abs());
+        let Some(ps) = res else {
+            continue;
+        };
+        num_hit += 1;
+        for p in ps.iter() {
+            assert!((p.sub(&c0).norm() - r0).abs() < 1.0e-8);
+            assert!((p.sub(&c1).norm() - r1).abs() < 1.0e-8);
+        }
+    }
+    assert!(num_hit > 10);
+    // touching circles
+    let ps = intersection_circle_circle(1., &[0., 0.], 2., &[3., 0.]).unwrap();
+    assert_eq!(ps, [[1., 0.], [1., 0.]]);
+    assert_eq!(
+        intersection_circle_circle(1., &[0., 0.], 2., &[0., 0.]),
+        None
+    );
+}
+
+#[test]
+fn test_from_points_welzl() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let num_point = rng.random_range(1..30);
+        let ps: Vec<[f64; 2]> = (0..num_point)
+            .map(|_| [2. * rng.random::<f64>() - 1., 2. * rng.random::<f64>() - 1.])
+            .collect();
+        let (r, c) = from_points_welzl(&ps).unwrap();
+        assert!(
+            ps.iter()
+                .all(|p| is_include_point(r * (1. + 1.0e-6), &c, p))
+        );
+        // the same as the smallest one among the circles through two or three points enclosing all the points
+        let encloses = |(r0, c0): &(f64, [f64; 2])| {
+            ps.iter()
+                .all(|p| is_include_point(r0 * (1. + 1.0e-8), c0, p))
+        };
+        let n = ps.len();
+        let r_min = itertools::iproduct!(0..n, 0..n, 0..n)
+            .filter_map(|(i, j, k)| {
+                if i >= j || j > k {
+                    return None;
+                }
+                if j == k {
+                    return Some(from_two_points(&ps[i], &ps[j]));
+                }
+                from_three_points(&ps[i], &ps[j], &ps[k])
+            })
+            .filter(encloses)
+            .fold(f64::MAX, |r_min, (r0, _)| r_min.min(r0));
+        if n > 1 {
+            assert!((r - r_min).abs() < 1.0e-8 * r_min.max(1.), "{r} {r_min}");
+        }
+    }
+    let ps = [[0., 0.], [1., 0.], [2., 0.], [0.5, 0.]];
+    assert_eq!(from_points_welzl(&ps), Some((1., [1., 0.])));
+    assert_eq!(from_points_welzl::<f64>(&[]), None);
+}
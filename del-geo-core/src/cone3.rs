@@ -0,0 +1,255 @@
+//! methods for 3D solid cone.
+//! The cone is represented as `(radius: Real, apex: &[Real;3], base: &[Real;3])`
+//! where `base` is the center of the base disk of `radius`.
+//! The infinite cone is the single nappe extending from the apex through the base without the cap.
+
+use crate::vec3::Vec3;
+
+/// range of the line parameter inside the infinite cone.
+/// The range is unbounded (i.e., `t_min = -inf` or `t_max = inf`) if the line goes inside forever.
+/// * Return `(t_min, t_max)` where `t_*` is a ratio of `line_dir` not distance.
+pub fn intersections_against_line_infinite<T>(
+    rad: T,
+    apex: &[T; 3],
+    base: &[T; 3],
+    line_org: &[T; 3],
+    line_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    let (zero, inf) = (T::zero(), T::infinity());
+    let a = base.sub(apex);
+    let aa = a.squared_norm();
+    let w = line_org.sub(apex);
+    let (wa, da) = (w.dot(&a), line_dir.dot(&a));
+    // inside the double cone if `c2 * t^2 + 2 * c1 * t + c0 >= 0`
+    let m = (aa + rad * rad) / aa;
+    let c2 = m * da * da - aa * line_dir.squared_norm();
+    let c1 = m * wa * da - aa * w.dot(line_dir);
+    let c0 = m * wa * wa - aa * w.squared_norm();
+    let range = if c2.abs() <= T::epsilon() * aa * line_dir.squared_norm() {
+        // parallel to a generating line
+        if c1.is_zero() {
+            if c0 < zero {
+                return None;
+            }
+            (-inf, inf)
+        } else {
+            let t = -c0 / (c1 + c1);
+            if c1 > zero { (t, inf) } else { (-inf, t) }
+        }
+    } else {
+        let det = c1 * c1 - c2 * c0;
+        if det < zero {
+            if c2 < zero {
+                return None;
+            }
+            (-inf, inf)
+        } else {
+            let det = det.sqrt();
+            let (t0, t1) = ((-c1 - det) / c2, (-c1 + det) / c2);
+            let (t0, t1) = (t0.min(t1), t0.max(t1));
+            if c2 < zero {
+                (t0, t1)
+            } else if da > zero {
+                // the line is steeper than the cone, and the nappe is on the side of `da`
+                (t1, inf)
+            } else {
+                (-inf, t0)
+            }
+        }
+    };
+    // exclude the other nappe of the double cone
+    crate::cylinder3::clip_range_by_slab(range, wa, da, zero, inf)
+}
+
+/// range of the line parameter inside the cone with the base cap
+/// * Return `(t_min, t_max)` where `t_*` is a ratio of `line_dir` not distance.
+pub fn intersections_against_line<T>(
+    rad: T,
+    apex: &[T; 3],
+    base: &[T; 3],
+    line_org: &[T; 3],
+    line_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    let range = intersections_against_line_infinite(rad, apex, base, line_org, line_dir)?;
+    let a = base.sub(apex);
+    crate::cylinder3::clip_range_by_slab(
+        range,
+        line_org.sub(apex).dot(&a),
+        line_dir.dot(&a),
+        T::zero(),
+        a.squared_norm(),
+    )
+}
+
+/// the same as [`intersections_against_line_infinite`] but `None` if the range is behind the ray origin.
+/// The returned `t_min` is negative if the origin is inside.
+pub fn intersections_against_ray_infinite<T>(
+    rad: T,
+    apex: &[T; 3],
+    base: &[T; 3],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    intersections_against_line_infinite(rad, apex, base, ray_org, ray_dir)
+        .filter(|(_t_min, t_max)| *t_max >= T::zero())
+}
+
+/// the same as [`intersections_against_line`] but `None` if the range is behind the ray origin.
+/// The returned `t_min` is negative if the origin is inside.
+pub fn intersections_against_ray<T>(
+    rad: T,
+    apex: &[T; 3],
+    base: &[T; 3],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    intersections_against_line(rad, apex, base, ray_org, ray_dir)
+        .filter(|(_t_min, t_max)| *t_max >= T::zero())
+}
+
+pub fn is_include_point<T>(rad: T, apex: &[T; 3], base: &[T; 3], p: &[T; 3]) -> bool
+where
+    T: num_traits::Float,
+{
+    let a = base.sub(apex);
+    let aa = a.squared_norm();
+    let q = p.sub(apex);
+    let qa = q.dot(&a);
+    if qa < T::zero() || qa > aa {
+        return false;
+    }
+    // radial^2 <= (rad * axial / height)^2
+    (q.squared_norm() - qa * qa / aa) * aa * aa <= rad * rad * qa * qa
+}
+
+/// the point in the solid cone nearest to `p`. Returns `p` itself if it is inside.
+pub fn nearest_to_point<T>(rad: T, apex: &[T; 3], base: &[T; 3], p: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let a = base.sub(apex);
+    let h = a.norm();
+    let a = a.scale(T::one() / h);
+    let q = p.sub(apex);
+    // 2D problem on the half-plane spanned by the axis and the radial direction of `p`
+    let x = q.dot(&a);
+    let radial = q.sub(&a.scale(x));
+    let r = radial.norm();
+    if x >= T::zero() && x <= h && r * h <= rad * x {
+        return *p;
+    }
+    let u = if r.is_zero() {
+        crate::vec3::orthonormal_basis(&a).0
+    } else {
+        radial.scale(T::one() / r)
+    };
+    let (_, q0) = crate::edge2::nearest_to_point(&[T::zero(), T::zero()], &[h, rad], &[x, r]);
+    let (_, q1) = crate::edge2::nearest_to_point(&[h, T::zero()], &[h, rad], &[x, r]);
+    let d0 = crate::vec2::length(&crate::vec2::sub(&q0, &[x, r]));
+    let d1 = crate::vec2::length(&crate::vec2::sub(&q1, &[x, r]));
+    let [x, r] = if d0 < d1 { q0 } else { q1 };
+    apex.add(&a.scale(x)).add(&u.scale(r))
+}
+
+/// tight AABB of the cone
+pub fn aabb3<T>(rad: T, apex: &[T; 3], base: &[T; 3]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let n = base.sub(apex).normalize();
+    let aabb = crate::cylinder3::aabb3_of_disk(rad, base, &n);
+    crate::aabb3::from_two_aabbs(
+        &aabb,
+        &[apex[0], apex[1], apex[2], apex[0], apex[1], apex[2]],
+    )
+}
+
+#[test]
+fn test_intersections_against_ray() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..300 {
+        let (apex, base) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let rad = 0.8 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0].abs() + 0.1;
+        let org = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+        let dir = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).sub(&org);
+        let res = intersections_against_ray(rad, &apex, &base, &org, &dir);
+        let res_inf = intersections_against_ray_infinite(rad, &apex, &base, &org, &dir);
+        if res.is_some() {
+            num_hit += 1;
+        }
+        // sample points on the ray and compare with the inclusion test
+        let far = apex.add(&base.sub(&apex).scale(1.0e+3));
+        for i in 0..100 {
+            let t = i as f64 * 0.05;
+            let q = org.add(&dir.scale(t));
+            for (res, is_include) in [
+                (res, is_include_point(rad, &apex, &base, &q)),
+                (res_inf, is_include_point(rad * 1.0e+3, &apex, &far, &q)),
+            ] {
+                let is_in = |eps: f64| res.is_some_and(|(t0, t1)| t0 - eps <= t && t <= t1 + eps);
+                if is_include {
+                    assert!(is_in(1.0e-8));
+                } else {
+                    assert!(!is_in(-1.0e-8));
+                }
+            }
+        }
+    }
+    assert!(num_hit > 10);
+    // through the apex along the axis
+    let (apex, base) = ([0., 0., 0.], [0., 0., 2.]);
+    let res = intersections_against_ray(1., &apex, &base, &[0., 0., -1.], &[0., 0., 1.]);
+    assert_eq!(res, Some((1., 3.)));
+    let res = intersections_against_ray_infinite(1., &apex, &base, &[0., 0., -1.], &[0., 0., 1.]);
+    assert_eq!(res, Some((1., f64::INFINITY)));
+    // parallel to a generating line
+    let res = intersections_against_ray_infinite(1., &apex, &base, &[0., 0., 1.], &[1., 0., 2.]);
+    assert_eq!(res, Some((-0.25, f64::INFINITY)));
+    // the other nappe
+    let res = intersections_against_ray_infinite(1., &apex, &base, &[0., 0., -1.], &[0., 0., -1.]);
+    assert_eq!(res, None);
+}
+
+#[test]
+fn test_nearest_to_point_and_aabb() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let (apex, base) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let rad = 0.8 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0].abs() + 0.1;
+        let aabb = aabb3(rad, &apex, &base);
+        let p = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+        let q = nearest_to_point(rad, &apex, &base, &p);
+        // slightly larger cone for the rounding error
+        let d = base.sub(&apex).scale(1.0e-8);
+        let (apex_a, base_a) = (apex.sub(&d), base.add(&d));
+        assert!(is_include_point(rad * (1. + 1.0e-7), &apex_a, &base_a, &q));
+        let dist = crate::vec3::distance(&p, &q);
+        for _ in 0..100 {
+            let r = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+            let r = nearest_to_point(rad, &apex, &base, &r);
+            assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &r) < 1.0e-20);
+            assert!(crate::vec3::distance(&p, &r) >= dist - 1.0e-10);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! methods for 3D convex polytope.
+//! The half-space is stored as `[a, b, c, d]` where `a*x + b*y + c*z + d >= 0` is the inside
+//! (the same convention as [`crate::frustum`]).
+
+use crate::vec3::Vec3;
+
+/// bounded convex polytope as the boundary representation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolytope<Real> {
+    pub vtx2xyz: Vec<[Real; 3]>,
+    /// vertex indices of the edges `(i0, i1)` with `i0 < i1`
+    pub edge2vtx: Vec<[usize; 2]>,
+    /// vertex indices of the faces in counter-clockwise order seen from the outside
+    pub face2vtx: Vec<Vec<usize>>,
+    /// index of the input half-space for each face
+    pub face2plane: Vec<usize>,
+}
+
+/// intersection of the half-spaces by enumerating the intersections of the three planes.
+/// The redundant half-spaces that do not touch the polytope at a face do not produce a face,
+/// and the vertex where more than three planes meet is merged.
+/// The half-space with the zero normal (e.g., `[0, 0, 0, 1]`) is ignored if it is always satisfied.
+/// * Return `None` if the intersection is empty, unbounded, or degenerated (zero volume)
+pub fn from_halfspaces<Real>(planes: &[[Real; 4]]) -> Option<ConvexPolytope<Real>>
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    // normalize so that the distance to the plane is metric
+    let mut plane2idx = vec![];
+    let mut normalized = vec![];
+    for (i_plane, p) in planes.iter().enumerate() {
+        let len = [p[0], p[1], p[2]].norm();
+        if len == zero && p[3] >= zero {
+            continue; // always satisfied
+        }
+        let p = p.map(|v| v / len);
+        if !p.iter().all(|v| v.is_finite()) {
+            return None; // never satisfied or NaN
+        }
+        plane2idx.push(i_plane);
+        normalized.push(p);
+    }
+    let planes = normalized;
+    let dist = |p: &[Real; 4], x: &[Real; 3]| p[0] * x[0] + p[1] * x[1] + p[2] * x[2] + p[3];
+    let scale = planes.iter().fold(Real::one(), |a, p| a.max(p[3].abs()));
+    let tol = Real::epsilon().sqrt() * scale;
+    let mut vtx2xyz: Vec<[Real; 3]> = vec![];
+    for (i, j, k) in itertools::iproduct!(0..planes.len(), 0..planes.len(), 0..planes.len()) {
+        if i >= j || j >= k {
+            continue;
+        }
+        let n = [i, j, k].map(|l| [planes[l][0], planes[l][1], planes[l][2]]);
+        let (cjk, cki, cij) = (n[1].cross(&n[2]), n[2].cross(&n[0]), n[0].cross(&n[1]));
+        let det = n[0].dot(&cjk);
+        if det.abs() <= Real::epsilon() {
+            continue;
+        }
+        let x = cjk
+            .scale(planes[i][3])
+            .add(&cki.scale(planes[j][3]))
+            .add(&cij.scale(planes[k][3]))
+            .scale(-Real::one() / det);
+        if planes.iter().any(|p| dist(p, &x) < -tol) {
+            continue;
+        }
+        if vtx2xyz.iter().any(|y| crate::vec3::distance(y, &x) <= tol) {
+            continue;
+        }
+        vtx2xyz.push(x);
+    }
+    if vtx2xyz.len() < 4 {
+        return None;
+    }
+    let mut face2vtx: Vec<Vec<usize>> = vec![];
+    let mut face2plane = vec![];
+    for (i_plane, plane) in planes.iter().enumerate() {
+        let mut vtxs: Vec<usize> = (0..vtx2xyz.len())
+            .filter(|&i_vtx| dist(plane, &vtx2xyz[i_vtx]).abs() <= tol)
+            .collect();
+        if vtxs.len() < 3 {
+            continue; // redundant half-space
+        }
+        // sort counter-clockwise around the outward normal
+        let n_out = [-plane[0], -plane[1], -plane[2]];
+        let (b1, b2) = crate::vec3::orthonormal_basis(&n_out);
+        let center = vtxs
+            .iter()
+            .fold([zero; 3], |a, &i| a.add(&vtx2xyz[i]))
+            .scale(Real::one() / Real::from(vtxs.len()).unwrap());
+        let angle = |i: usize| {
+            let d = vtx2xyz[i].sub(&center);
+            d.dot(&b2).atan2(d.dot(&b1))
+        };
+        vtxs.sort_by(|&i, &j| crate::polynomial_root::cmp_nan_last(&angle(i), &angle(j)));
+        // the duplicated half-space
+        let is_duplicated = face2vtx.iter().any(|f| {
+            let (mut f, mut g) = (f.clone(), vtxs.clone());
+            f.sort();
+            g.sort();
+            f == g
+        });
+        if is_duplicated {
+            continue;
+        }
+        face2vtx.push(vtxs);
+        face2plane.push(plane2idx[i_plane]);
+    }
+    let mut edge2vtx: Vec<[usize; 2]> = face2vtx
+        .iter()
+        .flat_map(|f| {
+            (0..f.len()).map(|i| {
+                let (i0, i1) = (f[i], f[(i + 1) % f.len()]);
+                [i0.min(i1), i0.max(i1)]
+            })
+        })
+        .collect();
+    // each edge is shared by exactly two faces if the polytope is closed
+    let num_half_edge = edge2vtx.len();
+    edge2vtx.sort();
+    edge2vtx.dedup();
+    if num_half_edge != edge2vtx.len() * 2 {
+        return None;
+    }
+    if vtx2xyz.len() + face2vtx.len() != edge2vtx.len() + 2 {
+        return None;
+    }
+    Some(ConvexPolytope {
+        vtx2xyz,
+        edge2vtx,
+        face2vtx,
+        face2plane,
+    })
+}
+
+impl<Real> ConvexPolytope<Real>
+where
+    Real: num_traits::Float,
+{
+    pub fn volume(&self) -> Real {
+        let center = self
+            .vtx2xyz
+            .iter()
+            .fold([Real::zero(); 3], |a, p| a.add(p))
+            .scale(Real::one() / Real::from(self.vtx2xyz.len()).unwrap());
+        self.face2vtx
+            .iter()
+            .flat_map(|f| {
+                (1..f.len() - 1).map(|i| {
+                    let (p0, p1, p2) = (
+                        &self.vtx2xyz[f[0]],
+                        &self.vtx2xyz[f[i]],
+                        &self.vtx2xyz[f[i + 1]],
+                    );
+                    crate::tet::volume(&center, p0, p1, p2).abs()
+                })
+            })
+            .fold(Real::zero(), |a, b| a + b)
+    }
+}
+
+#[test]
+fn test_from_halfspaces() {
+    // cube [-1, 1]^3 with the redundant and duplicated half-spaces
+    let cube = [
+        [1., 0., 0., 1.],
+        [-1., 0., 0., 1.],
+        [0., 1., 0., 1.],
+        [0., -1., 0., 1.],
+        [0., 0., 1., 1.],
+        [0., 0., -1., 1.],
+    ];
+    let mut planes = cube.to_vec();
+    planes.push([1., 1., 1., 5.]);
+    planes.push([0., 0., -2., 2.]);
+    let cvx = from_halfspaces::<f64>(&planes).unwrap();
+    assert_eq!(
+        (cvx.vtx2xyz.len(), cvx.edge2vtx.len(), cvx.face2vtx.len()),
+        (8, 12, 6)
+    );
+    assert_eq!(cvx.face2plane, vec![0, 1, 2, 3, 4, 5]);
+    assert!((cvx.volume() - 8.).abs() < 1.0e-10);
+    // the faces are counter-clockwise seen from the outside
+    for (f, &i_plane) in cvx.face2vtx.iter().zip(cvx.face2plane.iter()) {
+        let p = |i: usize| &cvx.vtx2xyz[f[i]];
+        let n = crate::tri3::normal(p(0), p(1), p(2));
+        let m = &cube[i_plane];
+        assert!(n[0] * m[0] + n[1] * m[1] + n[2] * m[2] < 0.);
+    }
+    // cutting the corner through the three vertices (four planes meet at a vertex)
+    let planes = [
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [-1., 0., 0., 1.],
+        [0., -1., 0., 1.],
+        [0., 0., -1., 1.],
+        [-1., -1., -1., 1.],
+    ];
+    let cvx = from_halfspaces::<f64>(&planes).unwrap();
+    assert_eq!(
+        (cvx.vtx2xyz.len(), cvx.edge2vtx.len(), cvx.face2vtx.len()),
+        (4, 6, 4)
+    );
+    assert!((cvx.volume() - 1. / 6.).abs() < 1.0e-10);
+    // unbounded
+    assert_eq!(from_halfspaces::<f64>(&cube[..5]), None);
+    // empty
+    let mut planes = cube.to_vec();
+    planes.push([1., 0., 0., -2.]);
+    assert_eq!(from_halfspaces::<f64>(&planes), None);
+    // the half-space with the zero normal
+    let mut planes = vec![[0., 0., 0., 1.]];
+    planes.extend_from_slice(&cube);
+    let cvx = from_halfspaces::<f64>(&planes).unwrap();
+    assert_eq!(cvx.face2plane, vec![1, 2, 3, 4, 5, 6]);
+    assert!((cvx.volume() - 8.).abs() < 1.0e-10);
+    planes[0] = [0., 0., 0., -1.];
+    assert_eq!(from_halfspaces::<f64>(&planes), None);
+}
+
+#[test]
+fn test_from_halfspaces_frustum() {
+    use crate::mat4_col_major::{DepthRange, Handedness};
+    let proj = crate::mat4_col_major::perspective_fov(
+        1.0f64,
+        1.5,
+        0.1,
+        3.0,
+        Handedness::Right,
+        DepthRange::NegOneToOne,
+    );
+    let frustum = crate::frustum::Frustum::from_view_projection(&proj, DepthRange::NegOneToOne);
+    let cvx = from_halfspaces(&frustum.planes).unwrap();
+    assert_eq!(
+        (cvx.vtx2xyz.len(), cvx.edge2vtx.len(), cvx.face2vtx.len()),
+        (8, 12, 6)
+    );
+    // the vertices are the corners of the normalized device coordinate
+    let inv = crate::mat4_col_major::try_inverse(&proj).unwrap();
+    for p in cvx.vtx2xyz.iter() {
+        let q = crate::mat4_col_major::transform_homogeneous(&proj, p).unwrap();
+        assert!(q.iter().all(|v| (v.abs() - 1.).abs() < 1.0e-8), "{q:?}");
+        let r = crate::mat4_col_major::transform_homogeneous(&inv, &q).unwrap();
+        assert!(crate::vec3::distance(p, &r) < 1.0e-8);
+    }
+}
@@ -0,0 +1,260 @@
+//! methods for 3D solid cylinder.
+//! The cylinder is represented as `(radius: Real, p0: &[Real;3], p1: &[Real;3])`
+//! where `p0` and `p1` are the centers of the two caps.
+//! The infinite cylinder has the axis passing through `p0` and `p1` without the caps.
+
+use crate::vec3::Vec3;
+
+/// clip the range of the line parameter `(t_min, t_max)` such that `g0 + t * dg` is in `[lo, hi]`
+pub(crate) fn clip_range_by_slab<T>(
+    (t_min, t_max): (T, T),
+    g0: T,
+    dg: T,
+    lo: T,
+    hi: T,
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    if dg.is_zero() {
+        return if lo <= g0 && g0 <= hi {
+            Some((t_min, t_max))
+        } else {
+            None
+        };
+    }
+    let ta = (lo - g0) / dg;
+    let tb = (hi - g0) / dg;
+    let t_min = t_min.max(ta.min(tb));
+    let t_max = t_max.min(ta.max(tb));
+    if t_min > t_max {
+        return None;
+    }
+    Some((t_min, t_max))
+}
+
+/// range of the line parameter inside the infinite cylinder.
+/// The range is `(-inf, inf)` if the line is parallel to the axis and inside the cylinder.
+/// * Return `(t_min, t_max)` where `t_*` is a ratio of `line_dir` not distance.
+pub fn intersections_against_line_infinite<T>(
+    rad: T,
+    p0: &[T; 3],
+    p1: &[T; 3],
+    line_org: &[T; 3],
+    line_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    let a = p1.sub(p0);
+    let aa = a.squared_norm();
+    let w = line_org.sub(p0);
+    let (wa, da) = (w.dot(&a), line_dir.dot(&a));
+    // squared distance to the axis is `c2 * t^2 + 2 * c1 * t + c0`
+    let c2 = line_dir.squared_norm() - da * da / aa;
+    let c1 = w.dot(line_dir) - wa * da / aa;
+    let c0 = w.squared_norm() - wa * wa / aa - rad * rad;
+    if c2 <= T::epsilon() * line_dir.squared_norm() {
+        return if c0 <= T::zero() {
+            Some((T::neg_infinity(), T::infinity()))
+        } else {
+            None
+        };
+    }
+    let det = c1 * c1 - c2 * c0;
+    if det < T::zero() {
+        return None;
+    }
+    let det = det.sqrt();
+    Some(((-c1 - det) / c2, (-c1 + det) / c2))
+}
+
+/// range of the line parameter inside the cylinder with the caps
+/// * Return `(t_min, t_max)` where `t_*` is a ratio of `line_dir` not distance.
+pub fn intersections_against_line<T>(
+    rad: T,
+    p0: &[T; 3],
+    p1: &[T; 3],
+    line_org: &[T; 3],
+    line_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    let range = intersections_against_line_infinite(rad, p0, p1, line_org, line_dir)?;
+    let a = p1.sub(p0);
+    clip_range_by_slab(
+        range,
+        line_org.sub(p0).dot(&a),
+        line_dir.dot(&a),
+        T::zero(),
+        a.squared_norm(),
+    )
+}
+
+/// the same as [`intersections_against_line_infinite`] but `None` if the range is behind the ray origin.
+/// The returned `t_min` is negative if the origin is inside.
+pub fn intersections_against_ray_infinite<T>(
+    rad: T,
+    p0: &[T; 3],
+    p1: &[T; 3],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    intersections_against_line_infinite(rad, p0, p1, ray_org, ray_dir)
+        .filter(|(_t_min, t_max)| *t_max >= T::zero())
+}
+
+/// the same as [`intersections_against_line`] but `None` if the range is behind the ray origin.
+/// The returned `t_min` is negative if the origin is inside.
+pub fn intersections_against_ray<T>(
+    rad: T,
+    p0: &[T; 3],
+    p1: &[T; 3],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    intersections_against_line(rad, p0, p1, ray_org, ray_dir)
+        .filter(|(_t_min, t_max)| *t_max >= T::zero())
+}
+
+pub fn is_include_point<T>(rad: T, p0: &[T; 3], p1: &[T; 3], p: &[T; 3]) -> bool
+where
+    T: num_traits::Float,
+{
+    let a = p1.sub(p0);
+    let aa = a.squared_norm();
+    let q = p.sub(p0);
+    let qa = q.dot(&a);
+    if qa < T::zero() || qa > aa {
+        return false;
+    }
+    q.squared_norm() - qa * qa / aa <= rad * rad
+}
+
+/// the point in the solid cylinder nearest to `p`. Returns `p` itself if it is inside.
+pub fn nearest_to_point<T>(rad: T, p0: &[T; 3], p1: &[T; 3], p: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let a = p1.sub(p0);
+    let q = p.sub(p0);
+    // the axial and the radial components are clamped independently
+    let s = q.dot(&a) / a.squared_norm();
+    let radial = q.sub(&a.scale(s));
+    let len = radial.norm();
+    let radial = if len > rad {
+        radial.scale(rad / len)
+    } else {
+        radial
+    };
+    let s = s.max(T::zero()).min(T::one());
+    p0.add(&a.scale(s)).add(&radial)
+}
+
+/// AABB of the cap disk centered at `center` with the unit normal `n`
+pub(crate) fn aabb3_of_disk<T>(rad: T, center: &[T; 3], n: &[T; 3]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let e: [T; 3] = std::array::from_fn(|i| rad * (T::one() - n[i] * n[i]).max(T::zero()).sqrt());
+    [
+        center[0] - e[0],
+        center[1] - e[1],
+        center[2] - e[2],
+        center[0] + e[0],
+        center[1] + e[1],
+        center[2] + e[2],
+    ]
+}
+
+/// tight AABB of the cylinder
+pub fn aabb3<T>(rad: T, p0: &[T; 3], p1: &[T; 3]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let n = p1.sub(p0).normalize();
+    crate::aabb3::from_two_aabbs(&aabb3_of_disk(rad, p0, &n), &aabb3_of_disk(rad, p1, &n))
+}
+
+#[test]
+fn test_intersections_against_ray() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..100 {
+        let (p0, p1) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let rad = 0.5 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0].abs() + 0.1;
+        let org = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+        let dir = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).sub(&org);
+        let res = intersections_against_ray(rad, &p0, &p1, &org, &dir);
+        if res.is_some() {
+            num_hit += 1;
+        }
+        // sample points on the ray and compare with the inclusion test
+        for i in 0..100 {
+            let t = i as f64 * 0.05;
+            let q = org.add(&dir.scale(t));
+            let is_in = |eps: f64| res.is_some_and(|(t0, t1)| t0 - eps <= t && t <= t1 + eps);
+            if is_include_point(rad, &p0, &p1, &q) {
+                assert!(is_in(1.0e-8));
+            } else {
+                assert!(!is_in(-1.0e-8));
+            }
+        }
+        // the infinite cylinder contains the finite one
+        if let Some((t0, t1)) = res {
+            let (s0, s1) = intersections_against_ray_infinite(rad, &p0, &p1, &org, &dir).unwrap();
+            assert!(s0 <= t0 + 1.0e-10 && t1 <= s1 + 1.0e-10);
+        }
+    }
+    assert!(num_hit > 10);
+    // parallel to the axis
+    let (p0, p1) = ([0., 0., 0.], [0., 0., 2.]);
+    let res = intersections_against_ray(1., &p0, &p1, &[0.5, 0., -1.], &[0., 0., 2.]);
+    assert_eq!(res, Some((0.5, 1.5)));
+    let res = intersections_against_ray_infinite(1., &p0, &p1, &[0.5, 0., -1.], &[0., 0., 2.]);
+    assert_eq!(res, Some((f64::NEG_INFINITY, f64::INFINITY)));
+    assert_eq!(
+        intersections_against_ray(1., &p0, &p1, &[1.5, 0., -1.], &[0., 0., 1.]),
+        None
+    );
+}
+
+#[test]
+fn test_nearest_to_point_and_aabb() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let (p0, p1) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let rad = 0.5 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0].abs() + 0.1;
+        let aabb = aabb3(rad, &p0, &p1);
+        let p = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+        let q = nearest_to_point(rad, &p0, &p1, &p);
+        // slightly larger cylinder for the rounding error
+        let (p0a, p1a) = (
+            p0.sub(&p1.sub(&p0).scale(1.0e-8)),
+            p1.add(&p1.sub(&p0).scale(1.0e-8)),
+        );
+        assert!(is_include_point(rad * (1. + 1.0e-8), &p0a, &p1a, &q));
+        let dist = crate::vec3::distance(&p, &q);
+        for _ in 0..100 {
+            let r = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+            let r = nearest_to_point(rad, &p0, &p1, &r);
+            assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &r) < 1.0e-20);
+            assert!(crate::vec3::distance(&p, &r) >= dist - 1.0e-10);
+        }
+    }
+}
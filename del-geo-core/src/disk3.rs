@@ -0,0 +1,200 @@
+//! methods for 3D disk and annulus.
+//! The disk is represented as `(rad: Real, rad_inner: Real, center: &[Real;3], normal: &[Real;3])`
+//! where `normal` is the unit normal of the plane of the disk.
+//! The region is the annulus `rad_inner <= |p - center| <= rad` on the plane,
+//! and it is the disk if `rad_inner` is zero.
+
+use crate::vec3::Vec3;
+
+/// the ray parameter at the intersection of the ray and the disk.
+/// Both sides of the disk are hit.
+/// * `ray_dir` - direction of ray (general non-zero vector, not necessarily unitary)
+/// * Return `t` where the intersection is `ray_org + t * ray_dir`
+pub fn intersection_ray<T>(
+    rad: T,
+    rad_inner: T,
+    center: &[T; 3],
+    normal: &[T; 3],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<T>
+where
+    T: num_traits::Float,
+{
+    let dn = ray_dir.dot(normal);
+    if dn.is_zero() {
+        return None;
+    }
+    let t = center.sub(ray_org).dot(normal) / dn;
+    if t < T::zero() {
+        return None;
+    }
+    let sq_r = ray_org.add(&ray_dir.scale(t)).sub(center).squared_norm();
+    if sq_r > rad * rad || sq_r < rad_inner * rad_inner {
+        return None;
+    }
+    Some(t)
+}
+
+pub fn area<T>(rad: T, rad_inner: T) -> T
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    T::PI() * (rad * rad - rad_inner * rad_inner)
+}
+
+/// uniformly sample a point on the disk. The pdf w.r.t. the area is `1 / area(rad, rad_inner)`.
+/// * `rnd` - two uniform random numbers in `[0, 1)`
+pub fn sample_uniform<T>(
+    rad: T,
+    rad_inner: T,
+    center: &[T; 3],
+    normal: &[T; 3],
+    rnd: &[T; 2],
+) -> [T; 3]
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let two = T::one() + T::one();
+    let r = (rad_inner * rad_inner + rnd[0] * (rad * rad - rad_inner * rad_inner)).sqrt();
+    let phi = two * T::PI() * rnd[1];
+    let (u, v) = crate::vec3::orthonormal_basis(normal);
+    center
+        .add(&u.scale(r * phi.cos()))
+        .add(&v.scale(r * phi.sin()))
+}
+
+/// solid angle of the full disk of `rad` seen from the point
+/// whose distances to the plane and to the axis are `h` and `d`, respectively.
+///
+/// The solid angle of the thin wedge of the azimuth around the foot of the point on the plane is
+/// `(h/sqrt(h^2+rho0^2) - h/sqrt(h^2+rho1^2)) * dphi` where `[rho0, rho1]` is the range covered by the disk.
+/// The integral over the azimuth is computed with the trapezoidal rule on a smooth periodic integrand,
+/// which converges exponentially.
+fn solid_angle_full_disk<T>(rad: T, h: T, d: T) -> T
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    const NDIV: usize = 128;
+    let (zero, one) = (T::zero(), T::one());
+    let two = one + one;
+    if h.is_zero() || rad.is_zero() {
+        return zero;
+    }
+    let f = |rho: T| h / (h * h + rho * rho).sqrt();
+    let dphi = two * T::PI() / T::from(NDIV).unwrap();
+    let sum = (0..NDIV)
+        .map(|i| T::from(i).unwrap() * dphi)
+        .map(|phi| {
+            if d < rad {
+                // the foot is inside. `rho` is the distance to the circle in the direction `phi`
+                let rho = d * phi.cos() + (rad * rad - d * d * phi.sin() * phi.sin()).sqrt();
+                one - f(rho)
+            } else {
+                // the foot is outside. The azimuth `beta` is parameterized as `sin(beta) = (rad/d) * sin(phi)`,
+                // and the range over `phi` in [0, 2pi) covers the disk twice.
+                let sin_beta = rad / d * phi.sin();
+                let cos_beta = (one - sin_beta * sin_beta).max(zero).sqrt();
+                if cos_beta.is_zero() {
+                    return zero;
+                }
+                let rho0 = d * cos_beta - rad * phi.cos();
+                let rho1 = d * cos_beta + rad * phi.cos();
+                (f(rho0) - f(rho1)) * rad * phi.cos() / (d * cos_beta) / two
+            }
+        })
+        .fold(zero, |a, b| a + b);
+    sum * dphi
+}
+
+/// solid angle subtended by the disk (or the annulus) seen from the point `p`
+pub fn solid_angle<T>(rad: T, rad_inner: T, center: &[T; 3], normal: &[T; 3], p: &[T; 3]) -> T
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let q = p.sub(center);
+    let h = q.dot(normal);
+    let d = q.sub(&normal.scale(h)).norm();
+    let h = h.abs();
+    solid_angle_full_disk(rad, h, d) - solid_angle_full_disk(rad_inner, h, d)
+}
+
+#[test]
+fn test_intersection_ray_and_sample() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let center: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let normal = crate::sphere::sample_surface_uniform(&[rng.random::<f64>(), rng.random()]);
+        let (rad, rad_inner) = (0.5 + rng.random::<f64>(), 0.3 * rng.random::<f64>());
+        let p = sample_uniform(
+            rad,
+            rad_inner,
+            &center,
+            &normal,
+            &[rng.random(), rng.random()],
+        );
+        let r = crate::vec3::distance(&p, &center);
+        assert!(rad_inner - 1.0e-10 <= r && r <= rad + 1.0e-10);
+        assert!(p.sub(&center).dot(&normal).abs() < 1.0e-10);
+        // ray toward the sampled point
+        let org: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let dir = p.sub(&org).scale(0.5);
+        let t = intersection_ray(rad, rad_inner, &center, &normal, &org, &dir).unwrap();
+        assert!((t - 2.).abs() < 1.0e-8);
+        assert_eq!(
+            intersection_ray(rad, rad_inner, &center, &normal, &org, &dir.scale(-1.)),
+            None
+        );
+    }
+    // through the hole of the annulus
+    let (c, n) = ([0., 0., 0.], [0., 0., 1.]);
+    let hit = |org: &[f64; 3]| intersection_ray(1., 0.5, &c, &n, org, &[0., 0., -1.]);
+    assert_eq!(hit(&[0.2, 0., 1.]), None);
+    assert_eq!(hit(&[0.7, 0., 1.]), Some(1.));
+    assert_eq!(hit(&[1.2, 0., 1.]), None);
+    assert!((area(1f64, 0.5) - 0.75 * std::f64::consts::PI).abs() < 1.0e-12);
+}
+
+#[test]
+fn test_solid_angle() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let pi = std::f64::consts::PI;
+    let (c, n) = ([0., 0., 0.], [0., 0., 1.]);
+    // on the axis
+    let on_axis = |rad: f64, h: f64| 2. * pi * (1. - h / (h * h + rad * rad).sqrt());
+    for (rad, h) in [(1., 1.), (0.3, 2.), (2., 0.1)] {
+        let omega = solid_angle(rad, 0., &c, &n, &[0., 0., h]);
+        assert!((omega - on_axis(rad, h)).abs() < 1.0e-10);
+        let omega = solid_angle(rad, 0.5 * rad, &c, &n, &[0., 0., -h]);
+        assert!((omega - on_axis(rad, h) + on_axis(0.5 * rad, h)).abs() < 1.0e-10);
+    }
+    // approaching the disk from the above tends to the hemisphere
+    assert!((solid_angle(1., 0., &c, &n, &[0.3, 0.2, 1.0e-6]) - 2. * pi).abs() < 1.0e-4);
+    assert_eq!(solid_angle(1., 0., &c, &n, &[2., 0., 0.]), 0.);
+    // off the axis, compare with the integration over the disk
+    for _iter in 0..10 {
+        let p = [3. * rng.random::<f64>(), 0., 0.1 + rng.random::<f64>()];
+        let (rad, rad_inner) = (1., 0.3);
+        let ndiv = 400;
+        let mut omega0 = 0.;
+        for ir in 0..ndiv {
+            let r = rad_inner + (rad - rad_inner) * (ir as f64 + 0.5) / ndiv as f64;
+            for it in 0..ndiv {
+                let theta = 2. * pi * (it as f64 + 0.5) / ndiv as f64;
+                let q = [r * theta.cos(), r * theta.sin(), 0.];
+                let dist = crate::vec3::distance(&p, &q);
+                let da = r * (rad - rad_inner) / ndiv as f64 * 2. * pi / ndiv as f64;
+                omega0 += p[2] / (dist * dist * dist) * da;
+            }
+        }
+        let omega1 = solid_angle(rad, rad_inner, &c, &n, &p);
+        assert!(
+            (omega0 - omega1).abs() < 1.0e-3 * omega0,
+            "{omega0} {omega1}"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! methods for the unit dual quaternion representing 3D rigid transformation.
+//! A dual quaternion `q_r + eps * q_d` is stored as `[T;8]`: the first four elements are the
+//! real part `q_r` and the last four elements are the dual part `q_d`.
+//! Each part is a quaternion in the `[i,j,k,w]` storage.
+
+/// trait for dual quaternion
+pub trait DualQuaternion<Real>
+where
+    Self: Sized,
+{
+    fn normalized(&self) -> Self;
+    fn mult(&self, other: &Self) -> Self;
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3];
+    fn to_mat4_col_major(&self) -> [Real; 16];
+}
+
+impl<Real> DualQuaternion<Real> for [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    fn normalized(&self) -> Self {
+        normalized(self)
+    }
+    fn mult(&self, other: &Self) -> Self {
+        mult(self, other)
+    }
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3] {
+        transform_point(self, p)
+    }
+    fn to_mat4_col_major(&self) -> [Real; 16] {
+        to_mat4_col_major(self)
+    }
+}
+
+fn from_real_and_dual<Real>(r: &[Real; 4], d: &[Real; 4]) -> [Real; 8]
+where
+    Real: Copy,
+{
+    [r[0], r[1], r[2], r[3], d[0], d[1], d[2], d[3]]
+}
+
+fn to_real_and_dual<Real>(a: &[Real; 8]) -> ([Real; 4], [Real; 4])
+where
+    Real: Copy,
+{
+    ([a[0], a[1], a[2], a[3]], [a[4], a[5], a[6], a[7]])
+}
+
+pub fn from_identity<Real>() -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [zero, zero, zero, Real::one(), zero, zero, zero, zero]
+}
+
+/// rigid transformation that rotates with the unit quaternion `q` then translates with `t`
+pub fn from_quaternion_and_translation<Real>(q: &[Real; 4], t: &[Real; 3]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let half = Real::one() / (Real::one() + Real::one());
+    let tq = [t[0] * half, t[1] * half, t[2] * half, Real::zero()];
+    let d = crate::quaternion::mult_quaternion(&tq, q);
+    from_real_and_dual(q, &d)
+}
+
+// above: from methods
+// ---------------------------------
+
+/// the unit quaternion and the translation of the rigid transformation
+pub fn to_quaternion_and_translation<Real>(a: &[Real; 8]) -> ([Real; 4], [Real; 3])
+where
+    Real: num_traits::Float,
+{
+    let two = Real::one() + Real::one();
+    let (r, d) = to_real_and_dual(a);
+    let rc = [-r[0], -r[1], -r[2], r[3]];
+    let t = crate::quaternion::mult_quaternion(&d, &rc);
+    (r, [t[0] * two, t[1] * two, t[2] * two])
+}
+
+pub fn to_mat4_col_major<Real>(a: &[Real; 8]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let (q, t) = to_quaternion_and_translation(a);
+    let mut m = crate::quaternion::to_mat4_col_major(&q);
+    m[12] = t[0];
+    m[13] = t[1];
+    m[14] = t[2];
+    m
+}
+
+// above: to methods
+// ---------------------------------
+
+/// make the real part unit length and the dual part orthogonal to the real part
+pub fn normalized<Real>(a: &[Real; 8]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let (r, d) = to_real_and_dual(a);
+    let sqlen = r[0] * r[0] + r[1] * r[1] + r[2] * r[2] + r[3] * r[3];
+    let dot = r[0] * d[0] + r[1] * d[1] + r[2] * d[2] + r[3] * d[3];
+    let inv_len = Real::one() / sqlen.sqrt();
+    let c = dot / sqlen;
+    let r1 = r.map(|v| v * inv_len);
+    let d1: [Real; 4] = std::array::from_fn(|i| (d[i] - r[i] * c) * inv_len);
+    from_real_and_dual(&r1, &d1)
+}
+
+/// the transformation `a * b` that applies `b` first then `a`
+pub fn mult<Real>(a: &[Real; 8], b: &[Real; 8]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let (ar, ad) = to_real_and_dual(a);
+    let (br, bd) = to_real_and_dual(b);
+    let r = crate::quaternion::mult_quaternion(&ar, &br);
+    let d0 = crate::quaternion::mult_quaternion(&ar, &bd);
+    let d1 = crate::quaternion::mult_quaternion(&ad, &br);
+    from_real_and_dual(&r, &std::array::from_fn(|i| d0[i] + d1[i]))
+}
+
+pub fn transform_point<Real>(a: &[Real; 8], p: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let (q, t) = to_quaternion_and_translation(a);
+    let v = crate::quaternion::transform_vec(&q, p);
+    [v[0] + t[0], v[1] + t[1], v[2] + t[2]]
+}
+
+/// dual quaternion linear blending (DLB) by Kavan et al. (2007),
+/// "Skinning with Dual Quaternions".
+/// The dual quaternions are flipped into the hemisphere of the first one before blending.
+pub fn linear_blending<Real>(dqs: &[[Real; 8]], weights: &[Real]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    assert_eq!(dqs.len(), weights.len());
+    assert!(!dqs.is_empty());
+    let pivot = &dqs[0];
+    let mut res = [Real::zero(); 8];
+    for (dq, &w) in dqs.iter().zip(weights.iter()) {
+        let dot = (0..4).fold(Real::zero(), |acc, i| acc + dq[i] * pivot[i]);
+        let w = if dot < Real::zero() { -w } else { w };
+        for i in 0..8 {
+            res[i] = res[i] + dq[i] * w;
+        }
+    }
+    normalized(&res)
+}
+
+#[test]
+fn test_dual_quaternion() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_transform = || {
+        let aa: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let t: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        (crate::quaternion::from_axisangle(&aa), t)
+    };
+    for _itr in 0..100 {
+        let (qa, ta) = random_transform();
+        let (qb, tb) = random_transform();
+        let a = from_quaternion_and_translation(&qa, &ta);
+        let b = from_quaternion_and_translation(&qb, &tb);
+        let ia = crate::isometry3::from_quaternion_and_translation(&qa, &ta);
+        let ib = crate::isometry3::from_quaternion_and_translation(&qb, &tb);
+        let p = [0.3, -0.5, 0.8];
+        {
+            let p0 = a.transform_point(&p);
+            let p1 = crate::isometry3::transform_point(&ia, &p);
+            assert!(crate::vec3::distance(&p0, &p1) < 1.0e-10);
+        }
+        {
+            let m0 = a.mult(&b).to_mat4_col_major();
+            let m1 = crate::isometry3::to_mat4_col_major(&crate::isometry3::compose(&ia, &ib));
+            assert!((0..16).all(|i| (m0[i] - m1[i]).abs() < 1.0e-10));
+        }
+        {
+            let c = normalized(&a.map(|v| v * 2.5));
+            assert!((0..8).all(|i| (c[i] - a[i]).abs() < 1.0e-10));
+            let c = linear_blending(&[a, b.map(|v| -v)], &[1.0, 0.0]);
+            assert!((0..8).all(|i| (c[i] - a[i]).abs() < 1.0e-10));
+            let c = linear_blending(&[a, b.map(|v| -v)], &[0.0, 1.0]);
+            assert!((0..8).all(|i| (c[i] - b[i]).abs() < 1.0e-10));
+        }
+    }
+    {
+        // blending the rotations around the same axis gives the rotation with the mean angle
+        let axis = crate::vec3::normalize(&[1.0f64, 2.0, -0.5]);
+        let q0 = crate::quaternion::around_axis(&axis, 0.2);
+        let q1 = crate::quaternion::around_axis(&axis, 1.0);
+        let a = from_quaternion_and_translation(&q0, &[0.; 3]);
+        let b = from_quaternion_and_translation(&q1, &[0.; 3]);
+        let c = linear_blending(&[a, b], &[0.5, 0.5]);
+        let q2 = crate::quaternion::around_axis(&axis, 0.6);
+        let (q, t) = to_quaternion_and_translation(&c);
+        assert!((0..4).all(|i| (q[i] - q2[i]).abs() < 1.0e-10));
+        assert!(t.iter().all(|v| v.abs() < 1.0e-10));
+    }
+}
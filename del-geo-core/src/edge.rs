@@ -1,6 +1,14 @@
-//! methods for 2D or 3D edge (line segment)
+//! methods for edge (line segment) in any dimension.
+//! [`crate::edge2`] and [`crate::edge3`] call these functions for the common operations.
 
 pub fn length<T, const N: usize>(p0: &[T; N], p1: &[T; N]) -> T
+where
+    T: num_traits::Float,
+{
+    squared_length(p0, p1).sqrt()
+}
+
+pub fn squared_length<T, const N: usize>(p0: &[T; N], p1: &[T; N]) -> T
 where
     T: num_traits::Float,
 {
@@ -8,5 +16,117 @@ where
     for i in 0..N {
         x = x + (p0[i] - p1[i]) * (p0[i] - p1[i]);
     }
-    x.sqrt()
+    x
+}
+
+/// linear interpolation. `ratio==0` should output `p0`
+pub fn position_from_ratio<T, const N: usize>(p0: &[T; N], p1: &[T; N], ratio: T) -> [T; N]
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    std::array::from_fn(|i| (one - ratio) * p0[i] + ratio * p1[i])
+}
+
+/// linear interpolation. The same as [`position_from_ratio`]
+pub fn lerp<T, const N: usize>(p0: &[T; N], p1: &[T; N], ratio: T) -> [T; N]
+where
+    T: num_traits::Float,
+{
+    position_from_ratio(p0, p1, ratio)
+}
+
+pub fn midpoint<T, const N: usize>(p0: &[T; N], p1: &[T; N]) -> [T; N]
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    std::array::from_fn(|i| (p0[i] + p1[i]) * half)
+}
+
+/// AABB of the edge in the layout of [`crate::aabb`]
+pub fn aabb<T, const N: usize, const SIZE_AABB: usize>(p0: &[T; N], p1: &[T; N]) -> [T; SIZE_AABB]
+where
+    T: num_traits::Float,
+{
+    const { assert!(N * 2 == SIZE_AABB) };
+    std::array::from_fn(|i| {
+        if i < N {
+            p0[i].min(p1[i])
+        } else {
+            p0[i - N].max(p1[i - N])
+        }
+    })
+}
+
+/// nearest point on the edge from the point `point_pos`.
+/// Note that [`crate::edge2::nearest_to_point`] returns `(ratio, point)` instead.
+/// * Returns `(dist, ratio)`
+///   - `dist` : distance
+///   - `ratio`: ratio of the nearest point on the edge. `0.5` for the zero-length edge
+pub fn nearest_to_point<T, const N: usize>(p0: &[T; N], p1: &[T; N], point_pos: &[T; N]) -> (T, T)
+where
+    T: num_traits::Float,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let half = one / (one + one);
+    let (mut a, mut b) = (zero, zero);
+    for i in 0..N {
+        let d = p1[i] - p0[i];
+        a = a + d * d;
+        b = b + d * (p0[i] - point_pos[i]);
+    }
+    let t = if !a.is_zero() {
+        (-b / a).clamp(zero, one)
+    } else {
+        half
+    };
+    let p: [T; N] = std::array::from_fn(|i| p0[i] + (p1[i] - p0[i]) * t);
+    (length(&p, point_pos), t)
+}
+
+#[test]
+fn test_edge_generic() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let ps: [[f64; 6]; 3] =
+            std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.));
+        let (dist, ratio) = nearest_to_point(&ps[0], &ps[1], &ps[2]);
+        assert!((0. ..=1.).contains(&ratio));
+        // compare with the sampling of the edge
+        let dist_min = (0..=1000)
+            .map(|i| {
+                length(
+                    &position_from_ratio(&ps[0], &ps[1], i as f64 / 1000.),
+                    &ps[2],
+                )
+            })
+            .fold(f64::MAX, f64::min);
+        assert!(dist <= dist_min + 1.0e-10 && dist > dist_min - 1.0e-3);
+        let aabb = aabb::<f64, 6, 12>(&ps[0], &ps[1]);
+        let m = midpoint(&ps[0], &ps[1]);
+        assert_eq!(m, position_from_ratio(&ps[0], &ps[1], 0.5));
+        assert!(crate::aabb::is_include_point::<f64, 6, 12>(&aabb, &m));
+        // the same as the 3D version
+        let p3: [[f64; 3]; 3] = ps.map(|p| [p[0], p[1], p[2]]);
+        let (dist3, ratio3) = crate::edge3::nearest_to_point3(&p3[0], &p3[1], &p3[2]);
+        let (dist, ratio) = nearest_to_point(&p3[0], &p3[1], &p3[2]);
+        assert_eq!((dist, ratio), (dist3, ratio3));
+    }
+    assert_eq!(nearest_to_point(&[1., 1.], &[1., 1.], &[1., 2.]), (1., 0.5));
+    // short edge in f32 is not degenerate
+    let (_, ratio) = nearest_to_point(&[0f32, 0.], &[1.0e-4, 0.], &[1.0e-4, 1.]);
+    assert_eq!(ratio, 1.);
+    // 2D and 3D versions
+    let (p0, p1) = ([1f64, -2.], [3., 4.]);
+    assert_eq!(crate::edge2::lerp(&p0, &p1, 0.25), lerp(&p0, &p1, 0.25));
+    assert_eq!(crate::edge2::midpoint(&p0, &p1), [2., 1.]);
+    assert_eq!(crate::edge2::aabb(&p0, &p1), [1., -2., 3., 4.]);
+    let (p0, p1) = ([1f64, -2., 5.], [3., 4., -1.]);
+    assert_eq!(crate::edge3::lerp(&p0, &p1, 0.5), midpoint(&p0, &p1));
+    assert_eq!(crate::edge3::midpoint(&p0, &p1), [2., 1., 2.]);
+    assert_eq!(crate::edge3::aabb(&p0, &p1), [1., -2., -1., 3., 4., 5.]);
 }
@@ -6,18 +6,14 @@ pub fn length<T>(ps: &[T; 2], pe: &[T; 2]) -> T
 where
     T: num_traits::Float,
 {
-    use crate::vec2::Vec2;
-    let [dx, dy] = ps.sub(pe);
-    (dx * dx + dy * dy).sqrt()
+    crate::edge::length(ps, pe)
 }
 
 pub fn squared_length<T>(ps: &[T; 2], pe: &[T; 2]) -> T
 where
     T: num_traits::Float,
 {
-    use crate::vec2::Vec2;
-    let [dx, dy] = ps.sub(pe);
-    dx * dx + dy * dy
+    crate::edge::squared_length(ps, pe)
 }
 
 pub fn unit_edge_vector<T>(ps: &[T; 2], pe: &[T; 2]) -> [T; 2]
@@ -35,11 +31,30 @@ pub fn position_from_ratio<T>(p0: &[T; 2], p1: &[T; 2], ratio: T) -> [T; 2]
 where
     T: num_traits::Float,
 {
-    let one = T::one();
-    [
-        (one - ratio) * p0[0] + ratio * p1[0],
-        (one - ratio) * p0[1] + ratio * p1[1],
-    ]
+    crate::edge::position_from_ratio(p0, p1, ratio)
+}
+
+/// linear interpolation. The same as [`position_from_ratio`]
+pub fn lerp<T>(p0: &[T; 2], p1: &[T; 2], ratio: T) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    crate::edge::lerp(p0, p1, ratio)
+}
+
+pub fn midpoint<T>(p0: &[T; 2], p1: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    crate::edge::midpoint(p0, p1)
+}
+
+/// AABB of the edge in the layout of [`crate::aabb2`]
+pub fn aabb<T>(p0: &[T; 2], p1: &[T; 2]) -> [T; 4]
+where
+    T: num_traits::Float,
+{
+    crate::edge::aabb(p0, p1)
 }
 
 pub fn culling_intersection<T>(
@@ -184,17 +199,412 @@ fn test_dldw_intersection_edge2() {
     }
 }
 
-pub fn winding_number<T>(ps: &[T; 2], pe: &[T; 2], po: &[T; 2]) -> T
+/// classification of the intersection between two 2D edges `p0`-`p1` and `q0`-`q1`.
+/// See [`intersection_edge2_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegSegResult<T> {
+    /// the edges do not share any point
+    Disjoint,
+    /// the edges cross at the point strictly inside both edges
+    Crossing {
+        ratio_p: T,
+        ratio_q: T,
+        point: [T; 2],
+    },
+    /// the edges share one point that is an end point of at least one edge
+    Touching {
+        ratio_p: T,
+        ratio_q: T,
+        point: [T; 2],
+    },
+    /// the collinear edges share the sub-segment `points[0]`-`points[1]`
+    /// whose ratios on each edge are `ratios_p` and `ratios_q` (sorted along the edge `p`)
+    Overlap {
+        ratios_p: [T; 2],
+        ratios_q: [T; 2],
+        points: [[T; 2]; 2],
+    },
+}
+
+impl<T> SegSegResult<T> {
+    pub fn is_intersecting(&self) -> bool {
+        !matches!(self, SegSegResult::Disjoint)
+    }
+
+    /// swap the roles of the edges `p` and `q`
+    fn swap(self) -> Self
+    where
+        T: PartialOrd + Copy,
+    {
+        match self {
+            SegSegResult::Disjoint => SegSegResult::Disjoint,
+            SegSegResult::Crossing {
+                ratio_p,
+                ratio_q,
+                point,
+            } => SegSegResult::Crossing {
+                ratio_p: ratio_q,
+                ratio_q: ratio_p,
+                point,
+            },
+            SegSegResult::Touching {
+                ratio_p,
+                ratio_q,
+                point,
+            } => SegSegResult::Touching {
+                ratio_p: ratio_q,
+                ratio_q: ratio_p,
+                point,
+            },
+            SegSegResult::Overlap {
+                ratios_p,
+                ratios_q,
+                points,
+            } => {
+                // sort along the new edge `p`
+                let [i0, i1] = if ratios_q[0] <= ratios_q[1] {
+                    [0, 1]
+                } else {
+                    [1, 0]
+                };
+                SegSegResult::Overlap {
+                    ratios_p: [ratios_q[i0], ratios_q[i1]],
+                    ratios_q: [ratios_p[i0], ratios_p[i1]],
+                    points: [points[i0], points[i1]],
+                }
+            }
+        }
+    }
+}
+
+/// intersection of the collinear edges where `p0 != p1`
+fn intersection_collinear_edge2<T>(
+    p0: &[T; 2],
+    p1: &[T; 2],
+    q0: &[T; 2],
+    q1: &[T; 2],
+) -> SegSegResult<T>
 where
-    T: num_traits::Float + num_traits::FloatConst,
+    T: num_traits::Float,
+{
+    use crate::vec2::Vec2;
+    let (zero, one) = (T::zero(), T::one());
+    let d = p1.sub(p0);
+    let len2 = d.squared_norm();
+    let t0 = q0.sub(p0).dot(&d) / len2;
+    let t1 = q1.sub(p0).dot(&d) / len2;
+    // ratio on `q` of the point at the ratio `t` on `p`
+    let ratio_q = |t: T| {
+        if t == t0 {
+            zero
+        } else if t == t1 {
+            one
+        } else {
+            (t - t0) / (t1 - t0)
+        }
+    };
+    // the end points are taken from the input without rounding
+    let point = |t: T| {
+        if t == zero {
+            *p0
+        } else if t == one {
+            *p1
+        } else if t == t0 {
+            *q0
+        } else if t == t1 {
+            *q1
+        } else {
+            position_from_ratio(p0, p1, t)
+        }
+    };
+    let lo = t0.min(t1).max(zero);
+    let hi = t0.max(t1).min(one);
+    if lo > hi {
+        SegSegResult::Disjoint
+    } else if lo == hi {
+        SegSegResult::Touching {
+            ratio_p: lo,
+            ratio_q: ratio_q(lo),
+            point: point(lo),
+        }
+    } else {
+        SegSegResult::Overlap {
+            ratios_p: [lo, hi],
+            ratios_q: [ratio_q(lo), ratio_q(hi)],
+            points: [point(lo), point(hi)],
+        }
+    }
+}
+
+/// intersection of the two edges `p0`-`p1` and `q0`-`q1` distinguishing
+/// the proper crossing, the touching at an end point, the collinear overlap, and the disjoint cases.
+/// Unlike [`intersection_edge2`], the collinear and zero-length edges are handled.
+/// The classification is exact if the orientation signs are exact (e.g., small integer coordinates).
+pub fn intersection_edge2_detailed<T>(
+    p0: &[T; 2],
+    p1: &[T; 2],
+    q0: &[T; 2],
+    q1: &[T; 2],
+) -> SegSegResult<T>
+where
+    T: num_traits::Float,
+{
+    let (zero, one) = (T::zero(), T::one());
+    let a0 = crate::tri2::area(p0, p1, q0);
+    let a1 = crate::tri2::area(p0, p1, q1);
+    let a2 = crate::tri2::area(q0, q1, p0);
+    let a3 = crate::tri2::area(q0, q1, p1);
+    if a0.is_zero() && a1.is_zero() {
+        // `q` is on the line of `p`, or `p` has zero length
+        if p0 != p1 {
+            return intersection_collinear_edge2(p0, p1, q0, q1);
+        }
+        if !a2.is_zero() {
+            return SegSegResult::Disjoint;
+        }
+        if q0 != q1 {
+            return intersection_collinear_edge2(q0, q1, p0, p1).swap();
+        }
+        if p0 == q0 {
+            return SegSegResult::Touching {
+                ratio_p: zero,
+                ratio_q: zero,
+                point: *p0,
+            };
+        }
+        return SegSegResult::Disjoint;
+    }
+    if a0 * a1 > zero || a2 * a3 > zero {
+        return SegSegResult::Disjoint;
+    }
+    // one of the end points is on the other edge
+    if a0.is_zero() || a1.is_zero() {
+        let (ratio_q, point) = if a0.is_zero() {
+            (zero, *q0)
+        } else {
+            (one, *q1)
+        };
+        let ratio_p = if a2.is_zero() {
+            zero
+        } else if a3.is_zero() {
+            one
+        } else {
+            a2 / (a2 - a3)
+        };
+        return SegSegResult::Touching {
+            ratio_p,
+            ratio_q,
+            point,
+        };
+    }
+    if a2.is_zero() || a3.is_zero() {
+        let (ratio_p, point) = if a2.is_zero() {
+            (zero, *p0)
+        } else {
+            (one, *p1)
+        };
+        return SegSegResult::Touching {
+            ratio_p,
+            ratio_q: a0 / (a0 - a1),
+            point,
+        };
+    }
+    let ratio_p = a2 / (a2 - a3);
+    SegSegResult::Crossing {
+        ratio_p,
+        ratio_q: a0 / (a0 - a1),
+        point: position_from_ratio(p0, p1, ratio_p),
+    }
+}
+
+#[test]
+fn test_intersection_edge2_detailed() {
+    use crate::vec2::Vec2;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    // compare with the non-degenerate version
+    for _iter in 0..1000 {
+        let ps: [[f64; 2]; 4] = std::array::from_fn(|_| [rng.random(), rng.random()]);
+        let res = intersection_edge2_detailed(&ps[0], &ps[1], &ps[2], &ps[3]);
+        match intersection_edge2(&ps[0], &ps[1], &ps[2], &ps[3]) {
+            None => assert_eq!(res, SegSegResult::Disjoint),
+            Some((r0, r1)) => {
+                let SegSegResult::Crossing {
+                    ratio_p,
+                    ratio_q,
+                    point,
+                } = res
+                else {
+                    panic!()
+                };
+                assert!((r0 - ratio_p).abs() < 1.0e-10 && (r1 - ratio_q).abs() < 1.0e-10);
+                let q = position_from_ratio(&ps[2], &ps[3], ratio_q);
+                assert!(point.sub(&q).norm() < 1.0e-10);
+            }
+        }
+    }
+    let res = |p0: [f64; 2], p1: [f64; 2], q0: [f64; 2], q1: [f64; 2]| {
+        let res = intersection_edge2_detailed(&p0, &p1, &q0, &q1);
+        assert_eq!(res, intersection_edge2_detailed(&q0, &q1, &p0, &p1).swap());
+        res
+    };
+    // end point on the interior
+    assert_eq!(
+        res([0., 0.], [2., 0.], [1., 0.], [1., 3.]),
+        SegSegResult::Touching {
+            ratio_p: 0.5,
+            ratio_q: 0.,
+            point: [1., 0.]
+        }
+    );
+    // sharing the end point
+    assert_eq!(
+        res([0., 0.], [2., 0.], [0., 1.], [0., 0.]),
+        SegSegResult::Touching {
+            ratio_p: 0.,
+            ratio_q: 1.,
+            point: [0., 0.]
+        }
+    );
+    // collinear overlap in the opposite direction
+    assert_eq!(
+        res([0., 0.], [4., 0.], [5., 0.], [1., 0.]),
+        SegSegResult::Overlap {
+            ratios_p: [0.25, 1.],
+            ratios_q: [1., 0.25],
+            points: [[1., 0.], [4., 0.]]
+        }
+    );
+    // collinear and touching at the end point
+    assert_eq!(
+        res([0., 0.], [2., 2.], [2., 2.], [3., 3.]),
+        SegSegResult::Touching {
+            ratio_p: 1.,
+            ratio_q: 0.,
+            point: [2., 2.]
+        }
+    );
+    // collinear and separated, parallel
+    assert_eq!(
+        res([0., 0.], [1., 0.], [2., 0.], [3., 0.]),
+        SegSegResult::Disjoint
+    );
+    assert_eq!(
+        res([0., 0.], [1., 0.], [0., 1.], [1., 1.]),
+        SegSegResult::Disjoint
+    );
+    // zero-length edges
+    assert_eq!(
+        res([1., 1.], [1., 1.], [0., 0.], [4., 4.]),
+        SegSegResult::Touching {
+            ratio_p: 0.,
+            ratio_q: 0.25,
+            point: [1., 1.]
+        }
+    );
+    assert_eq!(
+        res([1., 2.], [1., 2.], [0., 0.], [4., 4.]),
+        SegSegResult::Disjoint
+    );
+    assert!(res([1., 1.], [1., 1.], [1., 1.], [1., 1.]).is_intersecting());
+}
+
+/// side of the point against the directed edge. See [`orient_point`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// counter-clockwise side
+    Left,
+    /// clockwise side
+    Right,
+    Collinear,
+}
+
+/// side of the point `q` against the directed edge `p0`-`p1`.
+/// The point is regarded as `Collinear` when the absolute value of the cross product is
+/// within the error bound of the floating point arithmetic by Shewchuk (the same as the filter of `orient2d`),
+/// so that the returned `Left` or `Right` is reliable.
+pub fn orient_point<T>(p0: &[T; 2], p1: &[T; 2], q: &[T; 2]) -> Orientation
+where
+    T: num_traits::Float,
+{
+    let det_left = (p0[0] - q[0]) * (p1[1] - q[1]);
+    let det_right = (p0[1] - q[1]) * (p1[0] - q[0]);
+    let det = det_left - det_right;
+    let three = T::one() + T::one() + T::one();
+    let sixteen = T::from(16).unwrap();
+    let err_bound =
+        (three + sixteen * T::epsilon()) * T::epsilon() * (det_left.abs() + det_right.abs());
+    if det > err_bound {
+        Orientation::Left
+    } else if det < -err_bound {
+        Orientation::Right
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// signed angle in `[-pi, pi]` subtended by the edge `ps`-`pe` seen from the point `po`.
+/// Positive if the edge goes counter-clockwise around `po`.
+/// The sum over the edges of a closed polygon is `2 * pi` times the winding number.
+pub fn winding_angle_contribution<T>(ps: &[T; 2], pe: &[T; 2], po: &[T; 2]) -> T
+where
+    T: num_traits::Float,
 {
     use crate::vec2::Vec2;
-    let half = T::one() / (T::one() + T::one());
     let p0 = ps.sub(po);
     let p1 = pe.sub(po);
     let y: T = p1[1] * p0[0] - p1[0] * p0[1];
     let x: T = p0[0] * p1[0] + p0[1] * p1[1];
-    y.atan2(x) * T::FRAC_1_PI() * half
+    y.atan2(x)
+}
+
+pub fn winding_number<T>(ps: &[T; 2], pe: &[T; 2], po: &[T; 2]) -> T
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let half = T::one() / (T::one() + T::one());
+    winding_angle_contribution(ps, pe, po) * T::FRAC_1_PI() * half
+}
+
+#[test]
+fn test_orient_point_and_winding() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..1000 {
+        let p0: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let p1: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let q: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let area = crate::tri2::area(&p0, &p1, &q);
+        match orient_point(&p0, &p1, &q) {
+            Orientation::Left => assert!(area > 0.),
+            Orientation::Right => assert!(area < 0.),
+            Orientation::Collinear => assert!(area.abs() < 1.0e-14),
+        }
+        // the winding angle has the same sign as the orientation
+        let angle = winding_angle_contribution(&p0, &p1, &q);
+        assert_eq!(angle > 0., area > 0.);
+    }
+    // nearly collinear points where the sign of the cross product is not reliable
+    let (p0, p1) = ([0.1f64, 0.1], [0.3, 0.3]);
+    assert_eq!(orient_point(&p0, &p1, &[0.7, 0.7]), Orientation::Collinear);
+    assert_eq!(orient_point(&p0, &p1, &[0.7, 0.71]), Orientation::Left);
+    assert_eq!(orient_point(&p0, &p1, &[0.7, 0.69]), Orientation::Right);
+    assert_eq!(orient_point(&p0, &p0, &[0.7, 0.69]), Orientation::Collinear);
+    // sum of the winding angles of the square
+    let square = [[0f64, 0.], [1., 0.], [1., 1.], [0., 1.]];
+    let sum = |q: &[f64; 2]| -> f64 {
+        (0..4)
+            .map(|i| winding_angle_contribution(&square[i], &square[(i + 1) % 4], q))
+            .sum()
+    };
+    assert!((sum(&[0.3, 0.6]) - 2. * std::f64::consts::PI).abs() < 1.0e-10);
+    assert!(sum(&[1.3, 0.6]).abs() < 1.0e-10);
+    let wn: f64 = (0..4)
+        .map(|i| winding_number(&square[i], &square[(i + 1) % 4], &[0.3, 0.6]))
+        .sum();
+    assert!((wn - 1.).abs() < 1.0e-10);
 }
 
 /// Find the nearest point on a line segment to the origin(0,0)
@@ -248,6 +658,120 @@ fn test_nearest_point2() {
     assert!(pm.sub(&[0., 1.]).norm() < 1.0e-5);
 }
 
+/// the same as [`crate::edge3::nearest_to_point3`] in 2D
+/// * Returns `(dist, ratio)`
+///   - `dist` : distance
+///   - `ratio`: ratio of the nearest point on the edge. `0.5` for the zero-length edge
+pub fn nearest_to_point2<T>(p0: &[T; 2], p1: &[T; 2], q: &[T; 2]) -> (T, T)
+where
+    T: num_traits::Float,
+{
+    crate::edge::nearest_to_point(p0, p1, q)
+}
+
+/// the same as [`crate::edge3::nearest_to_edge3`] in 2D
+/// * Return `(distance, ratio_p, ratio_q)`
+///
+/// For the parallel edges with overlapping ranges (collinear or not), the ratios are at the middle of the overlap.
+pub fn nearest_to_edge2<T>(p0: &[T; 2], p1: &[T; 2], q0: &[T; 2], q1: &[T; 2]) -> (T, T, T)
+where
+    T: num_traits::Float,
+{
+    let zero = T::zero();
+    let half = T::one() / (T::one() + T::one());
+    match intersection_edge2_detailed(p0, p1, q0, q1) {
+        SegSegResult::Crossing {
+            ratio_p, ratio_q, ..
+        }
+        | SegSegResult::Touching {
+            ratio_p, ratio_q, ..
+        } => (zero, ratio_p, ratio_q),
+        SegSegResult::Overlap {
+            ratios_p, ratios_q, ..
+        } => (
+            zero,
+            (ratios_p[0] + ratios_p[1]) * half,
+            (ratios_q[0] + ratios_q[1]) * half,
+        ),
+        SegSegResult::Disjoint => {
+            use crate::vec2::Vec2;
+            let (vp, vq) = (p1.sub(p0), q1.sub(q0));
+            if !vp.norm().is_zero() && !vq.norm().is_zero() && vp.cross(&vq).abs() < T::epsilon() {
+                // parallel edges. the same convention as 3D (the middle of the overlap)
+                let to3 = |p: &[T; 2]| [p[0], p[1], zero];
+                return crate::edge3::nearest_to_edge3(&to3(p0), &to3(p1), &to3(q0), &to3(q1));
+            }
+            // the nearest pair involves an end point if the edges do not intersect
+            let (d0, r0) = nearest_to_point2(q0, q1, p0);
+            let (d1, r1) = nearest_to_point2(q0, q1, p1);
+            let (d2, r2) = nearest_to_point2(p0, p1, q0);
+            let (d3, r3) = nearest_to_point2(p0, p1, q1);
+            [
+                (d0, zero, r0),
+                (d1, T::one(), r1),
+                (d2, r2, zero),
+                (d3, r3, T::one()),
+            ]
+            .into_iter()
+            .fold(
+                (T::infinity(), zero, zero),
+                |a, b| if b.0 < a.0 { b } else { a },
+            )
+        }
+    }
+}
+
+#[test]
+fn test_nearest_to_edge2() {
+    use crate::vec2::Vec2;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..1000 {
+        let ps: [[f64; 2]; 4] = std::array::from_fn(|_| [rng.random(), rng.random()]);
+        let (dist, rp, rq) = nearest_to_edge2(&ps[0], &ps[1], &ps[2], &ps[3]);
+        let pc = position_from_ratio(&ps[0], &ps[1], rp);
+        let qc = position_from_ratio(&ps[2], &ps[3], rq);
+        assert!((pc.sub(&qc).norm() - dist).abs() < 1.0e-10);
+        // agree with the 3D version on the plane z=0
+        let to3 = |p: &[f64; 2]| [p[0], p[1], 0.];
+        let (dist3, _, _) =
+            crate::edge3::nearest_to_edge3(&to3(&ps[0]), &to3(&ps[1]), &to3(&ps[2]), &to3(&ps[3]));
+        assert!((dist - dist3).abs() < 1.0e-8, "{dist} {dist3}");
+        let (dist, r) = nearest_to_point2(&ps[0], &ps[1], &ps[2]);
+        let (dist3, r3) = crate::edge3::nearest_to_point3(&to3(&ps[0]), &to3(&ps[1]), &to3(&ps[2]));
+        assert!((dist - dist3).abs() < 1.0e-10 && (r - r3).abs() < 1.0e-10);
+    }
+    // parallel with the overlapping range (the middle of the overlap as in 3D)
+    assert_eq!(
+        nearest_to_edge2(&[0., 0.], &[2., 0.], &[1., 1.], &[3., 1.]),
+        (1., 0.75, 0.25)
+    );
+    assert_eq!(
+        crate::edge3::nearest_to_edge3(&[0., 0., 0.], &[2., 0., 0.], &[1., 1., 0.], &[3., 1., 0.]),
+        (1., 0.75, 0.25)
+    );
+    assert_eq!(
+        nearest_to_edge2(&[0., 0.], &[2., 0.], &[3., 1.], &[1., 1.]),
+        (1., 0.75, 0.75)
+    );
+    // parallel without the overlap
+    assert_eq!(
+        nearest_to_edge2(&[0., 0.], &[2., 0.], &[3., 1.], &[5., 1.]),
+        (2f64.sqrt(), 1., 0.)
+    );
+    // collinear overlap
+    assert_eq!(
+        nearest_to_edge2(&[0., 0.], &[4., 0.], &[2., 0.], &[6., 0.]),
+        (0., 0.75, 0.25)
+    );
+    // collinear separated
+    assert_eq!(
+        nearest_to_edge2(&[0., 0.], &[1., 0.], &[3., 0.], &[2., 0.]),
+        (1., 1., 1.)
+    );
+}
+
 pub fn intersection_length_against_aabb2(ps: &[f32; 2], pe: &[f32; 2], aabb2: &[f32; 4]) -> f32 {
     // 0 min, 1 max
     let edge_range_x = [ps[0].min(pe[0]), ps[0].max(pe[0])];
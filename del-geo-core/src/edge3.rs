@@ -30,20 +30,14 @@ pub fn length<T>(p0: &[T; 3], p1: &[T; 3]) -> T
 where
     T: num_traits::Float,
 {
-    let x = p0[0] - p1[0];
-    let y = p0[1] - p1[1];
-    let z = p0[2] - p1[2];
-    (x * x + y * y + z * z).sqrt()
+    crate::edge::length(p0, p1)
 }
 
 pub fn squared_length<T>(p0: &[T; 3], p1: &[T; 3]) -> T
 where
     T: num_traits::Float,
 {
-    let x = p0[0] - p1[0];
-    let y = p0[1] - p1[1];
-    let z = p0[2] - p1[2];
-    x * x + y * y + z * z
+    crate::edge::squared_length(p0, p1)
 }
 
 /// `ratio==0` should output `p0`
@@ -51,12 +45,30 @@ pub fn position_from_ratio<T>(p0: &[T; 3], p1: &[T; 3], ratio: T) -> [T; 3]
 where
     T: num_traits::Float,
 {
-    let one = T::one();
-    [
-        (one - ratio) * p0[0] + ratio * p1[0],
-        (one - ratio) * p0[1] + ratio * p1[1],
-        (one - ratio) * p0[2] + ratio * p1[2],
-    ]
+    crate::edge::position_from_ratio(p0, p1, ratio)
+}
+
+/// linear interpolation. The same as [`position_from_ratio`]
+pub fn lerp<T>(p0: &[T; 3], p1: &[T; 3], ratio: T) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    crate::edge::lerp(p0, p1, ratio)
+}
+
+pub fn midpoint<T>(p0: &[T; 3], p1: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    crate::edge::midpoint(p0, p1)
+}
+
+/// AABB of the edge in the layout of [`crate::aabb3`]
+pub fn aabb<T>(p0: &[T; 3], p1: &[T; 3]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    crate::edge::aabb(p0, p1)
 }
 
 /// * Returns `(dist, ratio)`
@@ -66,26 +78,54 @@ pub fn nearest_to_point3<T>(p0: &[T; 3], p1: &[T; 3], point_pos: &[T; 3]) -> (T,
 where
     T: num_traits::Float,
 {
-    use crate::vec3::Vec3;
-    let zero = T::zero();
-    let one = T::one();
-    let half = one / (one + one);
+    crate::edge::nearest_to_point(p0, p1, point_pos)
+}
+
+/// [`nearest_to_point3`] for eight edges `(p0.lane(i), p1.lane(i))` and eight points at once
+/// * Returns `(dist, ratio)` for each lane
+pub fn nearest_to_point3x8(
+    p0: &crate::vec3x8::Vec3x8,
+    p1: &crate::vec3x8::Vec3x8,
+    point_pos: &crate::vec3x8::Vec3x8,
+) -> ([f32; crate::vec3x8::LANE], [f32; crate::vec3x8::LANE]) {
     let d = p1.sub(p0);
-    let t = {
-        if d.dot(&d) > T::epsilon() {
-            let ps = std::array::from_fn(|i| p0[i] - point_pos[i]);
-            let a = d.dot(&d);
-            let b = d.dot(&ps);
-            (-b / a).clamp(zero, one)
+    let a = d.squared_norm();
+    let b = d.dot(&p0.sub(point_pos));
+    let t: [f32; crate::vec3x8::LANE] = std::array::from_fn(|i| {
+        if a[i] > f32::EPSILON {
+            (-b[i] / a[i]).clamp(0., 1.)
         } else {
-            half
+            0.5
         }
-    };
-    let p = crate::vec3::axpy(t, &d, p0);
-    let dist = length(&p, point_pos);
+    });
+    let dist = d.scale_add(&t, p0).sub(point_pos).norm();
     (dist, t)
 }
 
+#[test]
+fn test_nearest_to_point3x8() {
+    use crate::vec3x8::{LANE, Vec3x8};
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_vecs = || -> [[f32; 3]; LANE] {
+        std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.))
+    };
+    for _itr in 0..10 {
+        let (p0, mut p1, q) = (random_vecs(), random_vecs(), random_vecs());
+        p1[0] = p0[0]; // degenerate edge
+        let (dist, t) = nearest_to_point3x8(
+            &Vec3x8::from_array(&p0),
+            &Vec3x8::from_array(&p1),
+            &Vec3x8::from_array(&q),
+        );
+        for i in 0..LANE {
+            let (dist0, t0) = nearest_to_point3(&p0[i], &p1[i], &q[i]);
+            assert!((dist[i] - dist0).abs() < 1.0e-5 && (t[i] - t0).abs() < 1.0e-5);
+        }
+    }
+}
+
 pub fn wdw_integral_of_inverse_distance_cubic<T>(
     q: &[T; 3],
     p0: &[T; 3],
@@ -186,49 +226,66 @@ mod tests {
     }
 }
 
+/// nearest points between the two edges `p0`-`p1` and `q0`-`q1`
+/// * Return `(distance, ratio_p, ratio_q)`
+/// * Panics if an edge has zero length. See [`try_nearest_to_edge3`] for the non-panicking version.
 pub fn nearest_to_edge3<T>(p0: &[T; 3], p1: &[T; 3], q0: &[T; 3], q1: &[T; 3]) -> (T, T, T)
+where
+    T: num_traits::Float,
+{
+    try_nearest_to_edge3(p0, p1, q0, q1).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// the same as [`nearest_to_edge3`] but returns the error for the degenerate input
+/// (zero-length edge or non-finite coordinates) instead of panicking
+pub fn try_nearest_to_edge3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+) -> Result<(T, T, T), crate::error::DegenerateError>
 where
     T: num_traits::Float,
 {
     use crate::vec3::Vec3;
+    crate::error::check_finite(&[p0, p1, q0, q1])?;
     let zero = T::zero();
     let one = T::one();
     let half = one / (one + one);
     let vp = p1.sub(p0);
     let vq = q1.sub(q0);
-    assert!(vp.norm() > T::zero());
-    assert!(vq.norm() > T::zero());
+    if vp.norm().is_zero() || vq.norm().is_zero() {
+        return Err(crate::error::DegenerateError::ZeroLengthEdge);
+    }
     if vp.cross(&vq).norm() < T::epsilon() {
         // handling parallel edge
+        let vp2 = vp.dot(&vp);
         let pq0 = p0.sub(q0);
-        let uvp = vp.normalize();
         // a vector vertical to vp and vq and in the plane of vp and vq
-        let vert = pq0.sub(&uvp.scale(pq0.dot(&uvp)));
+        let vert = pq0.sub(&vp.scale(pq0.dot(&vp) / vp2));
         let dist = vert.norm(); // distance betwen two edges
-        let lp0 = p0.dot(&uvp);
-        let lp1 = p1.dot(&uvp);
-        let lq0 = q0.dot(&uvp);
-        let lq1 = q1.dot(&uvp);
-        let (lp_min, lp_max, p_min, p_max, rp_min, rp_max) =
-            (lp0, lp1, p0, p1, T::zero(), T::one());
-        assert!(lp_min < lp_max);
+        // parameters along `vp` relative to `p0` (`p0` at zero and `p1` at one)
+        // so that the large coordinates far from the origin do not round them
+        let lq0 = q0.sub(p0).dot(&vp) / vp2;
+        let lq1 = q1.sub(p0).dot(&vp) / vp2;
         let (lq_min, lq_max, q_min, q_max, rq_min, rq_max) = if lq0 < lq1 {
-            (lq0, lq1, q0, q1, T::zero(), T::one())
+            (lq0, lq1, q0, q1, zero, one)
         } else {
-            (lq1, lq0, q1, q0, T::one(), T::zero())
+            (lq1, lq0, q1, q0, one, zero)
         };
-        if lp_max < lq_min {
-            return (p_max.sub(q_min).norm(), rp_max, rq_min);
+        if one < lq_min {
+            return Ok((p1.sub(q_min).norm(), one, rq_min));
         }
-        if lq_max < lp_min {
-            return (q_max.sub(p_min).norm(), rp_min, rq_max);
+        if lq_max < zero {
+            return Ok((q_max.sub(p0).norm(), zero, rq_max));
         }
-        let lm_min = lp_min.max(lq_min);
-        let lm_max = lp_max.min(lq_max);
-        let lm = (lm_min + lm_max) * half;
-        let ratio_p = (lm - lp0) / (lp1 - lp0);
-        let ratio_q = (lm - lq0) / (lq1 - lq0);
-        return (dist, ratio_p, ratio_q);
+        let ratio_p = (zero.max(lq_min) + one.min(lq_max)) * half;
+        let ratio_q = if lq0 == lq1 {
+            half
+        } else {
+            (ratio_p - lq0) / (lq1 - lq0)
+        };
+        return Ok((dist, ratio_p, ratio_q));
     }
     let (rp1, rq1) = {
         // line-line intersection
@@ -247,21 +304,21 @@ where
         // both in range
         let pc = p0.add(&vp.scale(rp1));
         let qc = q0.add(&vq.scale(rq1));
-        return (pc.sub(&qc).norm(), rp1, rq1);
+        return Ok((pc.sub(&qc).norm(), rp1, rq1));
     }
     if (zero <= rp1 && rp1 <= one) && (rq1 <= zero || one <= rq1) {
         // p in range
         let rq1 = num_traits::clamp(rq1, zero, one);
         let qc = crate::vec3::axpy(rq1, &vq, q0);
         let (dist, rp1) = nearest_to_point3(p0, p1, &qc);
-        return (dist, rp1, rq1);
+        return Ok((dist, rp1, rq1));
     }
     if (zero <= rq1 && rq1 <= one) && (rp1 <= zero || one <= rp1) {
         // q in range
         let rp1 = num_traits::clamp(rp1, zero, one);
         let pc = crate::vec3::axpy(rp1, &vp, p0);
         let (dist, rq1) = nearest_to_point3(q0, q1, &pc);
-        return (dist, rp1, rq1);
+        return Ok((dist, rp1, rq1));
     }
     // convex projection technique
     let rp1 = num_traits::clamp(rp1, zero, one);
@@ -271,7 +328,7 @@ where
     let (_dist, rp1) = nearest_to_point3(p0, p1, &qc);
     let pc = p0.add(&p1.sub(p0).scale(rp1));
     let (dist, rq1) = nearest_to_point3(q0, q1, &pc);
-    (dist, rp1, rq1)
+    Ok((dist, rp1, rq1))
 }
 
 #[test]
@@ -310,6 +367,276 @@ fn test_nearest_to_edge3() {
     }
 }
 
+#[test]
+fn test_try_nearest_to_edge3() {
+    use crate::error::DegenerateError;
+    let (p0, p1) = ([0., 0., 0.], [1., 0., 0.]);
+    let (q0, q1) = ([0., 1., 1.], [0., 1., 1.]);
+    assert_eq!(
+        try_nearest_to_edge3(&p0, &p1, &q0, &q1),
+        Err(DegenerateError::ZeroLengthEdge)
+    );
+    assert_eq!(
+        try_nearest_to_edge3(&p0, &p1, &[f64::NAN, 0., 0.], &q1),
+        Err(DegenerateError::NonFinite)
+    );
+    assert_eq!(
+        try_nearest_to_edge3(&p0, &p1, &[0., 1., 1.], &[0., 2., 1.]),
+        Ok((2f64.sqrt(), 0., 0.))
+    );
+    // parallel short edges far from the origin
+    let p0: [f64; 3] = [-937716.5214148682, 672472.9634670743, -309955.8957494195];
+    let p1 = [-937716.521414868, 672472.9634670742, -309955.89574941946];
+    let q1 = std::array::from_fn(|i| p1[i] - p0[i]);
+    let (dist, rp, rq) = try_nearest_to_edge3(&p0, &p1, &[0.; 3], &q1).unwrap();
+    assert!(dist.is_finite() && (0. ..=1.).contains(&rp) && (0. ..=1.).contains(&rq));
+}
+
+/// feature of the edge that realizes the nearest point. See [`nearest_to_edge3_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeFeature {
+    /// the first end point (ratio is zero)
+    Vertex0,
+    /// the second end point (ratio is one)
+    Vertex1,
+    /// the point strictly between the end points
+    Interior,
+}
+
+impl EdgeFeature {
+    fn from_ratio<T>(r: T) -> Self
+    where
+        T: num_traits::Float,
+    {
+        if r.is_zero() {
+            EdgeFeature::Vertex0
+        } else if r.is_one() {
+            EdgeFeature::Vertex1
+        } else {
+            EdgeFeature::Interior
+        }
+    }
+
+    pub fn is_vertex(&self) -> bool {
+        *self != EdgeFeature::Interior
+    }
+}
+
+/// result of [`nearest_to_edge3_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeEdgeNearest<T> {
+    pub dist: T,
+    /// ratio on the edge `p` (`p0` at zero and `p1` at one)
+    pub ratio_p: T,
+    /// ratio on the edge `q` (`q0` at zero and `q1` at one)
+    pub ratio_q: T,
+    /// witness point on the edge `p`
+    pub point_p: [T; 3],
+    /// witness point on the edge `q`
+    pub point_q: [T; 3],
+    pub feature_p: EdgeFeature,
+    pub feature_q: EdgeFeature,
+}
+
+impl<T> EdgeEdgeNearest<T> {
+    /// both of the witness points are end points
+    pub fn is_vertex_vertex(&self) -> bool {
+        self.feature_p.is_vertex() && self.feature_q.is_vertex()
+    }
+
+    /// one of the witness points is an end point and the other is in the interior
+    pub fn is_vertex_interior(&self) -> bool {
+        self.feature_p.is_vertex() != self.feature_q.is_vertex()
+    }
+
+    /// both of the witness points are in the interior of the edges
+    pub fn is_interior_interior(&self) -> bool {
+        !self.feature_p.is_vertex() && !self.feature_q.is_vertex()
+    }
+}
+
+/// the same as [`nearest_to_edge3`] but also returns the witness points
+/// and the features (end point or interior) of the two edges that realize the minimum.
+/// For the overlapping parallel edges, the witness points are the middle of the overlap.
+pub fn nearest_to_edge3_detailed<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+) -> EdgeEdgeNearest<T>
+where
+    T: num_traits::Float,
+{
+    let (dist, ratio_p, ratio_q) = nearest_to_edge3(p0, p1, q0, q1);
+    let lerp = |a: &[T; 3], b: &[T; 3], r: T| -> [T; 3] {
+        match EdgeFeature::from_ratio(r) {
+            EdgeFeature::Vertex0 => *a,
+            EdgeFeature::Vertex1 => *b,
+            EdgeFeature::Interior => std::array::from_fn(|i| a[i] + (b[i] - a[i]) * r),
+        }
+    };
+    EdgeEdgeNearest {
+        dist,
+        ratio_p,
+        ratio_q,
+        point_p: lerp(p0, p1, ratio_p),
+        point_q: lerp(q0, q1, ratio_q),
+        feature_p: EdgeFeature::from_ratio(ratio_p),
+        feature_q: EdgeFeature::from_ratio(ratio_q),
+    }
+}
+
+#[test]
+fn test_nearest_to_edge3_detailed() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_class = [0; 3];
+    for _i in 0..1000 {
+        let p0 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let p1 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let q0 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let q1 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let res = nearest_to_edge3_detailed(&p0, &p1, &q0, &q1);
+        assert!((res.point_p.sub(&res.point_q).norm() - res.dist).abs() < 1.0e-10);
+        let d = res.point_q.sub(&res.point_p);
+        // the vector between the witness points is orthogonal to the edge at the interior point
+        if res.feature_p == EdgeFeature::Interior {
+            assert!(d.dot(&p1.sub(&p0)).abs() < 1.0e-6);
+        }
+        if res.feature_q == EdgeFeature::Interior {
+            assert!(d.dot(&q1.sub(&q0)).abs() < 1.0e-6);
+        }
+        let i_class = [
+            res.is_vertex_vertex(),
+            res.is_vertex_interior(),
+            res.is_interior_interior(),
+        ]
+        .iter()
+        .position(|&b| b)
+        .unwrap();
+        num_class[i_class] += 1;
+    }
+    assert!(num_class.iter().all(|&n| n > 10), "{num_class:?}");
+    // crossing edges
+    let res =
+        nearest_to_edge3_detailed(&[-1., 0., 0.], &[1., 0., 0.], &[0., -1., 1.], &[0., 1., 1.]);
+    assert_eq!(res.point_p, [0., 0., 0.]);
+    assert_eq!(res.point_q, [0., 0., 1.]);
+    assert!(res.is_interior_interior());
+    // end point against the interior
+    let res =
+        nearest_to_edge3_detailed(&[0., 0., 0.], &[1., 0., 0.], &[2., -1., 1.], &[2., 1., 1.]);
+    assert_eq!(res.feature_p, EdgeFeature::Vertex1);
+    assert_eq!(res.feature_q, EdgeFeature::Interior);
+    assert_eq!(res.point_q, [2., 0., 1.]);
+}
+
+/// distance between the edge `p0`-`p1` and the point `q`, and its gradient
+/// * Return `(dist, [d(dist)/d(p0), d(dist)/d(p1), d(dist)/d(q)])`
+///
+/// As the ratio minimizes the distance, its derivative does not contribute
+/// (the same holds when the ratio is clamped at the end point).
+/// The gradient is zero if the point is on the edge.
+pub fn wdw_nearest_to_point3<T>(p0: &[T; 3], p1: &[T; 3], q: &[T; 3]) -> (T, [[T; 3]; 3])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let one = T::one();
+    let (dist, r) = nearest_to_point3(p0, p1, q);
+    if dist.is_zero() {
+        return (dist, [[T::zero(); 3]; 3]);
+    }
+    let u = position_from_ratio(p0, p1, r).sub(q).scale(one / dist);
+    (dist, [u.scale(one - r), u.scale(r), u.scale(-one)])
+}
+
+/// distance between the two edges `p0`-`p1` and `q0`-`q1`, and its gradient
+/// * Return `(dist, [d(dist)/d(p0), d(dist)/d(p1), d(dist)/d(q0), d(dist)/d(q1)])`
+///
+/// The clamped cases (the nearest point at the end point) are handled in the same way as [`wdw_nearest_to_point3`].
+/// The gradient is zero if the edges intersect, and it is not unique for the parallel edges.
+pub fn wdw_nearest_to_edge3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+) -> (T, [[T; 3]; 4])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let one = T::one();
+    let (dist, rp, rq) = nearest_to_edge3(p0, p1, q0, q1);
+    if dist.is_zero() {
+        return (dist, [[T::zero(); 3]; 4]);
+    }
+    let pc = position_from_ratio(p0, p1, rp);
+    let qc = position_from_ratio(q0, q1, rq);
+    let u = pc.sub(&qc).scale(one / dist);
+    (
+        dist,
+        [
+            u.scale(one - rp),
+            u.scale(rp),
+            u.scale(rq - one),
+            u.scale(-rq),
+        ],
+    )
+}
+
+#[test]
+fn test_wdw_nearest_to_edge3() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let eps = 1.0e-5;
+    let mut num_clamped = 0;
+    for _i in 0..1000 {
+        let mut ps: [[f64; 3]; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut reng));
+        // edge-edge
+        let (dist0, dw0) = wdw_nearest_to_edge3(&ps[0], &ps[1], &ps[2], &ps[3]);
+        if dist0 < 0.05 {
+            continue;
+        }
+        let (_, rp, rq) = nearest_to_edge3(&ps[0], &ps[1], &ps[2], &ps[3]);
+        if rp == 0. || rp == 1. || rq == 0. || rq == 1. {
+            num_clamped += 1;
+        }
+        for (i_node, i_dim) in itertools::iproduct!(0..4, 0..3) {
+            let tmp = ps[i_node][i_dim];
+            ps[i_node][i_dim] = tmp + eps;
+            let (dist1, _) = wdw_nearest_to_edge3(&ps[0], &ps[1], &ps[2], &ps[3]);
+            ps[i_node][i_dim] = tmp - eps;
+            let (dist2, _) = wdw_nearest_to_edge3(&ps[0], &ps[1], &ps[2], &ps[3]);
+            ps[i_node][i_dim] = tmp;
+            let v_num = (dist1 - dist2) / (2. * eps);
+            assert!(
+                (v_num - dw0[i_node][i_dim]).abs() < 1.0e-4,
+                "{v_num} {dw0:?}"
+            );
+        }
+        // point-edge
+        let (dist0, dw0) = wdw_nearest_to_point3(&ps[0], &ps[1], &ps[2]);
+        for (i_node, i_dim) in itertools::iproduct!(0..3, 0..3) {
+            let tmp = ps[i_node][i_dim];
+            ps[i_node][i_dim] = tmp + eps;
+            let (dist1, _) = wdw_nearest_to_point3(&ps[0], &ps[1], &ps[2]);
+            ps[i_node][i_dim] = tmp - eps;
+            let (dist2, _) = wdw_nearest_to_point3(&ps[0], &ps[1], &ps[2]);
+            ps[i_node][i_dim] = tmp;
+            let v_num = (dist1 - dist2) / (2. * eps);
+            assert!((v_num - dw0[i_node][i_dim]).abs() < 1.0e-4 * (1. + 1. / dist0));
+        }
+        // translation invariance
+        let sum = dw0[0].add(&dw0[1]).add(&dw0[2]);
+        assert!(sum.norm() < 1.0e-10);
+    }
+    assert!(num_clamped > 100, "{num_clamped}");
+}
+
 /// the two edges need to be co-planar
 pub fn intersection_edge3_when_coplanar<T>(
     p0: &[T; 3],
@@ -348,3 +675,156 @@ where
     let (rq0, rq1) = (rq0 * t, -rq1 * t);
     Some((rp0, rp1, rq0, rq1))
 }
+
+/// intersection of the segment `p0`-`p1` against the plane passing through `o` with the normal `n`.
+/// The segment touching the plane at the end point is regarded as intersecting.
+/// * Return `(ratio, position)` where `position = (1 - ratio) * p0 + ratio * p1`, or
+///   `None` if the segment is on one side of the plane or parallel to the plane
+pub fn intersection_against_plane<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    o: &[T; 3],
+    n: &[T; 3],
+) -> Option<(T, [T; 3])>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let d0 = p0.sub(o).dot(n);
+    let d1 = p1.sub(o).dot(n);
+    if (d0 > T::zero() && d1 > T::zero()) || (d0 < T::zero() && d1 < T::zero()) || d0 == d1 {
+        return None;
+    }
+    let ratio = d0 / (d0 - d1);
+    Some((ratio, position_from_ratio(p0, p1, ratio)))
+}
+
+/// intersection of the segment `p0`-`p1` against the triangle `q0`-`q1`-`q2`
+/// by the Möller–Trumbore algorithm (see [`crate::tri3::intersection_against_line`])
+/// restricted to the segment.
+/// * Return `(ratio, barycentric coordinates of the triangle)` where
+///   `(1 - ratio) * p0 + ratio * p1 = r0 * q0 + r1 * q1 + r2 * q2`, or
+///   `None` if there is no intersection or the segment is parallel to the triangle
+pub fn intersection_against_tri3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+) -> Option<(T, [T; 3])>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let (zero, one) = (T::zero(), T::one());
+    let dir = p1.sub(p0);
+    let edge1 = q1.sub(q0);
+    let edge2 = q2.sub(q0);
+    let pvec = dir.cross(&edge2);
+    let det = edge1.dot(&pvec);
+    // relative to the scale, as `det` is proportional to the length of the segment times the area
+    if det.abs() <= T::epsilon() * dir.norm() * edge1.cross(&edge2).norm() {
+        return None;
+    }
+    let invdet = one / det;
+    let tvec = p0.sub(q0);
+    let u = invdet * tvec.dot(&pvec);
+    if u < zero || u > one {
+        return None;
+    }
+    let qvec = tvec.cross(&edge1);
+    let v = invdet * dir.dot(&qvec);
+    if v < zero || u + v > one {
+        return None;
+    }
+    let t = invdet * edge2.dot(&qvec);
+    if t < zero || t > one {
+        return None;
+    }
+    Some((t, [one - u - v, u, v]))
+}
+
+/// the segment `p0`-`p1` overlaps the AABB (including the boundary). See [`crate::aabb3::clip_edge`]
+pub fn intersect_aabb3<T>(p0: &[T; 3], p1: &[T; 3], aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    crate::aabb3::clip_edge(aabb, p0, p1).is_some()
+}
+
+#[test]
+fn test_intersection_against_primitives() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let (mut num_plane, mut num_tri, mut num_aabb) = (0, 0, 0);
+    for _iter in 0..1000 {
+        let (p0, p1) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let (q0, q1, q2) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let dir = p1.sub(&p0);
+        // plane
+        let n = crate::tri3::normal(&q0, &q1, &q2);
+        let sd = |p: &[f64; 3]| crate::plane::signed_distance(&q0, &n, p);
+        match intersection_against_plane(&p0, &p1, &q0, &n) {
+            Some((ratio, pos)) => {
+                num_plane += 1;
+                assert!((0. ..=1.).contains(&ratio));
+                assert!(sd(&pos).abs() < 1.0e-10);
+                assert!(pos.sub(&p0.add(&dir.scale(ratio))).norm() < 1.0e-10);
+            }
+            None => assert!(sd(&p0) * sd(&p1) > 0.),
+        }
+        // triangle (the same as the line version within the segment)
+        let res = intersection_against_tri3(&p0, &p1, &q0, &q1, &q2);
+        let t = crate::tri3::intersection_against_line(&q0, &q1, &q2, &p0, &dir)
+            .filter(|t| (0. ..=1.).contains(t));
+        assert_eq!(res.map(|v| v.0), t);
+        if let Some((ratio, bc)) = res {
+            num_tri += 1;
+            let pos = crate::tri3::position_from_barycentric_coords(&q0, &q1, &q2, &bc);
+            assert!(pos.sub(&p0.add(&dir.scale(ratio))).norm() < 1.0e-10);
+            assert!(bc.iter().all(|&r| r >= 0.) && (bc[0] + bc[1] + bc[2] - 1.).abs() < 1.0e-10);
+        }
+        // aabb
+        let aabb = [-0.3, -0.2, -0.1, 0.1, 0.2, 0.3];
+        let is_hit = crate::aabb::intersections_against_line::<f64, 3, 6>(&aabb, &p0, &dir)
+            .is_some_and(|(t0, t1)| t0 <= 1. && t1 >= 0.);
+        assert_eq!(intersect_aabb3(&p0, &p1, &aabb), is_hit);
+        if is_hit {
+            num_aabb += 1;
+        }
+    }
+    assert!(num_plane > 100 && num_tri > 10 && num_aabb > 50);
+    // touching the plane at the end point
+    let res = intersection_against_plane(&[0., 0., 1.], &[0., 0., 0.], &[0.; 3], &[0., 0., 1.]);
+    assert_eq!(res, Some((1., [0., 0., 0.])));
+    // parallel
+    let res = intersection_against_plane(&[0., 0., 0.], &[1., 0., 0.], &[0.; 3], &[0., 0., 1.]);
+    assert_eq!(res, None);
+    // the segment ends before the triangle while the line hits it
+    let tri = ([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+    let (p0, p1) = ([0.2, 0.2, 1.], [0.2, 0.2, 0.5]);
+    assert_eq!(
+        intersection_against_tri3(&p0, &p1, &tri.0, &tri.1, &tri.2),
+        None
+    );
+    // small geometry (1mm) in f32 is not parallel
+    let tri = ([0f32, 0., 0.], [1.0e-3, 0., 0.], [0., 1.0e-3, 0.]);
+    let (p0, p1) = ([2.0e-4, 2.0e-4, 5.0e-4], [2.0e-4, 2.0e-4, -5.0e-4]);
+    let (t, bc) = intersection_against_tri3(&p0, &p1, &tri.0, &tri.1, &tri.2).unwrap();
+    assert!((t - 0.5).abs() < 1.0e-5);
+    assert!(crate::vec3::distance(&bc, &[0.6, 0.2, 0.2]) < 1.0e-5);
+    // parallel
+    let (p0, p1) = ([2.0e-4, 2.0e-4, 0.], [3.0e-4, 2.0e-4, 0.]);
+    assert_eq!(
+        intersection_against_tri3(&p0, &p1, &tri.0, &tri.1, &tri.2),
+        None
+    );
+}
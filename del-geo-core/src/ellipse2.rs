@@ -0,0 +1,177 @@
+//! methods for 2D ellipse.
+//! data structure `&[Real;6]` in the same layout as [`crate::obb2`]
+//! first 2 Reals are for center
+//! next 2 Reals are for the first semi-axis vector
+//! next 2 Reals are for the second semi-axis vector (orthogonal to the first one)
+
+use crate::vec2::Vec2;
+
+/// root of `(r0*z0/(w+r0-1))^2 + (z1/w)^2 - 1 = 0` for `w > 0` by the bisection.
+/// `g` is the value of the function at `w = 1`.
+/// The variable `w = s + 1` of Eberly's `s` avoids the cancellation when `z1` is tiny.
+fn root_for_nearest<T>(r0: T, z0: T, z1: T, g: T) -> T
+where
+    T: num_traits::Float,
+{
+    let (zero, one) = (T::zero(), T::one());
+    let half = one / (one + one);
+    let (n0, r0m1) = (r0 * z0, r0 - one);
+    let mut w0 = z1;
+    let mut w1 = if g < zero { one } else { n0.hypot(z1) };
+    let mut w = one;
+    for _itr in 0..256 {
+        w = (w0 + w1) * half;
+        if w == w0 || w == w1 {
+            break;
+        }
+        let ratio0 = n0 / (w + r0m1);
+        let ratio1 = z1 / w;
+        let g = ratio0 * ratio0 + ratio1 * ratio1 - one;
+        if g > zero {
+            w0 = w;
+        } else if g < zero {
+            w1 = w;
+        } else {
+            break;
+        }
+    }
+    w
+}
+
+/// nearest point on the boundary of the axis-aligned ellipse `(x0/e0)^2 + (x1/e1)^2 = 1`
+/// for the point `y` in the first quadrant (i.e., `y0, y1 >= 0`) and `e0 >= e1 > 0`.
+///
+/// This is the robust bisection method by Eberly,
+/// "Distance from a Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid".
+pub fn nearest_to_point_on_boundary_sorted_first_quadrant<T>(
+    &[e0, e1]: &[T; 2],
+    &[y0, y1]: &[T; 2],
+) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    let (zero, one) = (T::zero(), T::one());
+    if y1 > zero {
+        if y0 > zero {
+            let (z0, z1) = (y0 / e0, y1 / e1);
+            let g = z0 * z0 + z1 * z1 - one;
+            if g == zero {
+                return [y0, y1];
+            }
+            let r0 = (e0 / e1) * (e0 / e1);
+            let w = root_for_nearest(r0, z0, z1, g);
+            [r0 * y0 / (w + r0 - one), y1 / w]
+        } else {
+            [zero, e1]
+        }
+    } else {
+        let numer0 = e0 * y0;
+        let denom0 = e0 * e0 - e1 * e1;
+        if numer0 < denom0 {
+            let xde0 = numer0 / denom0;
+            [e0 * xde0, e1 * (one - xde0 * xde0).sqrt()]
+        } else {
+            [e0, zero]
+        }
+    }
+}
+
+/// the two semi-axis lengths and unit vectors
+fn radii_and_unit_axes<T>(elli: &[T; 6]) -> ([T; 2], [[T; 2]; 2])
+where
+    T: num_traits::Float,
+{
+    let u = [elli[2], elli[3]];
+    let v = [elli[4], elli[5]];
+    let (ru, rv) = (u.norm(), v.norm());
+    ([ru, rv], [u.scale(T::one() / ru), v.scale(T::one() / rv)])
+}
+
+pub fn is_include_point<T>(elli: &[T; 6], p: &[T; 2]) -> bool
+where
+    T: num_traits::Float,
+{
+    let (r, axes) = radii_and_unit_axes(elli);
+    let d = p.sub(&[elli[0], elli[1]]);
+    let x0 = d.dot(&axes[0]) / r[0];
+    let x1 = d.dot(&axes[1]) / r[1];
+    x0 * x0 + x1 * x1 <= T::one()
+}
+
+/// nearest point on the boundary of the ellipse.
+/// The point on the boundary is returned even if `p` is inside.
+pub fn nearest_to_point_on_boundary<T>(elli: &[T; 6], p: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    let (r, axes) = radii_and_unit_axes(elli);
+    let c = [elli[0], elli[1]];
+    let d = p.sub(&c);
+    let y = [d.dot(&axes[0]), d.dot(&axes[1])];
+    // sort the axes in the decreasing order of the radius
+    let (i0, i1) = if r[0] >= r[1] { (0, 1) } else { (1, 0) };
+    let x = nearest_to_point_on_boundary_sorted_first_quadrant(
+        &[r[i0], r[i1]],
+        &[y[i0].abs(), y[i1].abs()],
+    );
+    let mut xl = [T::zero(); 2];
+    xl[i0] = x[0].copysign(y[i0]);
+    xl[i1] = x[1].copysign(y[i1]);
+    c.add(&axes[0].scale(xl[0])).add(&axes[1].scale(xl[1]))
+}
+
+/// distance from the point to the boundary of the ellipse
+pub fn distance_to_point_on_boundary<T>(elli: &[T; 6], p: &[T; 2]) -> T
+where
+    T: num_traits::Float,
+{
+    p.sub(&nearest_to_point_on_boundary(elli, p)).norm()
+}
+
+#[test]
+fn test_nearest_to_point_on_boundary() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..300 {
+        let c: [f64; 2] = [rng.random(), rng.random()];
+        let theta = rng.random::<f64>() * std::f64::consts::PI;
+        let (ru, rv) = (0.1 + rng.random::<f64>(), 0.1 + rng.random::<f64>());
+        // circle and the very flat ellipse
+        let rv = match i_iter {
+            0 => ru,
+            1 => ru * 1.0e-3,
+            _ => rv,
+        };
+        let (s, t) = theta.sin_cos();
+        let elli = [c[0], c[1], ru * t, ru * s, -rv * s, rv * t];
+        let p: [f64; 2] = match i_iter % 3 {
+            0 => [c[0] + ru * t * 0.5, c[1] + ru * s * 0.5], // on the axis
+            _ => [
+                4. * rng.random::<f64>() - 1.5,
+                4. * rng.random::<f64>() - 1.5,
+            ],
+        };
+        let q = nearest_to_point_on_boundary(&elli, &p);
+        // on the boundary
+        let d = q.sub(&c);
+        let (x0, x1) = ((d[0] * t + d[1] * s) / ru, (-d[0] * s + d[1] * t) / rv);
+        assert!((x0 * x0 + x1 * x1 - 1.).abs() < 1.0e-8);
+        // compare with the sampling of the boundary
+        let dist = distance_to_point_on_boundary(&elli, &p);
+        let dist_min = (0..10000)
+            .map(|i| {
+                let phi = i as f64 / 10000. * 2. * std::f64::consts::PI;
+                let b = [
+                    c[0] + ru * t * phi.cos() - rv * s * phi.sin(),
+                    c[1] + ru * s * phi.cos() + rv * t * phi.sin(),
+                ];
+                b.sub(&p).norm()
+            })
+            .fold(f64::MAX, f64::min);
+        assert!(dist <= dist_min + 1.0e-10);
+        assert!(dist >= dist_min - 1.0e-3 * ru.max(rv));
+    }
+    assert!(is_include_point(&[0., 0., 2., 0., 0., 1.], &[1.9, 0.]));
+    assert!(!is_include_point(&[0., 0., 2., 0., 0., 1.], &[0., 1.1]));
+}
@@ -0,0 +1,303 @@
+//! methods for 3D ellipsoid.
+//! data structure `&[Real;12]` in the same layout as [`crate::obb3`]
+//! first 3 Reals are for center
+//! next 3x3 Reals are for the three mutually orthogonal semi-axis vectors
+
+use crate::vec3::Vec3;
+
+/// the three semi-axis lengths and unit vectors
+pub fn radii_and_unit_axes<T>(elli: &[T; 12]) -> ([T; 3], [[T; 3]; 3])
+where
+    T: num_traits::Float,
+{
+    let axes: [[T; 3]; 3] =
+        std::array::from_fn(|i| [elli[3 + i * 3], elli[4 + i * 3], elli[5 + i * 3]]);
+    let radii = axes.map(|u| u.norm());
+    (
+        radii,
+        std::array::from_fn(|i| axes[i].scale(T::one() / radii[i])),
+    )
+}
+
+/// coordinates of the point in the frame where the ellipsoid is the unit sphere
+fn to_unit_sphere<T>(elli: &[T; 12], v: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| {
+        let u = [elli[3 + i * 3], elli[4 + i * 3], elli[5 + i * 3]];
+        v.dot(&u) / u.squared_norm()
+    })
+}
+
+/// range of the line parameter inside the ellipsoid.
+/// Computed as the intersection against the unit sphere after the affine transformation,
+/// which does not change the line parameter.
+/// * Return `(t_min, t_max)` where `t_*` is a ratio of `line_dir` not distance.
+pub fn intersections_against_line<T>(
+    elli: &[T; 12],
+    line_org: &[T; 3],
+    line_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    let o = to_unit_sphere(elli, &line_org.sub(&[elli[0], elli[1], elli[2]]));
+    let d = to_unit_sphere(elli, line_dir);
+    let a = d.squared_norm();
+    let b = o.dot(&d);
+    let c = o.squared_norm() - T::one();
+    let det = b * b - a * c;
+    if det < T::zero() {
+        return None;
+    }
+    let det = det.sqrt();
+    Some(((-b - det) / a, (-b + det) / a))
+}
+
+/// the same as [`intersections_against_line`] but `None` if the range is behind the ray origin.
+/// The returned `t_min` is negative if the origin is inside.
+pub fn intersections_against_ray<T>(
+    elli: &[T; 12],
+    ray_org: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<(T, T)>
+where
+    T: num_traits::Float,
+{
+    intersections_against_line(elli, ray_org, ray_dir).filter(|(_t_min, t_max)| *t_max >= T::zero())
+}
+
+pub fn is_include_point<T>(elli: &[T; 12], p: &[T; 3]) -> bool
+where
+    T: num_traits::Float,
+{
+    to_unit_sphere(elli, &p.sub(&[elli[0], elli[1], elli[2]])).squared_norm() <= T::one()
+}
+
+/// tight AABB of the ellipsoid
+pub fn aabb3<T>(elli: &[T; 12]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let e: [T; 3] = std::array::from_fn(|k| {
+        (elli[3 + k] * elli[3 + k] + elli[6 + k] * elli[6 + k] + elli[9 + k] * elli[9 + k]).sqrt()
+    });
+    [
+        elli[0] - e[0],
+        elli[1] - e[1],
+        elli[2] - e[2],
+        elli[0] + e[0],
+        elli[1] + e[1],
+        elli[2] + e[2],
+    ]
+}
+
+/// root of `sum_i (r_i*z_i/(w+r_i-1))^2 - 1 = 0` with `r_2 = 1` for `w > 0` by the bisection.
+/// `g` is the value of the function at `w = 1`.
+/// The variable `w = s + 1` of Eberly's `s` avoids the cancellation when `z2` is tiny.
+fn root_for_nearest<T>(r0: T, r1: T, z0: T, z1: T, z2: T, g: T) -> T
+where
+    T: num_traits::Float,
+{
+    let (zero, one) = (T::zero(), T::one());
+    let half = one / (one + one);
+    let (n0, n1) = (r0 * z0, r1 * z1);
+    let (r0m1, r1m1) = (r0 - one, r1 - one);
+    let mut w0 = z2;
+    let mut w1 = if g < zero { one } else { [n0, n1, z2].norm() };
+    let mut w = one;
+    for _itr in 0..256 {
+        w = (w0 + w1) * half;
+        if w == w0 || w == w1 {
+            break;
+        }
+        let ratio0 = n0 / (w + r0m1);
+        let ratio1 = n1 / (w + r1m1);
+        let ratio2 = z2 / w;
+        let g = ratio0 * ratio0 + ratio1 * ratio1 + ratio2 * ratio2 - one;
+        if g > zero {
+            w0 = w;
+        } else if g < zero {
+            w1 = w;
+        } else {
+            break;
+        }
+    }
+    w
+}
+
+/// nearest point on the surface of the axis-aligned ellipsoid `sum_i (x_i/e_i)^2 = 1`
+/// for the point `y` in the first octant (i.e., `y_i >= 0`) and `e0 >= e1 >= e2 > 0`.
+///
+/// This is the robust bisection method by Eberly,
+/// "Distance from a Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid".
+/// The degenerate cases fall back to [`crate::ellipse2::nearest_to_point_on_boundary_sorted_first_quadrant`].
+pub fn nearest_to_point_on_surface_sorted_first_octant<T>(
+    &[e0, e1, e2]: &[T; 3],
+    &[y0, y1, y2]: &[T; 3],
+) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    use crate::ellipse2::nearest_to_point_on_boundary_sorted_first_quadrant as nearest2;
+    let (zero, one) = (T::zero(), T::one());
+    if y2 > zero {
+        if y1 > zero {
+            if y0 > zero {
+                let (z0, z1, z2) = (y0 / e0, y1 / e1, y2 / e2);
+                let g = z0 * z0 + z1 * z1 + z2 * z2 - one;
+                if g == zero {
+                    return [y0, y1, y2];
+                }
+                let r0 = (e0 / e2) * (e0 / e2);
+                let r1 = (e1 / e2) * (e1 / e2);
+                let w = root_for_nearest(r0, r1, z0, z1, z2, g);
+                [r0 * y0 / (w + r0 - one), r1 * y1 / (w + r1 - one), y2 / w]
+            } else {
+                let [x1, x2] = nearest2(&[e1, e2], &[y1, y2]);
+                [zero, x1, x2]
+            }
+        } else if y0 > zero {
+            let [x0, x2] = nearest2(&[e0, e2], &[y0, y2]);
+            [x0, zero, x2]
+        } else {
+            [zero, zero, e2]
+        }
+    } else {
+        let (denom0, denom1) = (e0 * e0 - e2 * e2, e1 * e1 - e2 * e2);
+        let (numer0, numer1) = (e0 * y0, e1 * y1);
+        if numer0 < denom0 && numer1 < denom1 {
+            let (xde0, xde1) = (numer0 / denom0, numer1 / denom1);
+            let discr = one - xde0 * xde0 - xde1 * xde1;
+            if discr > zero {
+                return [e0 * xde0, e1 * xde1, e2 * discr.sqrt()];
+            }
+        }
+        let [x0, x1] = nearest2(&[e0, e1], &[y0, y1]);
+        [x0, x1, zero]
+    }
+}
+
+/// nearest point on the surface of the ellipsoid.
+/// The point on the surface is returned even if `p` is inside.
+pub fn nearest_to_point_on_surface<T>(elli: &[T; 12], p: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let (r, axes) = radii_and_unit_axes(elli);
+    let c = [elli[0], elli[1], elli[2]];
+    let d = p.sub(&c);
+    let y = axes.map(|u| d.dot(&u));
+    // sort the axes in the decreasing order of the radius
+    let mut idx = [0, 1, 2];
+    idx.sort_by(|&i, &j| crate::polynomial_root::cmp_nan_last(&r[j], &r[i]));
+    let x = nearest_to_point_on_surface_sorted_first_octant(
+        &idx.map(|i| r[i]),
+        &idx.map(|i| y[i].abs()),
+    );
+    let mut q = c;
+    for k in 0..3 {
+        let i = idx[k];
+        q = q.add(&axes[i].scale(x[k].copysign(y[i])));
+    }
+    q
+}
+
+/// distance from the point to the surface of the ellipsoid
+pub fn distance_to_point_on_surface<T>(elli: &[T; 12], p: &[T; 3]) -> T
+where
+    T: num_traits::Float,
+{
+    crate::vec3::distance(p, &nearest_to_point_on_surface(elli, p))
+}
+
+#[cfg(test)]
+fn from_random_for_test(rng: &mut impl rand::Rng, radii: &[f64; 3]) -> [f64; 12] {
+    let c: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    let q: [f64; 4] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    let r = crate::quaternion::to_mat3_col_major(&crate::quaternion::normalized(&q));
+    [
+        c[0],
+        c[1],
+        c[2],
+        r[0] * radii[0],
+        r[1] * radii[0],
+        r[2] * radii[0],
+        r[3] * radii[1],
+        r[4] * radii[1],
+        r[5] * radii[1],
+        r[6] * radii[2],
+        r[7] * radii[2],
+        r[8] * radii[2],
+    ]
+}
+
+#[test]
+fn test_intersections_against_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..100 {
+        let radii: [f64; 3] = std::array::from_fn(|_| 0.1 + rng.random::<f64>());
+        let elli = from_random_for_test(&mut rng, &radii);
+        let org: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let dir = [elli[0], elli[1], elli[2]]
+            .sub(&org)
+            .add(&[rng.random(), 0., 0.]);
+        let Some((t0, t1)) = intersections_against_ray(&elli, &org, &dir) else {
+            continue;
+        };
+        num_hit += 1;
+        for t in [t0, t1] {
+            let p = org.add(&dir.scale(t));
+            assert!(distance_to_point_on_surface(&elli, &p) < 1.0e-8);
+        }
+        let pm = org.add(&dir.scale((t0 + t1) * 0.5));
+        assert!(is_include_point(&elli, &pm));
+        let aabb = aabb3(&elli);
+        assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &pm) == 0.);
+    }
+    assert!(num_hit > 30);
+}
+
+#[test]
+fn test_nearest_to_point_on_surface() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..300 {
+        let radii: [f64; 3] = match i_iter {
+            0 => [0.5, 0.5, 0.5],
+            1 => [1.0, 0.5, 0.5],
+            2 => [1.0, 1.0, 1.0e-3],
+            _ => std::array::from_fn(|_| 0.1 + rng.random::<f64>()),
+        };
+        let elli = from_random_for_test(&mut rng, &radii);
+        let c = [elli[0], elli[1], elli[2]];
+        let p: [f64; 3] = match i_iter % 3 {
+            0 => c.add(&[elli[3], elli[4], elli[5]].scale(0.3)), // on the axis
+            _ => std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.),
+        };
+        let q = nearest_to_point_on_surface(&elli, &p);
+        let x = to_unit_sphere(&elli, &q.sub(&c));
+        assert!((x.squared_norm() - 1.).abs() < 1.0e-8);
+        // compare with the sampling of the surface
+        let dist = distance_to_point_on_surface(&elli, &p);
+        let (_, axes) = radii_and_unit_axes(&elli);
+        let dist_min = (0..20000)
+            .map(|_| {
+                let s: [f64; 3] =
+                    crate::sphere::sample_surface_uniform(&[rng.random(), rng.random()]);
+                let b = (0..3).fold(c, |b, i| b.add(&axes[i].scale(radii[i] * s[i])));
+                crate::vec3::distance(&b, &p)
+            })
+            .fold(f64::MAX, f64::min);
+        assert!(dist <= dist_min + 1.0e-10);
+        assert!(dist >= dist_min - 0.05 * radii.iter().fold(0f64, |a, &b| a.max(b)));
+    }
+    // a NaN radius does not panic
+    let elli = [0., 0., 0., f64::NAN, 0., 0., 0., 1., 0., 0., 0., 1.];
+    let _ = nearest_to_point_on_surface(&elli, &[1., 1., 1.]);
+}
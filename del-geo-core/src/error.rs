@@ -0,0 +1,42 @@
+//! error for the degenerate input of the geometric queries.
+//! The `try_*` functions (e.g., [`crate::edge3::try_nearest_to_edge3`]) return this error
+//! instead of panicking or returning NaN, so that the dirty inputs can be skipped in the batch processing.
+
+/// reason why the input is degenerate for the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DegenerateError {
+    /// the edge has zero length
+    ZeroLengthEdge,
+    /// the triangle has zero area
+    ZeroAreaTriangle,
+    /// the line is parallel to the plane
+    ParallelToPlane,
+    /// the input has NaN or infinity
+    NonFinite,
+}
+
+impl std::fmt::Display for DegenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            DegenerateError::ZeroLengthEdge => "the edge has zero length",
+            DegenerateError::ZeroAreaTriangle => "the triangle has zero area",
+            DegenerateError::ParallelToPlane => "the line is parallel to the plane",
+            DegenerateError::NonFinite => "the input has NaN or infinity",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for DegenerateError {}
+
+/// `Err(NonFinite)` if any of the points has NaN or infinity
+pub(crate) fn check_finite<T, const N: usize>(points: &[&[T; N]]) -> Result<(), DegenerateError>
+where
+    T: num_traits::Float,
+{
+    if points.iter().all(|p| p.iter().all(|v| v.is_finite())) {
+        Ok(())
+    } else {
+        Err(DegenerateError::NonFinite)
+    }
+}
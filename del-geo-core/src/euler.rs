@@ -0,0 +1,92 @@
+//! Euler angles as a value with the explicit convention of the rotation order
+
+pub use crate::mat3_col_major::{EulerAxes, EulerOrder};
+
+/// Euler angles `angles = [a, b, c]` (radian) in the convention `order`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EulerAngles<Real> {
+    pub order: EulerOrder,
+    pub angles: [Real; 3],
+}
+
+impl<Real> EulerAngles<Real>
+where
+    Real: num_traits::Float + num_traits::FloatConst + std::fmt::Debug,
+{
+    pub fn new(order: EulerOrder, angles: [Real; 3]) -> Self {
+        Self { order, angles }
+    }
+
+    /// Euler angles of the unit quaternion `[i,j,k,w]`.
+    /// See [`crate::quaternion::to_euler`] for the ranges of the angles.
+    pub fn from_quaternion(q: &[Real; 4], order: EulerOrder) -> Self {
+        Self {
+            order,
+            angles: crate::quaternion::to_euler(q, order),
+        }
+    }
+
+    /// Euler angles of the rotation matrix (column major)
+    pub fn from_mat3_col_major(m: &[Real; 9], order: EulerOrder) -> Self {
+        Self {
+            order,
+            angles: crate::mat3_col_major::to_euler(m, order),
+        }
+    }
+
+    pub fn to_quaternion(&self) -> [Real; 4] {
+        let [a, b, c] = self.angles;
+        crate::quaternion::from_euler(self.order, a, b, c)
+    }
+
+    pub fn to_mat3_col_major(&self) -> [Real; 9] {
+        let [a, b, c] = self.angles;
+        crate::mat3_col_major::from_euler(self.order, a, b, c)
+    }
+
+    /// the same rotation in another convention
+    pub fn to_order(&self, order: EulerOrder) -> Self {
+        Self::from_quaternion(&self.to_quaternion(), order)
+    }
+}
+
+#[test]
+fn test_euler_angles() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let axes = [
+        EulerAxes::XYZ,
+        EulerAxes::XZY,
+        EulerAxes::YXZ,
+        EulerAxes::YZX,
+        EulerAxes::ZXY,
+        EulerAxes::ZYX,
+        EulerAxes::XYX,
+        EulerAxes::XZX,
+        EulerAxes::YXY,
+        EulerAxes::YZY,
+        EulerAxes::ZXZ,
+        EulerAxes::ZYZ,
+    ];
+    let orders: Vec<_> = axes
+        .iter()
+        .flat_map(|&a| [EulerOrder::Extrinsic(a), EulerOrder::Intrinsic(a)])
+        .collect();
+    for _itr in 0..10 {
+        let q: [f64; 4] = crate::quaternion::sample_uniform(&mut rng);
+        let m = crate::quaternion::to_mat3_col_major(&q);
+        for &order in orders.iter() {
+            let e0 = EulerAngles::from_quaternion(&q, order);
+            let e1 = EulerAngles::from_mat3_col_major(&m, order);
+            assert!((0..3).all(|i| (e0.angles[i] - e1.angles[i]).abs() < 1.0e-8));
+            let m0 = e0.to_mat3_col_major();
+            assert!((0..9).all(|i| (m0[i] - m[i]).abs() < 1.0e-10));
+            let m1 = crate::quaternion::to_mat3_col_major(&e0.to_quaternion());
+            assert!((0..9).all(|i| (m1[i] - m[i]).abs() < 1.0e-10));
+            let i_order = rng.random_range(0..orders.len());
+            let m2 = e0.to_order(orders[i_order]).to_mat3_col_major();
+            assert!((0..9).all(|i| (m2[i] - m[i]).abs() < 1.0e-10));
+        }
+    }
+}
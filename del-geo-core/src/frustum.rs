@@ -0,0 +1,116 @@
+//! View frustum represented by six planes for the culling
+//! Each plane is stored as `[a, b, c, d]` where `a*x + b*y + c*z + d >= 0` is the inside.
+
+use crate::mat4_col_major::DepthRange;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Frustum<Real> {
+    /// left, right, bottom, top, near and far planes with unit normals pointing inside
+    pub planes: [[Real; 4]; 6],
+}
+
+// SAFETY: transparent wrapper of an array of `Pod`
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Zeroable> bytemuck::Zeroable for Frustum<Real> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Pod> bytemuck::Pod for Frustum<Real> {}
+
+impl<Real> Frustum<Real>
+where
+    Real: num_traits::Float,
+{
+    /// extract the planes from the view-projection matrix (column major)
+    /// with the method of Gribb & Hartmann (2001),
+    /// "Fast Extraction of Viewing Frustum Planes from the World-View-Projection Matrix"
+    /// * `depth_range` - depth range of the normalized device coordinate of the projection
+    pub fn from_view_projection(m: &[Real; 16], depth_range: DepthRange) -> Self {
+        let row = |i: usize| [m[i], m[4 + i], m[8 + i], m[12 + i]];
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let add = |a: &[Real; 4], b: &[Real; 4]| std::array::from_fn(|i| a[i] + b[i]);
+        let sub = |a: &[Real; 4], b: &[Real; 4]| std::array::from_fn(|i| a[i] - b[i]);
+        let near = match depth_range {
+            DepthRange::NegOneToOne => add(&r3, &r2),
+            DepthRange::ZeroToOne => r2,
+        };
+        let planes = [
+            add(&r3, &r0),
+            sub(&r3, &r0),
+            add(&r3, &r1),
+            sub(&r3, &r1),
+            near,
+            sub(&r3, &r2),
+        ];
+        let planes = planes.map(|p: [Real; 4]| {
+            let len = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            if len.is_zero() { p } else { p.map(|v| v / len) }
+        });
+        Self { planes }
+    }
+
+    fn signed_distance(plane: &[Real; 4], p: &[Real; 3]) -> Real {
+        plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3]
+    }
+
+    /// the point is inside the frustum (including the boundary)
+    pub fn contains_point(&self, p: &[Real; 3]) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| Self::signed_distance(plane, p) >= Real::zero())
+    }
+
+    /// the sphere is not completely outside of any of the planes.
+    /// This test is conservative: it may return `true` for some spheres near the corners.
+    pub fn intersects_sphere(&self, center: &[Real; 3], radius: Real) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| Self::signed_distance(plane, center) >= -radius)
+    }
+
+    /// the AABB `[x_min, y_min, z_min, x_max, y_max, z_max]` is not completely outside of
+    /// any of the planes. This test is conservative: it may return `true` for some boxes
+    /// near the corners.
+    pub fn intersects_aabb3(&self, aabb: &[Real; 6]) -> bool {
+        self.planes.iter().all(|plane| {
+            // the corner of the box farthest along the normal of the plane
+            let p = std::array::from_fn(|i| {
+                if plane[i] >= Real::zero() {
+                    aabb[i + 3]
+                } else {
+                    aabb[i]
+                }
+            });
+            Self::signed_distance(plane, &p) >= Real::zero()
+        })
+    }
+}
+
+#[test]
+fn test_frustum() {
+    use crate::mat4_col_major::Handedness;
+    for depth_range in [DepthRange::NegOneToOne, DepthRange::ZeroToOne] {
+        let proj = crate::mat4_col_major::perspective_fov(
+            std::f64::consts::FRAC_PI_2,
+            1.0,
+            1.0,
+            10.0,
+            Handedness::Right,
+            depth_range,
+        );
+        let view = crate::mat4_col_major::look_at_rh(&[0., 0., 5.], &[0., 0., 0.], &[0., 1., 0.]);
+        let frustum = Frustum::from_view_projection(
+            &crate::mat4_col_major::mult_mat_col_major(&proj, &view),
+            depth_range,
+        );
+        assert!(frustum.contains_point(&[0., 0., 0.]));
+        assert!(frustum.contains_point(&[1.9, 1.9, 2.]));
+        assert!(!frustum.contains_point(&[3.1, 0., 2.]));
+        assert!(!frustum.contains_point(&[0., 0., 4.5])); // before near plane
+        assert!(!frustum.contains_point(&[0., 0., -5.5])); // behind far plane
+        assert!(frustum.intersects_sphere(&[3.3, 0., 2.], 0.5));
+        assert!(!frustum.intersects_sphere(&[4.0, 0., 2.], 0.5));
+        assert!(frustum.intersects_aabb3(&[2.9, -0.1, 1.9, 3.5, 0.1, 2.1]));
+        assert!(!frustum.intersects_aabb3(&[3.8, -0.1, 1.9, 4.5, 0.1, 2.1]));
+        assert!(!frustum.intersects_aabb3(&[-1., -1., -7., 1., 1., -6.]));
+    }
+}
@@ -0,0 +1,229 @@
+//! conversions between the arrays of this crate and the `f32` types of the `glam` crate.
+//! * `[f32;2]`, `[f32;3]` <-> `glam::Vec2`, `glam::Vec3`, `glam::Vec3A`
+//! * `[f32;9]`, `[f32;16]` (column major) <-> `glam::Mat3`, `glam::Mat4`
+//! * `[f32;4]` quaternion `[i,j,k,w]` <-> `glam::Quat` (also stored as `[x,y,z,w]`)
+//!
+//! The traits [`AsVec3`] and [`AsMat4ColMajor`] let the functions at the bottom of this module
+//! accept either the arrays or the `glam` types.
+
+use crate::newtype::{M3, M4, V2, V3};
+
+pub fn vec2_to_glam(v: &[f32; 2]) -> glam::Vec2 {
+    glam::Vec2::from_array(*v)
+}
+
+pub fn vec2_from_glam(v: &glam::Vec2) -> [f32; 2] {
+    v.to_array()
+}
+
+pub fn vec3_to_glam(v: &[f32; 3]) -> glam::Vec3 {
+    glam::Vec3::from_array(*v)
+}
+
+pub fn vec3_from_glam(v: &glam::Vec3) -> [f32; 3] {
+    v.to_array()
+}
+
+/// the 16-byte aligned 3D vector of `glam`
+pub fn vec3a_to_glam(v: &[f32; 3]) -> glam::Vec3A {
+    glam::Vec3A::from_array(*v)
+}
+
+pub fn vec3a_from_glam(v: &glam::Vec3A) -> [f32; 3] {
+    v.to_array()
+}
+
+pub fn mat3_col_major_to_glam(m: &[f32; 9]) -> glam::Mat3 {
+    glam::Mat3::from_cols_array(m)
+}
+
+pub fn mat3_col_major_from_glam(m: &glam::Mat3) -> [f32; 9] {
+    m.to_cols_array()
+}
+
+pub fn mat4_col_major_to_glam(m: &[f32; 16]) -> glam::Mat4 {
+    glam::Mat4::from_cols_array(m)
+}
+
+pub fn mat4_col_major_from_glam(m: &glam::Mat4) -> [f32; 16] {
+    m.to_cols_array()
+}
+
+/// the quaternion `[i,j,k,w]`. The real part is the last element in both representations.
+pub fn quaternion_to_glam(q: &[f32; 4]) -> glam::Quat {
+    glam::Quat::from_array(*q)
+}
+
+pub fn quaternion_from_glam(q: &glam::Quat) -> [f32; 4] {
+    q.to_array()
+}
+
+impl From<V2<f32>> for glam::Vec2 {
+    fn from(v: V2<f32>) -> Self {
+        vec2_to_glam(&v.0)
+    }
+}
+
+impl From<glam::Vec2> for V2<f32> {
+    fn from(v: glam::Vec2) -> Self {
+        V2(vec2_from_glam(&v))
+    }
+}
+
+impl From<V3<f32>> for glam::Vec3 {
+    fn from(v: V3<f32>) -> Self {
+        vec3_to_glam(&v.0)
+    }
+}
+
+impl From<glam::Vec3> for V3<f32> {
+    fn from(v: glam::Vec3) -> Self {
+        V3(vec3_from_glam(&v))
+    }
+}
+
+impl From<V3<f32>> for glam::Vec3A {
+    fn from(v: V3<f32>) -> Self {
+        vec3a_to_glam(&v.0)
+    }
+}
+
+impl From<glam::Vec3A> for V3<f32> {
+    fn from(v: glam::Vec3A) -> Self {
+        V3(vec3a_from_glam(&v))
+    }
+}
+
+impl From<M3<f32>> for glam::Mat3 {
+    fn from(m: M3<f32>) -> Self {
+        mat3_col_major_to_glam(&m.0)
+    }
+}
+
+impl From<glam::Mat3> for M3<f32> {
+    fn from(m: glam::Mat3) -> Self {
+        M3(mat3_col_major_from_glam(&m))
+    }
+}
+
+impl From<M4<f32>> for glam::Mat4 {
+    fn from(m: M4<f32>) -> Self {
+        mat4_col_major_to_glam(&m.0)
+    }
+}
+
+impl From<glam::Mat4> for M4<f32> {
+    fn from(m: glam::Mat4) -> Self {
+        M4(mat4_col_major_from_glam(&m))
+    }
+}
+
+// ---------------------------------
+// below: functions accepting either representation
+
+/// 3D vector either as the array or the `glam` type
+pub trait AsVec3 {
+    fn to_vec3(&self) -> [f32; 3];
+}
+
+impl AsVec3 for [f32; 3] {
+    fn to_vec3(&self) -> [f32; 3] {
+        *self
+    }
+}
+
+impl AsVec3 for glam::Vec3 {
+    fn to_vec3(&self) -> [f32; 3] {
+        vec3_from_glam(self)
+    }
+}
+
+impl AsVec3 for glam::Vec3A {
+    fn to_vec3(&self) -> [f32; 3] {
+        vec3a_from_glam(self)
+    }
+}
+
+/// 4x4 matrix either as the column-major array or the `glam` type
+pub trait AsMat4ColMajor {
+    fn to_mat4_col_major(&self) -> [f32; 16];
+}
+
+impl AsMat4ColMajor for [f32; 16] {
+    fn to_mat4_col_major(&self) -> [f32; 16] {
+        *self
+    }
+}
+
+impl AsMat4ColMajor for glam::Mat4 {
+    fn to_mat4_col_major(&self) -> [f32; 16] {
+        mat4_col_major_from_glam(self)
+    }
+}
+
+/// see [`crate::mat4_col_major::transform_homogeneous`]
+pub fn transform_homogeneous<M, V>(m: &M, p: &V) -> Option<[f32; 3]>
+where
+    M: AsMat4ColMajor,
+    V: AsVec3,
+{
+    crate::mat4_col_major::transform_homogeneous(&m.to_mat4_col_major(), &p.to_vec3())
+}
+
+/// see [`crate::aabb::intersections_against_ray`]
+pub fn aabb3_intersections_against_ray<V0, V1>(
+    aabb: &[f32; 6],
+    ray_org: &V0,
+    ray_dir: &V1,
+) -> Option<(f32, f32)>
+where
+    V0: AsVec3,
+    V1: AsVec3,
+{
+    crate::aabb::intersections_against_ray(aabb, &ray_org.to_vec3(), &ray_dir.to_vec3())
+}
+
+#[test]
+fn test_glam() {
+    let v = [1f32, 2., 3.];
+    assert_eq!(vec3_to_glam(&v), glam::Vec3::new(1., 2., 3.));
+    assert_eq!(vec3_from_glam(&vec3_to_glam(&v)), v);
+    assert_eq!(vec3a_from_glam(&vec3a_to_glam(&v)), v);
+    assert_eq!(vec2_from_glam(&vec2_to_glam(&[4., 5.])), [4., 5.]);
+    assert_eq!(V3::from(glam::Vec3A::from(V3(v))), V3(v));
+    assert_eq!(V2::from(glam::Vec2::from(V2([4., 5.]))), V2([4., 5.]));
+    // matrices are column major in both representations
+    let m: [f32; 9] = std::array::from_fn(|i| i as f32 * 0.5 - 1.);
+    let gm = mat3_col_major_to_glam(&m);
+    let p0 = crate::mat3_col_major::mult_vec(&m, &v);
+    assert_eq!(vec3_from_glam(&(gm * vec3_to_glam(&v))), p0);
+    assert_eq!(M3::from(glam::Mat3::from(M3(m))), M3(m));
+    let m = crate::mat4_col_major::from_translate(&[1f32, 2., 3.]);
+    let gm = mat4_col_major_to_glam(&m);
+    assert_eq!(
+        gm.transform_point3(glam::Vec3::ZERO),
+        glam::Vec3::new(1., 2., 3.)
+    );
+    assert_eq!(M4::from(glam::Mat4::from(M4(m))), M4(m));
+    // the real part of the quaternion is the last element in both representations
+    let q = crate::quaternion::from_axisangle(&[0.3f32, -0.2, 0.1]);
+    let gq = quaternion_to_glam(&q);
+    let p0 = crate::quaternion::to_mat3_col_major(&q);
+    let p1 = glam::Mat3::from_quat(gq).to_cols_array();
+    assert!((0..9).all(|i| (p0[i] - p1[i]).abs() < 1.0e-6));
+    assert_eq!(quaternion_from_glam(&gq), q);
+    // either representation is accepted
+    assert_eq!(
+        transform_homogeneous(&m, &v),
+        transform_homogeneous(&gm, &glam::Vec3A::from_array(v))
+    );
+    let aabb = [-1., -1., -1., 1., 1., 1.];
+    let org = [-3f32, 0.1, 0.2];
+    let dir = [1f32, 0., 0.];
+    let t = aabb3_intersections_against_ray(&aabb, &org, &dir);
+    assert_eq!(t, Some((2., 4.)));
+    assert_eq!(
+        aabb3_intersections_against_ray(&aabb, &vec3_to_glam(&org), &vec3a_to_glam(&dir)),
+        t
+    );
+}
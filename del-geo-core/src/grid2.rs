@@ -0,0 +1,117 @@
+//! methods for 2D uniform grid.
+//! The grid is infinite with the origin at zero, and the cell `[i, j]` covers
+//! `[i, j] * cell_size` to `[i+1, j+1] * cell_size`. The cell index can be negative.
+
+/// cells visited by the segment `p0`-`p1` in the order from `p0` to `p1`.
+/// The consecutive cells share an edge. When the segment passes exactly through the corner of the cells,
+/// only one of the two cells next to the corner is visited (see [`cells_along_edge_conservative`]).
+/// Returns the empty vector if the coordinates are NaN, infinite or too large for the `i64` cell index.
+/// See [`crate::grid3::CellsAlongRay`]
+pub fn cells_along_edge<Real>(p0: &[Real; 2], p1: &[Real; 2], cell_size: Real) -> Vec<[i64; 2]>
+where
+    Real: num_traits::Float,
+{
+    if crate::error::check_finite(&[p0, p1]).is_err() {
+        return vec![];
+    }
+    // finite grid covering the segment
+    let cell_index = |v: Real| (v / cell_size).floor().to_i64();
+    let mut cell0 = [0i64; 2];
+    let mut grid_dims = [0usize; 2];
+    for i in 0..2 {
+        let (Some(c0), Some(c1)) = (cell_index(p0[i].min(p1[i])), cell_index(p0[i].max(p1[i])))
+        else {
+            return vec![];
+        };
+        let Some(dim) = c1.checked_sub(c0).and_then(|d| usize::try_from(d).ok()) else {
+            return vec![];
+        };
+        cell0[i] = c0;
+        grid_dims[i] = dim + 1;
+    }
+    let grid_origin = cell0.map(|v| Real::from(v).unwrap() * cell_size);
+    let dir = [p1[0] - p0[0], p1[1] - p0[1]];
+    let ray = crate::ray::Ray2::new(p0, &dir).with_range(Real::zero(), Real::one());
+    crate::grid3::CellsAlongRay::new(&ray, &grid_origin, cell_size, &grid_dims)
+        .map(|(c, _, _)| [cell0[0] + c[0] as i64, cell0[1] + c[1] as i64])
+        .collect()
+}
+
+/// all the cells whose closed region touches the segment `p0`-`p1` (conservative rasterization).
+/// In addition to [`cells_along_edge`], this includes the cells sharing only the corner or the boundary
+/// with the segment. The cells are roughly in the order from `p0` to `p1`.
+pub fn cells_along_edge_conservative<Real>(
+    p0: &[Real; 2],
+    p1: &[Real; 2],
+    cell_size: Real,
+) -> Vec<[i64; 2]>
+where
+    Real: num_traits::Float,
+{
+    let mut res = vec![];
+    let mut visited = std::collections::HashSet::new();
+    for c in cells_along_edge(p0, p1, cell_size) {
+        for (i, j) in itertools::iproduct!(-1..=1, -1..=1) {
+            let c = [c[0] + i, c[1] + j];
+            if visited.contains(&c) {
+                continue;
+            }
+            let aabb = [c[0], c[1], c[0] + 1, c[1] + 1].map(|v| Real::from(v).unwrap() * cell_size);
+            if crate::aabb::clip_edge::<Real, 2, 4>(&aabb, p0, p1).is_some() {
+                visited.insert(c);
+                res.push(c);
+            }
+        }
+    }
+    res
+}
+
+#[test]
+fn test_cells_along_edge() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let cell_size = 0.3;
+    let cell_of = |p: &[f64; 2]| p.map(|v| (v / cell_size).floor() as i64);
+    for _iter in 0..100 {
+        let p0: [f64; 2] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let p1: [f64; 2] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        let cells = cells_along_edge(&p0, &p1, cell_size);
+        assert_eq!(cells.first(), Some(&cell_of(&p0)));
+        assert_eq!(cells.last(), Some(&cell_of(&p1)));
+        for w in cells.windows(2) {
+            assert_eq!((w[0][0] - w[1][0]).abs() + (w[0][1] - w[1][1]).abs(), 1);
+        }
+        // every sampled cell is included
+        for i in 0..=1000 {
+            let p = crate::edge::position_from_ratio(&p0, &p1, i as f64 * 1.0e-3);
+            assert!(cells.contains(&cell_of(&p)));
+        }
+        // the conservative cells include the cells above
+        let cells1 = cells_along_edge_conservative(&p0, &p1, cell_size);
+        assert!(cells.iter().all(|c| cells1.contains(c)));
+    }
+    // passing through the corners of the cells
+    let cells = cells_along_edge(&[0.5, 0.5], &[2.5, 2.5], 1.0);
+    assert_eq!(cells.len(), 5);
+    let mut cells = cells_along_edge_conservative(&[0.5, 0.5], &[2.5, 2.5], 1.0);
+    cells.sort();
+    assert_eq!(
+        cells,
+        vec![[0, 0], [0, 1], [1, 0], [1, 1], [1, 2], [2, 1], [2, 2]]
+    );
+    // on the boundary of the cells
+    let mut cells = cells_along_edge_conservative(&[-0.5, 0.], &[0.5, 0.], 1.0);
+    cells.sort();
+    assert_eq!(cells, vec![[-1, -1], [-1, 0], [0, -1], [0, 0]]);
+    // non-finite or too far coordinates
+    assert!(cells_along_edge(&[f64::NAN, 0.], &[1., 1.], 1.0).is_empty());
+    assert!(cells_along_edge(&[0., 0.], &[f64::INFINITY, 1.], 1.0).is_empty());
+    assert!(cells_along_edge(&[-1.0e30, 0.], &[1.0e30, 1.], 1.0).is_empty());
+    assert!(cells_along_edge_conservative(&[f64::NAN, 0.], &[1., 1.], 1.0).is_empty());
+    // degenerated segment
+    assert_eq!(
+        cells_along_edge(&[-0.5, 0.5], &[-0.5, 0.5], 1.0),
+        vec![[-1, 0]]
+    );
+}
@@ -0,0 +1,205 @@
+//! methods for 3D uniform grid.
+//! The grid is represented as `(grid_origin: &[Real;3], cell_size: Real, grid_dims: &[usize;3])`
+//! where the cell `[i, j, k]` covers `grid_origin + [i, j, k] * cell_size` to `grid_origin + [i+1, j+1, k+1] * cell_size`.
+
+/// iterator of the cells along the ray by the method of Amanatides & Woo (1987),
+/// "A Fast Voxel Traversal Algorithm for Ray Tracing".
+/// It yields `(cell_index, t_enter, t_exit)` in the order along the ray.
+/// This works for any dimension (see [`crate::grid2`] for 2D).
+#[derive(Debug, Clone)]
+pub struct CellsAlongRay<Real, const NDIM: usize> {
+    grid_dims: [usize; NDIM],
+    cell: [usize; NDIM],
+    /// `+1`, `-1` or `0` for each axis
+    step: [i8; NDIM],
+    /// ray parameter at the next cell boundary for each axis
+    t_next: [Real; NDIM],
+    /// increment of the ray parameter to cross one cell for each axis
+    t_delta: [Real; NDIM],
+    t_cur: Real,
+    t_end: Real,
+    is_end: bool,
+}
+
+impl<Real, const NDIM: usize> CellsAlongRay<Real, NDIM>
+where
+    Real: num_traits::Float,
+{
+    pub fn new(
+        ray: &crate::ray::Ray<Real, NDIM>,
+        grid_origin: &[Real; NDIM],
+        cell_size: Real,
+        grid_dims: &[usize; NDIM],
+    ) -> Self {
+        let zero = Real::zero();
+        let mut res = Self {
+            grid_dims: *grid_dims,
+            cell: [0; NDIM],
+            step: [0; NDIM],
+            t_next: [Real::infinity(); NDIM],
+            t_delta: [Real::infinity(); NDIM],
+            t_cur: zero,
+            t_end: zero,
+            is_end: true,
+        };
+        if grid_dims.contains(&0) {
+            return res;
+        }
+        // clip the ray by the box of the grid (slab method)
+        let (mut t0, mut t1) = (ray.t_min, ray.t_max);
+        for i in 0..NDIM {
+            let lo = grid_origin[i];
+            let hi = grid_origin[i] + Real::from(grid_dims[i]).unwrap() * cell_size;
+            if ray.dir[i] != zero {
+                let (s0, s1) = (
+                    (lo - ray.org[i]) / ray.dir[i],
+                    (hi - ray.org[i]) / ray.dir[i],
+                );
+                t0 = t0.max(s0.min(s1));
+                t1 = t1.min(s0.max(s1));
+            } else if ray.org[i] < lo || ray.org[i] > hi {
+                return res;
+            }
+        }
+        if t0 > t1 {
+            return res;
+        }
+        let p = ray.at(t0);
+        for i in 0..NDIM {
+            let x = ((p[i] - grid_origin[i]) / cell_size).floor();
+            let x = x.max(zero).min(Real::from(grid_dims[i] - 1).unwrap());
+            res.cell[i] = x.to_usize().unwrap();
+            let lo = grid_origin[i] + Real::from(res.cell[i]).unwrap() * cell_size;
+            if ray.dir[i] > zero {
+                res.step[i] = 1;
+                res.t_next[i] = (lo + cell_size - ray.org[i]) / ray.dir[i];
+                res.t_delta[i] = cell_size / ray.dir[i];
+            } else if ray.dir[i] < zero {
+                res.step[i] = -1;
+                res.t_next[i] = (lo - ray.org[i]) / ray.dir[i];
+                res.t_delta[i] = -cell_size / ray.dir[i];
+            }
+        }
+        res.t_cur = t0;
+        res.t_end = t1;
+        res.is_end = false;
+        res
+    }
+}
+
+impl<Real, const NDIM: usize> Iterator for CellsAlongRay<Real, NDIM>
+where
+    Real: num_traits::Float,
+{
+    type Item = ([usize; NDIM], Real, Real);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_end {
+            return None;
+        }
+        // axis of the nearest cell boundary
+        let i_dim = (0..NDIM).fold(0, |a, i| {
+            if self.t_next[i] < self.t_next[a] {
+                i
+            } else {
+                a
+            }
+        });
+        let t_exit = self.t_next[i_dim].min(self.t_end);
+        let item = (self.cell, self.t_cur, t_exit);
+        if self.t_next[i_dim] >= self.t_end {
+            self.is_end = true;
+            return Some(item);
+        }
+        match self.step[i_dim] {
+            1 if self.cell[i_dim] + 1 < self.grid_dims[i_dim] => self.cell[i_dim] += 1,
+            -1 if self.cell[i_dim] > 0 => self.cell[i_dim] -= 1,
+            _ => self.is_end = true,
+        }
+        self.t_cur = t_exit;
+        self.t_next[i_dim] = self.t_next[i_dim] + self.t_delta[i_dim];
+        Some(item)
+    }
+}
+
+/// voxels of the grid along the ray in the range of the ray parameter.
+/// See [`CellsAlongRay`]
+pub fn voxels_along_ray<Real>(
+    ray: &crate::ray::Ray3<Real>,
+    grid_origin: &[Real; 3],
+    cell_size: Real,
+    grid_dims: &[usize; 3],
+) -> CellsAlongRay<Real, 3>
+where
+    Real: num_traits::Float,
+{
+    CellsAlongRay::new(ray, grid_origin, cell_size, grid_dims)
+}
+
+#[test]
+fn test_voxels_along_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let (grid_origin, cell_size, grid_dims) = ([-1.0f64, -0.5, -1.5], 0.25, [8, 4, 12]);
+    let mut num_hit = 0;
+    for _iter in 0..300 {
+        let org: [f64; 3] = std::array::from_fn(|_| 6. * rng.random::<f64>() - 3.);
+        // toward the point around the grid
+        let mut dir: [f64; 3] = std::array::from_fn(|i| 3. * rng.random::<f64>() - 1.5 - org[i]);
+        if rng.random::<f64>() < 0.2 {
+            dir[rng.random_range(0..3)] = 0.0;
+        }
+        let ray = crate::ray::Ray3::new(&org, &dir);
+        let voxels: Vec<_> = voxels_along_ray(&ray, &grid_origin, cell_size, &grid_dims).collect();
+        // brute force with the sampling along the ray
+        let mut voxels0: Vec<[usize; 3]> = vec![];
+        for i in 0..100000 {
+            let t = i as f64 * 1.0e-4;
+            let p = ray.at(t);
+            let idx: [f64; 3] =
+                std::array::from_fn(|i| ((p[i] - grid_origin[i]) / cell_size).floor());
+            if (0..3).any(|i| idx[i] < 0. || idx[i] >= grid_dims[i] as f64) {
+                continue;
+            }
+            let idx = idx.map(|v| v as usize);
+            if voxels0.last() != Some(&idx) {
+                voxels0.push(idx);
+            }
+        }
+        if voxels.is_empty() {
+            assert!(voxels0.is_empty());
+            continue;
+        }
+        num_hit += 1;
+        // every sampled voxel is visited in the same order
+        let mut j = 0;
+        for v in voxels0.iter() {
+            while j < voxels.len() && voxels[j].0 != *v {
+                j += 1;
+            }
+            assert!(j < voxels.len(), "{voxels:?} {voxels0:?}");
+        }
+        // the consecutive voxels share a face and the ray parameters are continuous
+        for w in voxels.windows(2) {
+            let diff: usize = (0..3).map(|i| w[0].0[i].abs_diff(w[1].0[i])).sum();
+            assert_eq!(diff, 1);
+            assert_eq!(w[0].2, w[1].1);
+        }
+        for (v, t0, t1) in voxels.iter() {
+            assert!(t0 <= t1);
+            let p = ray.at((t0 + t1) * 0.5);
+            let aabb: [f64; 6] =
+                std::array::from_fn(|i| grid_origin[i % 3] + (v[i % 3] + i / 3) as f64 * cell_size);
+            assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &p) < 1.0e-20);
+        }
+    }
+    assert!(num_hit > 30);
+    // along the axis
+    let ray = crate::ray::Ray3::new(&[0.1, 0.1, 0.1], &[-1., 0., 0.]);
+    let voxels: Vec<_> = voxels_along_ray(&ray, &[0., 0., 0.], 1., &[3, 2, 2]).collect();
+    assert_eq!(voxels, vec![([0, 0, 0], 0., 0.1)]);
+    let ray = crate::ray::Ray3::new(&[-1., 0.5, 0.5], &[1., 0., 0.]).with_range(0., 2.5);
+    let voxels: Vec<_> = voxels_along_ray(&ray, &[0., 0., 0.], 1., &[3, 2, 2]).collect();
+    assert_eq!(voxels, vec![([0, 0, 0], 1., 2.), ([1, 0, 0], 2., 2.5)]);
+}
@@ -0,0 +1,245 @@
+//! methods for the 3D rigid transformation (isometry) stored as `[T;7]`.
+//! The first four elements are the unit quaternion `[i,j,k,w]` for the rotation
+//! and the last three elements are the translation.
+//! The transformation maps the point `p` to `R * p + t`.
+
+/// trait for 3D rigid transformation
+pub trait Isometry3<Real>
+where
+    Self: Sized,
+{
+    fn to_quaternion(&self) -> [Real; 4];
+    fn to_translation(&self) -> [Real; 3];
+    fn compose(&self, other: &Self) -> Self;
+    fn inverse(&self) -> Self;
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3];
+    fn transform_vector(&self, v: &[Real; 3]) -> [Real; 3];
+    fn to_mat4_col_major(&self) -> [Real; 16];
+}
+
+impl<Real> Isometry3<Real> for [Real; 7]
+where
+    Real: num_traits::Float,
+{
+    fn to_quaternion(&self) -> [Real; 4] {
+        to_quaternion(self)
+    }
+    fn to_translation(&self) -> [Real; 3] {
+        to_translation(self)
+    }
+    fn compose(&self, other: &Self) -> Self {
+        compose(self, other)
+    }
+    fn inverse(&self) -> Self {
+        inverse(self)
+    }
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3] {
+        transform_point(self, p)
+    }
+    fn transform_vector(&self, v: &[Real; 3]) -> [Real; 3] {
+        transform_vector(self, v)
+    }
+    fn to_mat4_col_major(&self) -> [Real; 16] {
+        to_mat4_col_major(self)
+    }
+}
+
+pub fn from_identity<Real>() -> [Real; 7]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [zero, zero, zero, Real::one(), zero, zero, zero]
+}
+
+pub fn from_quaternion_and_translation<Real>(q: &[Real; 4], t: &[Real; 3]) -> [Real; 7]
+where
+    Real: Copy,
+{
+    [q[0], q[1], q[2], q[3], t[0], t[1], t[2]]
+}
+
+/// the matrix needs to be rigid (i.e., the upper-left 3x3 block is a rotation)
+pub fn from_mat4_col_major<Real>(m: &[Real; 16]) -> [Real; 7]
+where
+    Real: num_traits::Float + std::fmt::Debug,
+{
+    let r = crate::mat4_col_major::to_mat3_col_major_xyz(m);
+    let q = crate::mat3_col_major::to_quaternion(&r);
+    let t = crate::mat4_col_major::to_vec3_translation(m);
+    from_quaternion_and_translation(&q, &t)
+}
+
+// above: from methods
+// ---------------------------------
+
+pub fn to_quaternion<Real>(a: &[Real; 7]) -> [Real; 4]
+where
+    Real: Copy,
+{
+    [a[0], a[1], a[2], a[3]]
+}
+
+pub fn to_translation<Real>(a: &[Real; 7]) -> [Real; 3]
+where
+    Real: Copy,
+{
+    [a[4], a[5], a[6]]
+}
+
+pub fn to_mat4_col_major<Real>(a: &[Real; 7]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let mut m = crate::quaternion::to_mat4_col_major(&to_quaternion(a));
+    m[12] = a[4];
+    m[13] = a[5];
+    m[14] = a[6];
+    m
+}
+
+// above: to methods
+// ---------------------------------
+
+/// the transformation `a * b` that applies `b` first then `a`
+pub fn compose<Real>(a: &[Real; 7], b: &[Real; 7]) -> [Real; 7]
+where
+    Real: num_traits::Float,
+{
+    let qa = to_quaternion(a);
+    let q = crate::quaternion::mult_quaternion(&qa, &to_quaternion(b));
+    let t = transform_point(a, &to_translation(b));
+    from_quaternion_and_translation(&crate::quaternion::normalized(&q), &t)
+}
+
+pub fn inverse<Real>(a: &[Real; 7]) -> [Real; 7]
+where
+    Real: num_traits::Float,
+{
+    let qi = crate::quaternion::inverse(to_quaternion(a));
+    let t = crate::quaternion::transform_vec(&qi, &to_translation(a));
+    from_quaternion_and_translation(&qi, &[-t[0], -t[1], -t[2]])
+}
+
+pub fn transform_point<Real>(a: &[Real; 7], p: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let v = crate::quaternion::transform_vec(&to_quaternion(a), p);
+    [v[0] + a[4], v[1] + a[5], v[2] + a[6]]
+}
+
+/// rotate the vector without the translation
+pub fn transform_vector<Real>(a: &[Real; 7], v: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    crate::quaternion::transform_vec(&to_quaternion(a), v)
+}
+
+/// interpolation with the linear interpolation of the translation and
+/// the spherical linear interpolation of the rotation
+pub fn lerp_slerp<Real>(a: &[Real; 7], b: &[Real; 7], t: Real) -> [Real; 7]
+where
+    Real: num_traits::Float,
+{
+    let q = crate::quaternion::slerp(&to_quaternion(a), &to_quaternion(b), t);
+    let tr: [Real; 3] = std::array::from_fn(|i| a[4 + i] + (b[4 + i] - a[4 + i]) * t);
+    from_quaternion_and_translation(&q, &tr)
+}
+
+/// screw linear interpolation (ScLERP) that moves from `a` to `b` with the constant
+/// linear and angular velocities along the screw axis, i.e., `a * exp(t * log(a^{-1} * b))`.
+/// The rotation takes the shorter arc. See [`lerp_slerp`] for the interpolation
+/// that does not couple the rotation and the translation.
+pub fn sclerp<Real>(a: &[Real; 7], b: &[Real; 7], t: Real) -> [Real; 7]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    let twist = crate::se3::log(&compose(&inverse(a), b));
+    let (q, tr) = crate::se3::exp(&twist.map(|v| v * t));
+    compose(a, &from_quaternion_and_translation(&q, &tr))
+}
+
+#[test]
+fn test_sclerp() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_isometry = || {
+        let aa: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let t: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        from_quaternion_and_translation(&crate::quaternion::from_axisangle(&aa), &t)
+    };
+    let dist = |a: &[f64; 7], b: &[f64; 7]| {
+        let (ma, mb) = (to_mat4_col_major(a), to_mat4_col_major(b));
+        (0..16).fold(0f64, |acc, i| acc.max((ma[i] - mb[i]).abs()))
+    };
+    for i_iter in 0..100 {
+        let a = random_isometry();
+        let b = random_isometry();
+        assert!(dist(&sclerp(&a, &b, 0.0), &a) < 1.0e-10);
+        assert!(dist(&sclerp(&a, &b, 1.0), &b) < 1.0e-10);
+        // constant velocity: the relative motion of each quarter step is the same
+        let c: Vec<_> = (0..5).map(|i| sclerp(&a, &b, i as f64 * 0.25)).collect();
+        let d0 = compose(&inverse(&c[0]), &c[1]);
+        for i in 1..4 {
+            let d1 = compose(&inverse(&c[i]), &c[i + 1]);
+            assert!(dist(&d0, &d1) < 1.0e-10);
+        }
+        // rotation is the same as that of the slerp
+        let t = (i_iter as f64 + 0.5) / 100.;
+        let q0 = to_quaternion(&sclerp(&a, &b, t));
+        let q1 = to_quaternion(&lerp_slerp(&a, &b, t));
+        let d = (0..4).fold(0f64, |acc, i| acc.max((q0[i] - q1[i]).abs()));
+        assert!(d < 1.0e-10);
+    }
+}
+
+#[test]
+fn test_isometry3() {
+    use crate::mat4_col_major::Mat4ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_isometry = || {
+        let aa: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let t: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        from_quaternion_and_translation(&crate::quaternion::from_axisangle(&aa), &t)
+    };
+    for _itr in 0..100 {
+        let a = random_isometry();
+        let b = random_isometry();
+        let p = [0.3, -0.5, 0.8];
+        let ma = a.to_mat4_col_major();
+        let mb = b.to_mat4_col_major();
+        {
+            let p0 = a.transform_point(&p);
+            let p1 = ma.transform_homogeneous(&p).unwrap();
+            assert!(crate::vec3::distance(&p0, &p1) < 1.0e-10);
+            let v0 = a.transform_vector(&p);
+            let v1 = ma.transform_direction(&p);
+            assert!(crate::vec3::distance(&v0, &v1) < 1.0e-10);
+        }
+        {
+            let mab0 = a.compose(&b).to_mat4_col_major();
+            let mab1 = ma.mult_mat(&mb);
+            assert!((0..16).all(|i| (mab0[i] - mab1[i]).abs() < 1.0e-10));
+        }
+        {
+            let ai = a.inverse();
+            let p0 = ai.transform_point(&a.transform_point(&p));
+            assert!(crate::vec3::distance(&p0, &p) < 1.0e-10);
+        }
+        {
+            let m0 = from_mat4_col_major(&ma).to_mat4_col_major();
+            assert!((0..16).all(|i| (m0[i] - ma[i]).abs() < 1.0e-10));
+        }
+        {
+            let c0 = lerp_slerp(&a, &b, 0.0).to_mat4_col_major();
+            let c1 = lerp_slerp(&a, &b, 1.0).to_mat4_col_major();
+            assert!((0..16).all(|i| (c0[i] - ma[i]).abs() < 1.0e-10));
+            assert!((0..16).all(|i| (c1[i] - mb[i]).abs() < 1.0e-10));
+        }
+    }
+}
@@ -8,6 +8,7 @@ sphere < tet
 
 pub mod aabb;
 pub mod aabb2;
+pub mod aabb2_i;
 pub mod aabb3;
 pub mod range;
 
@@ -21,15 +22,34 @@ pub mod obb3;
 pub mod vec2;
 pub mod vec3;
 //
+pub mod batch;
 pub mod bezier_cubic;
 pub mod bezier_quadratic;
 pub mod ccd2;
 pub mod ccd3;
+pub mod circle2;
+pub mod cone3;
+pub mod convex3;
+pub mod cylinder3;
+pub mod disk3;
+pub mod dual_quat;
 pub mod edge;
 pub mod edge2;
 pub mod edge3;
+pub mod ellipse2;
+pub mod ellipsoid3;
+pub mod error;
+pub mod euler;
+pub mod frustum;
+#[cfg(feature = "glam")]
+pub mod glam_interop;
+pub mod grid2;
+pub mod grid3;
 pub mod hex;
+pub mod isometry3;
+pub mod line;
 pub mod line2;
+pub mod line3;
 pub mod mat2_col_major;
 pub mod mat2_sym;
 pub mod mat3_row_major;
@@ -37,17 +57,32 @@ pub mod mat3_sym;
 pub mod mat3x4_col_major;
 pub mod matn_col_major;
 pub mod matn_row_major;
+#[cfg(feature = "mint")]
+pub mod mint_interop;
+pub mod morton;
 pub mod ndc;
+pub mod newtype;
 pub mod obb2;
 pub mod plane;
+pub mod polygon2;
+pub mod polygon3;
 pub mod polynomial_root;
 pub mod quaternion;
+pub mod ray;
+pub mod ray3x8;
+pub mod rot2;
+pub mod sampling;
+pub mod se3;
+pub mod similarity3;
+pub mod so3;
 pub mod sphere;
 pub mod spherical_harmonics;
 pub mod tet;
+pub mod torus3;
 pub mod tri2;
 pub mod tri3;
 pub mod uvec3;
+pub mod vec3x8;
 pub mod vec4;
 pub mod vecn;
 pub mod view_projection;
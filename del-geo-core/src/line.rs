@@ -0,0 +1,60 @@
+//! methods for infinite line in any dimension parameterized by the origin and the direction vector
+//! as `org + t * dir` (see [`crate::line2`] and [`crate::line3`] for the dimension specific methods).
+//! The direction `dir` is a general non-zero vector, not necessarily unitary.
+
+/// parameter `t` of the orthogonal projection of `p` onto the line
+pub fn parameter_of_projection<T, const N: usize>(org: &[T; N], dir: &[T; N], p: &[T; N]) -> T
+where
+    T: num_traits::Float,
+{
+    let (mut a, mut b) = (T::zero(), T::zero());
+    for i in 0..N {
+        a = a + dir[i] * dir[i];
+        b = b + dir[i] * (p[i] - org[i]);
+    }
+    b / a
+}
+
+/// the point on the line nearest to `p` (i.e., the orthogonal projection)
+pub fn nearest_point<T, const N: usize>(org: &[T; N], dir: &[T; N], p: &[T; N]) -> [T; N]
+where
+    T: num_traits::Float,
+{
+    let t = parameter_of_projection(org, dir, p);
+    std::array::from_fn(|i| org[i] + dir[i] * t)
+}
+
+/// distance from the point `p` to the line
+pub fn distance<T, const N: usize>(org: &[T; N], dir: &[T; N], p: &[T; N]) -> T
+where
+    T: num_traits::Float,
+{
+    crate::edge::length(&nearest_point(org, dir, p), p)
+}
+
+#[test]
+fn test_line_generic() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let ps: [[f64; 4]; 3] =
+            std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.));
+        let (org, dir, p) = (&ps[0], &ps[1], &ps[2]);
+        let q = nearest_point(org, dir, p);
+        // the difference is orthogonal to the line
+        let d: f64 = (0..4).map(|i| (p[i] - q[i]) * dir[i]).sum();
+        assert!(d.abs() < 1.0e-10);
+        let t = parameter_of_projection(org, dir, p);
+        let dist = distance(org, dir, p);
+        for s in [t - 0.1, t + 0.1, 0., 1.] {
+            let r: [f64; 4] = std::array::from_fn(|i| org[i] + dir[i] * s);
+            assert!(dist <= crate::edge::length(&r, p));
+        }
+    }
+    assert_eq!(
+        parameter_of_projection(&[1., 0., 0.], &[2., 0., 0.], &[4., 1., 0.]),
+        1.5
+    );
+    assert_eq!(distance(&[1., 0.], &[2., 0.], &[4., -3.]), 3.);
+}
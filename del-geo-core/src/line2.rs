@@ -66,6 +66,79 @@ fn test_dw_intersection() {
     }
 }
 
+/// classification of the intersection between two 2D lines. See [`intersection_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Line2Intersection<T> {
+    /// the lines cross at `point = org0 + t0 * dir0 = org1 + t1 * dir1`
+    Point { point: [T; 2], t0: T, t1: T },
+    /// the lines are parallel and do not share any point
+    Parallel,
+    /// the lines are the same
+    Coincident,
+}
+
+/// intersection between the line `org0 + t0 * dir0` and the line `org1 + t1 * dir1`
+/// with the parameters of both lines.
+/// The lines are regarded as parallel if the sine of the angle between them is within a few machine epsilons,
+/// and regarded as coincident if `org1` is on the first line up to the rounding error of the origins.
+/// [`intersection`] returns only the point.
+pub fn intersection_detailed<T>(
+    org0: &[T; 2],
+    dir0: &[T; 2],
+    org1: &[T; 2],
+    dir1: &[T; 2],
+) -> Line2Intersection<T>
+where
+    T: num_traits::Float,
+{
+    use crate::vec2::Vec2;
+    let tol = T::from(4).unwrap() * T::epsilon();
+    let det = dir0[0] * dir1[1] - dir0[1] * dir1[0];
+    let d = org1.sub(org0);
+    if det.abs() <= tol * dir0.norm() * dir1.norm() {
+        // distance between the lines relative to the rounding error of the origins
+        let c = d[0] * dir0[1] - d[1] * dir0[0];
+        if c.abs() <= tol * dir0.norm() * (org0.norm() + org1.norm()) {
+            return Line2Intersection::Coincident;
+        }
+        return Line2Intersection::Parallel;
+    }
+    let t0 = (d[0] * dir1[1] - d[1] * dir1[0]) / det;
+    let t1 = (d[0] * dir0[1] - d[1] * dir0[0]) / det;
+    Line2Intersection::Point {
+        point: org0.add(&dir0.scale(t0)),
+        t0,
+        t1,
+    }
+}
+
+#[test]
+fn test_intersection_detailed() {
+    use crate::vec2::Vec2;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut rand_vec2 = || -> [f64; 2] { std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.) };
+    for _iter in 0..100 {
+        let (org0, dir0, org1, dir1) = (rand_vec2(), rand_vec2(), rand_vec2(), rand_vec2());
+        let Line2Intersection::Point { point, t0, t1 } =
+            intersection_detailed(&org0, &dir0, &org1, &dir1)
+        else {
+            panic!();
+        };
+        assert!(point.sub(&org0.add(&dir0.scale(t0))).norm() < 1.0e-8);
+        assert!(point.sub(&org1.add(&dir1.scale(t1))).norm() < 1.0e-8);
+        let p = intersection(&org0, &dir0, &org1, &dir1);
+        assert!(point.sub(&p).norm() < 1.0e-8);
+        // parallel and coincident
+        let res = intersection_detailed(&org0, &dir0, &org1, &dir0.scale(-2.));
+        assert_eq!(res, Line2Intersection::Parallel);
+        let org2 = org0.add(&dir0.scale(1.5));
+        let res = intersection_detailed(&org0, &dir0, &org2, &dir0.scale(0.5));
+        assert_eq!(res, Line2Intersection::Coincident);
+    }
+}
+
 pub fn dw_intersection_against_bisector<Real>(
     ls: &[Real; 2], // source of line
     ld: &[Real; 2], // direction of line
@@ -117,3 +190,114 @@ fn test_dw_intersection_against_bisector() {
         assert!(v0.sub(&v1).norm() < 1.0e-5);
     }
 }
+
+/// fit the line to the points minimizing the sum of the squared orthogonal distances
+/// * Return `(origin, dir)` where `origin` is the centroid and `dir` is the unit direction
+/// * Return `None` if there is no point
+pub fn fit_total_least_squares<T>(points: &[[T; 2]]) -> Option<([T; 2], [T; 2])>
+where
+    T: num_traits::Float + std::fmt::Debug,
+{
+    use crate::vec2::Vec2;
+    if points.is_empty() {
+        return None;
+    }
+    let n = T::from(points.len()).unwrap();
+    let origin = points
+        .iter()
+        .fold([T::zero(); 2], |a, p| a.add(p))
+        .scale(T::one() / n);
+    // covariance matrix as a symmetric matrix `[xx, xy, yy]`
+    let cov = points.iter().fold([T::zero(); 3], |a, p| {
+        let d = p.sub(&origin);
+        [a[0] + d[0] * d[0], a[1] + d[0] * d[1], a[2] + d[1] * d[1]]
+    });
+    let (u, lambda) = crate::mat2_sym::eigen_decomposition(&cov);
+    // eigen vector of the larger eigen value (not sorted for the diagonal matrix)
+    let i = if lambda[0] > lambda[1] { 0 } else { 1 };
+    Some((origin, [u[i * 2], u[i * 2 + 1]]))
+}
+
+/// robust line fitting with RANSAC followed by [`fit_total_least_squares`] on the inliers
+/// * `threshold` - maximum distance from the line for the inlier
+/// * `num_iteration` - number of the random samples of two points
+/// * Return `(origin, dir, inliers)` where `inliers` is the sorted indices of the inlier points
+/// * Return `None` if there are less than two distinct points
+pub fn fit_ransac<T, Reng>(
+    points: &[[T; 2]],
+    threshold: T,
+    num_iteration: usize,
+    reng: &mut Reng,
+) -> Option<([T; 2], [T; 2], Vec<usize>)>
+where
+    T: num_traits::Float + std::fmt::Debug,
+    Reng: rand::Rng,
+{
+    use crate::vec2::Vec2;
+    if points.len() < 2 {
+        return None;
+    }
+    let inliers = |origin: &[T; 2], dir: &[T; 2]| -> Vec<usize> {
+        (0..points.len())
+            .filter(|&i| crate::line::distance(origin, dir, &points[i]) <= threshold)
+            .collect()
+    };
+    let mut best: Option<Vec<usize>> = None;
+    for _itr in 0..num_iteration {
+        let i0 = reng.random_range(0..points.len());
+        let i1 = reng.random_range(0..points.len());
+        let d = points[i1].sub(&points[i0]);
+        if i0 == i1 || d.squared_norm().is_zero() {
+            continue;
+        }
+        let idxs = inliers(&points[i0], &d.normalize());
+        if best.as_ref().is_none_or(|b| idxs.len() > b.len()) {
+            best = Some(idxs);
+        }
+    }
+    let best = best?;
+    let inlier_points: Vec<[T; 2]> = best.iter().map(|&i| points[i]).collect();
+    let (origin, dir) = fit_total_least_squares(&inlier_points)?;
+    Some((origin, dir, inliers(&origin, &dir)))
+}
+
+#[test]
+fn test_fit() {
+    use crate::vec2::Vec2;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let (origin0, dir0) = ([0.3, -0.2], [0.6, 0.8]);
+    // points near the line and the outliers
+    let mut points: Vec<[f64; 2]> = (0..100)
+        .map(|_| {
+            let t = 4. * rng.random::<f64>() - 2.;
+            let h = 0.01 * (2. * rng.random::<f64>() - 1.);
+            origin0
+                .add(&dir0.scale(t))
+                .add(&crate::vec2::rotate90(&dir0).scale(h))
+        })
+        .collect();
+    {
+        let (origin, dir) = fit_total_least_squares(&points).unwrap();
+        assert!(dir.cross(&dir0).abs() < 1.0e-2);
+        assert!(origin.sub(&origin0).cross(&dir0).abs() < 1.0e-2);
+        assert!((dir.norm() - 1.).abs() < 1.0e-10);
+    }
+    for _i in 0..50 {
+        points.push([4. * rng.random::<f64>() - 2., 4. * rng.random::<f64>() - 2.]);
+    }
+    let (origin, dir, inliers) = fit_ransac(&points, 0.02, 100, &mut rng).unwrap();
+    assert!(dir.cross(&dir0).abs() < 1.0e-2);
+    assert!(origin.sub(&origin0).cross(&dir0).abs() < 1.0e-2);
+    assert!((0..100).all(|i| inliers.contains(&i)));
+    assert!(inliers.len() < 110);
+    // the least squares without RANSAC is spoiled by the outliers
+    let (_, dir) = fit_total_least_squares(&points).unwrap();
+    assert!(dir.cross(&dir0).abs() > 1.0e-2);
+    // axis-aligned
+    let (_, dir) = fit_total_least_squares(&[[0., 1.], [1., 1.], [3., 1.]]).unwrap();
+    assert_eq!(dir[1], 0.);
+    assert_eq!(fit_ransac(&[[1., 1.], [1., 1.]], 0.1, 10, &mut rng), None);
+    assert_eq!(fit_total_least_squares::<f64>(&[]), None);
+}
@@ -0,0 +1,228 @@
+//! methods for 3D infinite line passing through two points.
+//! The line is represented as `(p0: &[Real;3], p1: &[Real;3])` with `p0 != p1`
+//! and the point on the line is parameterized by the ratio `r` as `p0 + r * (p1 - p0)`
+//! (the same as [`crate::edge3`] but the ratio is not clamped).
+
+use crate::vec3::Vec3;
+
+/// relation between two lines. See [`nearest_to_line3`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineLineClass {
+    /// the lines are neither parallel nor intersecting
+    Skew,
+    /// the lines intersect at one point
+    Intersecting,
+    /// the lines are parallel (including the coincident lines)
+    Parallel,
+}
+
+/// nearest points between the two lines. See [`nearest_to_line3`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineLineNearest<T> {
+    pub dist: T,
+    pub ratio_p: T,
+    pub ratio_q: T,
+    pub point_p: [T; 3],
+    pub point_q: [T; 3],
+    pub class: LineLineClass,
+}
+
+/// nearest points between the line passing through `p0`, `p1` and the line passing through `q0`, `q1`.
+///
+/// The lines are classified as parallel if the sine of the angle between them is below `sqrt(epsilon)`,
+/// and then `point_p` is `p0` (i.e., `ratio_p` is zero).
+/// The lines are classified as intersecting if the distance is below `sqrt(epsilon)` relative to the lengths of the defining edges.
+/// * Returns NaN if the defining points coincide. See [`try_nearest_to_line3`] for the checked version.
+pub fn nearest_to_line3<T>(p0: &[T; 3], p1: &[T; 3], q0: &[T; 3], q1: &[T; 3]) -> LineLineNearest<T>
+where
+    T: num_traits::Float,
+{
+    let vp = p1.sub(p0);
+    let vq = q1.sub(q0);
+    let w = p0.sub(q0);
+    let (a, b, c) = (vp.dot(&vp), vp.dot(&vq), vq.dot(&vq));
+    let (d, e) = (vp.dot(&w), vq.dot(&w));
+    let det = a * c - b * b;
+    let (ratio_p, ratio_q, is_parallel) = if det <= T::epsilon() * a * c {
+        let ratio_q = crate::line::parameter_of_projection(q0, &vq, p0);
+        (T::zero(), ratio_q, true)
+    } else {
+        ((b * e - c * d) / det, (a * e - b * d) / det, false)
+    };
+    let point_p = crate::vec3::axpy(ratio_p, &vp, p0);
+    let point_q = crate::vec3::axpy(ratio_q, &vq, q0);
+    let dist = point_p.sub(&point_q).norm();
+    let class = if is_parallel {
+        LineLineClass::Parallel
+    } else if dist * dist <= T::epsilon() * (a + c) {
+        LineLineClass::Intersecting
+    } else {
+        LineLineClass::Skew
+    };
+    LineLineNearest {
+        dist,
+        ratio_p,
+        ratio_q,
+        point_p,
+        point_q,
+        class,
+    }
+}
+
+/// the same as [`nearest_to_line3`] but returns the error for the degenerate input
+/// (coincident defining points or non-finite coordinates) instead of the NaN values
+pub fn try_nearest_to_line3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+) -> Result<LineLineNearest<T>, crate::error::DegenerateError>
+where
+    T: num_traits::Float,
+{
+    crate::error::check_finite(&[p0, p1, q0, q1])?;
+    if p0 == p1 || q0 == q1 {
+        return Err(crate::error::DegenerateError::ZeroLengthEdge);
+    }
+    let res = nearest_to_line3(p0, p1, q0, q1);
+    if !(res.dist.is_finite() && res.ratio_p.is_finite() && res.ratio_q.is_finite()) {
+        return Err(crate::error::DegenerateError::NonFinite);
+    }
+    Ok(res)
+}
+
+#[test]
+fn test_try_nearest_to_line3() {
+    use crate::error::DegenerateError;
+    let (p0, p1) = ([0., 0., 0.], [1., 0., 0.]);
+    let q = [0., 1., 1.];
+    assert_eq!(
+        try_nearest_to_line3(&p0, &p0, &q, &q),
+        Err(DegenerateError::ZeroLengthEdge)
+    );
+    assert_eq!(
+        try_nearest_to_line3(&p0, &p1, &q, &q),
+        Err(DegenerateError::ZeroLengthEdge)
+    );
+    assert_eq!(
+        try_nearest_to_line3(&p0, &p1, &[f64::INFINITY, 0., 0.], &q),
+        Err(DegenerateError::NonFinite)
+    );
+    let res = try_nearest_to_line3(&p0, &p1, &q, &[0., 2., 1.]).unwrap();
+    assert_eq!(res, nearest_to_line3(&p0, &p1, &q, &[0., 2., 1.]));
+}
+
+/// derivative of the ratios of [`nearest_to_line3`] with respect to the defining points
+/// * Return `Some((dr_p, dr_q))` where `dr_p[i]` (resp. `dr_q[i]`) is the gradient of `ratio_p` (resp. `ratio_q`)
+///   with respect to the `i`-th point of `[p0, p1, q0, q1]`
+/// * Return `None` for the parallel lines where the ratios are not unique
+#[allow(clippy::type_complexity)]
+pub fn dw_nearest_to_line3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+) -> Option<([[T; 3]; 4], [[T; 3]; 4])>
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let res = nearest_to_line3(p0, p1, q0, q1);
+    if res.class == LineLineClass::Parallel {
+        return None;
+    }
+    let (s, t) = (res.ratio_p, res.ratio_q);
+    let vp = p1.sub(p0);
+    let vq = q1.sub(q0);
+    let r = res.point_p.sub(&res.point_q);
+    let (a, b, c) = (vp.dot(&vp), vp.dot(&vq), vq.dot(&vq));
+    let det = a * c - b * b;
+    // the ratios satisfy `f0 = vp.r = 0` and `f1 = vq.r = 0`.
+    // gradients of `f0` and `f1` w.r.t. the points while the ratios are fixed
+    let df0 = [
+        vp.scale(one - s).sub(&r),
+        vp.scale(s).add(&r),
+        vp.scale(t - one),
+        vp.scale(-t),
+    ];
+    let df1 = [
+        vq.scale(one - s),
+        vq.scale(s),
+        vq.scale(t - one).sub(&r),
+        vq.scale(-t).add(&r),
+    ];
+    // implicit function theorem with the Jacobian `[[a, -b], [b, -c]]` w.r.t. `(s, t)`
+    let dr_p = std::array::from_fn(|i| df1[i].scale(b / det).sub(&df0[i].scale(c / det)));
+    let dr_q = std::array::from_fn(|i| df1[i].scale(a / det).sub(&df0[i].scale(b / det)));
+    Some((dr_p, dr_q))
+}
+
+#[test]
+fn test_nearest_to_line3() {
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _i in 0..1000 {
+        let p0 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let p1 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let q0 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let q1 = crate::vec3::sample_unit_cube::<_, f64>(&mut reng);
+        let res = nearest_to_line3(&p0, &p1, &q0, &q1);
+        assert_eq!(res.class, LineLineClass::Skew);
+        // the connecting vector is orthogonal to both lines
+        let r = res.point_p.sub(&res.point_q);
+        assert!(r.dot(&p1.sub(&p0)).abs() < 1.0e-8);
+        assert!(r.dot(&q1.sub(&q0)).abs() < 1.0e-8);
+        // not farther than the segments
+        let (dist_edge, _, _) = crate::edge3::nearest_to_edge3(&p0, &p1, &q0, &q1);
+        assert!(res.dist <= dist_edge + 1.0e-10);
+    }
+    // intersecting
+    let res = nearest_to_line3(&[0., 0., 0.], &[1., 0., 0.], &[3., 1., 0.], &[3., 2., 0.]);
+    assert_eq!(res.class, LineLineClass::Intersecting);
+    assert_eq!((res.dist, res.ratio_p, res.ratio_q), (0., 3., -1.));
+    // parallel
+    let res = nearest_to_line3(&[0., 0., 0.], &[1., 0., 0.], &[4., 1., 0.], &[2., 1., 0.]);
+    assert_eq!(res.class, LineLineClass::Parallel);
+    assert_eq!((res.dist, res.ratio_p, res.ratio_q), (1., 0., 2.));
+    assert_eq!(
+        dw_nearest_to_line3(&[0., 0., 0.], &[1., 0., 0.], &[4., 1., 0.], &[2., 1., 0.]),
+        None
+    );
+}
+
+#[test]
+fn test_dw_nearest_to_line3() {
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let eps = 1.0e-6;
+    for _i in 0..100 {
+        let mut ps: [[f64; 3]; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut reng));
+        let res0 = nearest_to_line3(&ps[0], &ps[1], &ps[2], &ps[3]);
+        let sin2 = {
+            let (vp, vq) = (ps[1].sub(&ps[0]), ps[3].sub(&ps[2]));
+            vp.cross(&vq).squared_norm() / (vp.squared_norm() * vq.squared_norm())
+        };
+        if sin2 < 0.1 {
+            continue;
+        }
+        let (dr_p, dr_q) = dw_nearest_to_line3(&ps[0], &ps[1], &ps[2], &ps[3]).unwrap();
+        for (i_node, i_dim) in itertools::iproduct!(0..4, 0..3) {
+            ps[i_node][i_dim] += eps;
+            let res1 = nearest_to_line3(&ps[0], &ps[1], &ps[2], &ps[3]);
+            ps[i_node][i_dim] -= eps;
+            let v_num = (res1.ratio_p - res0.ratio_p) / eps;
+            let v_ana = dr_p[i_node][i_dim];
+            assert!(
+                (v_num - v_ana).abs() < 1.0e-4 * (1. + v_ana.abs()),
+                "{v_num} {v_ana}"
+            );
+            let v_num = (res1.ratio_q - res0.ratio_q) / eps;
+            let v_ana = dr_q[i_node][i_dim];
+            assert!(
+                (v_num - v_ana).abs() < 1.0e-4 * (1. + v_ana.abs()),
+                "{v_num} {v_ana}"
+            );
+        }
+    }
+}
@@ -1,4 +1,88 @@
 //! methods for 2x3 matrix used for affine transformation especially for Gaussian splatting
+//! The first four elements are the 2x2 linear part and the last two elements are the translation.
+//! This is the 3x3 homogeneous matrix with the bottom row `[0,0,1]` omitted.
+
+pub fn from_identity<Real>() -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    [one, zero, zero, one, zero, zero]
+}
+
+pub fn from_translate<Real>(t: &[Real; 2]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    [one, zero, zero, one, t[0], t[1]]
+}
+
+/// counter-clockwise rotation around the origin
+pub fn from_rotate<Real>(theta: Real) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let (s, c) = theta.sin_cos();
+    [c, s, -s, c, zero, zero]
+}
+
+pub fn from_scale<Real>(s: &[Real; 2]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [s[0], zero, zero, s[1], zero, zero]
+}
+
+/// the affine transformation `x -> a * x + t`
+pub fn from_mat2_col_major_and_translation<Real>(a: &[Real; 4], t: &[Real; 2]) -> [Real; 6]
+where
+    Real: Copy,
+{
+    [a[0], a[1], a[2], a[3], t[0], t[1]]
+}
+
+/// the bottom row of the 3x3 homogeneous matrix is ignored
+pub fn from_mat3_col_major<Real>(m: &[Real; 9]) -> [Real; 6]
+where
+    Real: Copy,
+{
+    [m[0], m[1], m[3], m[4], m[6], m[7]]
+}
+
+// above: from methods
+// ---------------------------------
+
+/// 3x3 homogeneous matrix with the bottom row `[0,0,1]`
+pub fn to_mat3_col_major<Real>(a: &[Real; 6]) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [a[0], a[1], zero, a[2], a[3], zero, a[4], a[5], Real::one()]
+}
+
+/// the 2x2 linear part
+pub fn to_mat2_col_major<Real>(a: &[Real; 6]) -> [Real; 4]
+where
+    Real: Copy,
+{
+    [a[0], a[1], a[2], a[3]]
+}
+
+pub fn to_vec2_translation<Real>(a: &[Real; 6]) -> [Real; 2]
+where
+    Real: Copy,
+{
+    [a[4], a[5]]
+}
+
+// above: to methods
+// ---------------------------------
 
 pub fn mult_vec3<Real>(a: &[Real; 6], b: &[Real; 3]) -> [Real; 2]
 where
@@ -42,3 +126,96 @@ pub fn transform_ndc2pix(img_shape: (usize, usize)) -> [f32; 6] {
         0.5 * (img_shape.1 as f32),
     ]
 }
+
+pub fn transform_point<Real>(a: &[Real; 6], p: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [
+        a[0] * p[0] + a[2] * p[1] + a[4],
+        a[1] * p[0] + a[3] * p[1] + a[5],
+    ]
+}
+
+/// transform the direction (i.e., the translation is not applied)
+pub fn transform_direction<Real>(a: &[Real; 6], v: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [a[0] * v[0] + a[2] * v[1], a[1] * v[0] + a[3] * v[1]]
+}
+
+/// the transformation `a * b` that applies `b` first then `a`
+pub fn mult_mat<Real>(a: &[Real; 6], b: &[Real; 6]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let c0 = transform_direction(a, &[b[0], b[1]]);
+    let c1 = transform_direction(a, &[b[2], b[3]]);
+    let t = transform_point(a, &[b[4], b[5]]);
+    [c0[0], c0[1], c1[0], c1[1], t[0], t[1]]
+}
+
+/// Returns `None` if the linear part is singular
+pub fn try_inverse<Real>(a: &[Real; 6]) -> Option<[Real; 6]>
+where
+    Real: num_traits::Float,
+{
+    let det = a[0] * a[3] - a[1] * a[2];
+    if det.is_zero() {
+        return None;
+    }
+    let inv_det = Real::one() / det;
+    let l = [
+        a[3] * inv_det,
+        -a[1] * inv_det,
+        -a[2] * inv_det,
+        a[0] * inv_det,
+    ];
+    let t = [-(l[0] * a[4] + l[2] * a[5]), -(l[1] * a[4] + l[3] * a[5])];
+    Some(from_mat2_col_major_and_translation(&l, &t))
+}
+
+#[test]
+fn test_affine_2d() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 6] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let b: [f64; 6] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let (ma, mb) = (to_mat3_col_major(&a), to_mat3_col_major(&b));
+        assert_eq!(from_mat3_col_major(&ma), a);
+        let p = [0.3, -0.5];
+        {
+            let p0 = transform_point(&a, &p);
+            let p1 = crate::mat3_col_major::transform_homogeneous(&ma, &p).unwrap();
+            assert!(crate::vec2::length(&crate::vec2::sub(&p0, &p1)) < 1.0e-10);
+            let v0 = transform_direction(&a, &p);
+            let v1 = crate::mat2_col_major::mult_vec(&to_mat2_col_major(&a), &p);
+            assert!(crate::vec2::length(&crate::vec2::sub(&v0, &v1)) < 1.0e-10);
+        }
+        {
+            let mab0 = to_mat3_col_major(&mult_mat(&a, &b));
+            let mab1 = crate::mat3_col_major::mult_mat_col_major(&ma, &mb);
+            assert!((0..9).all(|i| (mab0[i] - mab1[i]).abs() < 1.0e-10));
+        }
+        {
+            let mai0 = to_mat3_col_major(&try_inverse(&a).unwrap());
+            let mai1 = crate::mat3_col_major::try_inverse(&ma).unwrap();
+            assert!((0..9).all(|i| (mai0[i] - mai1[i]).abs() < 1.0e-6));
+        }
+    }
+    {
+        // translate * rotate * scale
+        let m = mult_mat(
+            &from_translate(&[1.0f64, 2.0]),
+            &mult_mat(
+                &from_rotate(std::f64::consts::FRAC_PI_2),
+                &from_scale(&[2., 3.]),
+            ),
+        );
+        let p = transform_point(&m, &[1.0, 1.0]);
+        assert!(crate::vec2::length(&crate::vec2::sub(&p, &[-2.0, 4.0])) < 1.0e-10);
+    }
+}
@@ -150,6 +150,96 @@ where
     mult_mat_col_major(&z, &yx)
 }
 
+/// sequence of the rotation axes of Euler angles.
+/// The first six are Tait-Bryan angles and the last six are proper Euler angles
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EulerAxes {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerAxes {
+    /// indices of the three rotation axes (x:0, y:1, z:2)
+    pub fn to_indices(&self) -> [usize; 3] {
+        match self {
+            EulerAxes::XYZ => [0, 1, 2],
+            EulerAxes::XZY => [0, 2, 1],
+            EulerAxes::YXZ => [1, 0, 2],
+            EulerAxes::YZX => [1, 2, 0],
+            EulerAxes::ZXY => [2, 0, 1],
+            EulerAxes::ZYX => [2, 1, 0],
+            EulerAxes::XYX => [0, 1, 0],
+            EulerAxes::XZX => [0, 2, 0],
+            EulerAxes::YXY => [1, 0, 1],
+            EulerAxes::YZY => [1, 2, 1],
+            EulerAxes::ZXZ => [2, 0, 2],
+            EulerAxes::ZYZ => [2, 1, 2],
+        }
+    }
+}
+
+/// convention of Euler angles `(a, b, c)` for the axis sequence `(i, j, k)`
+/// * `Extrinsic` - rotations around the fixed axes: `R = R_k(c) * R_j(b) * R_i(a)`
+/// * `Intrinsic` - rotations around the rotated axes: `R = R_i(a) * R_j(b) * R_k(c)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EulerOrder {
+    Extrinsic(EulerAxes),
+    Intrinsic(EulerAxes),
+}
+
+fn from_rotate_axis<Real>(i_axis: usize, theta: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    match i_axis {
+        0 => from_rotate_x(theta),
+        1 => from_rotate_y(theta),
+        2 => from_rotate_z(theta),
+        _ => unreachable!(),
+    }
+}
+
+/// rotation matrix from Euler angles `(a, b, c)` in the convention `order`.
+/// `EulerOrder::Extrinsic(EulerAxes::XYZ)` is the same as the Bryant angles.
+pub fn from_euler<Real>(order: EulerOrder, a: Real, b: Real, c: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let (axes, is_extrinsic) = match order {
+        EulerOrder::Extrinsic(axes) => (axes.to_indices(), true),
+        EulerOrder::Intrinsic(axes) => (axes.to_indices(), false),
+    };
+    let ra = from_rotate_axis(axes[0], a);
+    let rb = from_rotate_axis(axes[1], b);
+    let rc = from_rotate_axis(axes[2], c);
+    if is_extrinsic {
+        mult_mat_col_major(&rc, &mult_mat_col_major(&rb, &ra))
+    } else {
+        mult_mat_col_major(&ra, &mult_mat_col_major(&rb, &rc))
+    }
+}
+
+#[test]
+fn test_from_euler() {
+    let r0 = from_euler(EulerOrder::Extrinsic(EulerAxes::XYZ), 0.1f64, -0.4, 1.3);
+    let r1 = from_bryant_angles(0.1f64, -0.4, 1.3);
+    assert!(r0.sub(&r1).norm() < 1.0e-15);
+    let r0 = from_euler(EulerOrder::Intrinsic(EulerAxes::ZXZ), 0.1f64, -0.4, 1.3);
+    let r1 = from_euler(EulerOrder::Extrinsic(EulerAxes::ZXZ), 1.3f64, -0.4, 0.1);
+    assert!(r0.sub(&r1).norm() < 1.0e-15);
+}
+
 /// transformation converting normalized device coordinate (NDC) `[-1,+1]^2` to pixel coordinate
 /// * `image_shape` - (width, height)
 pub fn from_transform_ndc2pix(img_shape: (usize, usize)) -> [f32; 9] {
@@ -296,6 +386,40 @@ where
     crate::vec3::to_mat3_from_axisangle_vec(n)
 }
 
+/// sample a rotation matrix uniformly from SO(3) (Haar measure)
+///
+/// The unit quaternion from [`crate::quaternion::sample_uniform`] is converted to a matrix.
+pub fn sample_uniform_rotation<Reng, T>(rng: &mut Reng) -> [T; 9]
+where
+    Reng: rand::Rng,
+    T: num_traits::Float + num_traits::FloatConst,
+    rand::distr::StandardUniform: rand::distr::Distribution<T>,
+{
+    crate::quaternion::to_mat3_col_major(&crate::quaternion::sample_uniform(rng))
+}
+
+#[test]
+fn test_sample_uniform_rotation() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut mean = [0f64; 9];
+    let num_sample = 10000;
+    for _iter in 0..num_sample {
+        let r: [f64; 9] = sample_uniform_rotation(&mut rng);
+        assert!((r.determinant() - 1.).abs() < 1.0e-10);
+        let diff = r
+            .transpose()
+            .mult_mat_col_major(&r)
+            .sub(&from_identity())
+            .squared_norm();
+        assert!(diff < 1.0e-20);
+        mean = mean.add(&r);
+    }
+    // the average of the uniformly distributed rotations is zero
+    let mean = mean.scale(1. / num_sample as f64);
+    assert!(mean.norm() < 0.05, "{}", mean.norm());
+}
+
 // above: from methods
 // ---------------------------------------------
 // below: to methods
@@ -403,9 +527,7 @@ fn test_to_quaternion() {
         let quat0 = quat0.normalized();
         let r_mat = quat0.to_mat3_col_major();
         let quat1 = to_quaternion(&r_mat);
-        let quat0 = nalgebra::Vector4::<f32>::from_row_slice(&quat0);
-        let quat1 = nalgebra::Vector4::<f32>::from_row_slice(&quat1);
-        assert!((quat0 - quat1).norm().min((quat0 + quat1).norm()) < 1.0e-7);
+        assert!(crate::quaternion::are_close(&quat0, &quat1, 2.0e-7f32));
     }
 }
 
@@ -430,6 +552,60 @@ where
     [c0 * (m[5] - m[7]), c0 * (m[6] - m[2]), c0 * (m[1] - m[3])]
 }
 
+/// Euler angles `[a, b, c]` in the convention `order` from a rotation matrix.
+/// The first and last angles are in `[-pi, pi]`.
+/// See [`crate::quaternion::to_euler`] for the range of the second angle.
+pub fn to_euler<Real>(m: &[Real; 9], order: EulerOrder) -> [Real; 3]
+where
+    Real: num_traits::Float + num_traits::FloatConst + std::fmt::Debug,
+{
+    crate::quaternion::to_euler(&to_quaternion(m), order)
+}
+
+#[test]
+fn test_to_euler() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let all_axes = [
+        EulerAxes::XYZ,
+        EulerAxes::XZY,
+        EulerAxes::YXZ,
+        EulerAxes::YZX,
+        EulerAxes::ZXY,
+        EulerAxes::ZYX,
+        EulerAxes::XYX,
+        EulerAxes::XZX,
+        EulerAxes::YXY,
+        EulerAxes::YZY,
+        EulerAxes::ZXZ,
+        EulerAxes::ZYZ,
+    ];
+    for (axes, i_kind) in itertools::iproduct!(all_axes, 0..2) {
+        let order = match i_kind {
+            0 => EulerOrder::Extrinsic(axes),
+            1 => EulerOrder::Intrinsic(axes),
+            _ => unreachable!(),
+        };
+        for i_iter in 0..100 {
+            let angles: [f64; 3] = match i_iter {
+                // singular configurations
+                0 => [0.3, 0., -0.2],
+                1 => [0.3, std::f64::consts::FRAC_PI_2, -0.2],
+                2 => [0.3, std::f64::consts::PI, -0.2],
+                _ => std::array::from_fn(|_| rng.random_range(-3f64..3f64)),
+            };
+            let r0 = from_euler(order, angles[0], angles[1], angles[2]);
+            let angles1 = to_euler(&r0, order);
+            let r1 = from_euler(order, angles1[0], angles1[1], angles1[2]);
+            assert!(
+                r0.sub(&r1).norm() < 1.0e-6,
+                "{order:?} {angles:?} {angles1:?}"
+            );
+        }
+    }
+}
+
 /// Return a 2x3 matrix with column major storage by throwing away the last row
 pub fn to_mat2x3_col_major_xy(m: &[f32; 9]) -> [f32; 6] {
     [m[0], m[1], m[3], m[4], m[6], m[7]]
@@ -611,29 +787,84 @@ where
         - b[3] * b[1] * b[8]
 }
 
+/// real eigenvalues of a general (not necessarily symmetric) 3x3 matrix
+/// by solving the characteristic cubic polynomial.
+/// The result is the same for the row major storage.
+///
+/// # Returns
+/// `(eigen_values, num_real)` where the first `num_real` elements are the real eigenvalues
+/// in ascending order. `num_real` is one if the other two eigenvalues are a complex pair.
+pub fn eigenvalues_real<T>(m: &[T; 9]) -> ([T; 3], usize)
+where
+    T: num_traits::Float,
+{
+    // det(x*I - m) = x^3 - tr(m) x^2 + (sum of principal minors) x - det(m)
+    let trace = m[0] + m[4] + m[8];
+    let minors = m[0] * m[4] - m[1] * m[3] + m[4] * m[8] - m[5] * m[7] + m[8] * m[0] - m[2] * m[6];
+    crate::polynomial_root::cubic_roots_real(-determinant(m), minors, -trace, T::one())
+}
+
+#[test]
+fn test_eigenvalues_real() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    for _iter in 0..100 {
+        let p: [f64; 9] = std::array::from_fn(|_| rng.random_range(-1f64..1f64));
+        let Some(pi) = p.try_inverse() else { continue };
+        let mut l: [f64; 3] = std::array::from_fn(|_| rng.random_range(-2f64..2f64));
+        l.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let m = p
+            .mult_mat_col_major(&from_diagonal(&l))
+            .mult_mat_col_major(&pi);
+        let (e, n) = eigenvalues_real(&m);
+        assert_eq!(n, 3);
+        for i in 0..3 {
+            assert!(
+                (e[i] - l[i]).abs() < 1.0e-5 * (1. + m.norm()),
+                "{l:?} {e:?}"
+            );
+        }
+    }
+    {
+        // rotation has the eigen value one and a complex pair
+        let r = from_bryant_angles(0.3f64, -0.8, 1.1);
+        let (e, n) = eigenvalues_real(&r);
+        assert_eq!(n, 1);
+        assert!((e[0] - 1.).abs() < 1.0e-10);
+    }
+    {
+        // double eigen values
+        let r = from_bryant_angles(0.3f64, -0.8, 1.1);
+        let m = r
+            .mult_mat_col_major(&from_diagonal(&[2., 1., 1.]))
+            .mult_mat_col_major(&r.transpose());
+        let (e, n) = eigenvalues_real(&m);
+        assert_eq!(n, 3);
+        assert!((e[0] - 1.).abs() < 1.0e-6 && (e[1] - 1.).abs() < 1.0e-6);
+        assert!((e[2] - 2.).abs() < 1.0e-10);
+    }
+}
+
 /// # Argument
 /// * `n` - world 3D vector that corresponds local z (no need to be unit vector)
+///
+/// Returns the identity matrix if `n` is (nearly) zero
 pub fn transform_lcl2world_given_local_z<T>(n: &[T; 3]) -> [T; 9]
 where
     T: num_traits::Float,
 {
     use crate::vec3::Vec3;
-    let n = n.normalize();
-    let zero = T::zero();
-    let one = T::one();
-
-    let t = if n[0].abs() > T::from(0.1).unwrap() {
-        [zero, one, zero]
-    } else {
-        [one, zero, zero]
+    let Some(n) = n.normalize_checked(T::min_positive_value()) else {
+        return from_identity();
     };
-    let u = t.cross(&n).normalize();
-    let v = n.cross(&u);
+    let (u, v) = n.orthonormal_basis();
     [u[0], u[1], u[2], v[0], v[1], v[2], n[0], n[1], n[2]]
 }
 
 /// Return 3x3 rotation matrix as a column major storage.
 /// That rotation matrix rotate `v0: &[T;3]` to `v1: &[T;3]`.
+/// Returns the identity matrix if either of the vectors is (nearly) zero.
 pub fn minimum_rotation_matrix<T>(v0: &[T; 3], v1: &[T; 3]) -> [T; 9]
 where
     T: num_traits::Float,
@@ -641,8 +872,10 @@ where
     use crate::vec3::Vec3;
     let one = T::one();
     let half = one / (one + one);
-    let ep = v0.normalize();
-    let eq = v1.normalize();
+    let eps = T::min_positive_value();
+    let (Some(ep), Some(eq)) = (v0.normalize_checked(eps), v1.normalize_checked(eps)) else {
+        return from_identity();
+    };
     let n = ep.cross(&eq);
     let st2 = n.dot(&n);
     let ct = ep.dot(&eq);
@@ -717,6 +950,11 @@ fn test_minimum_rotation() {
             assert!(b0.sub(&b1).norm() < 1.0e-15);
         }
     }
+    // zero vectors do not produce NaN
+    let i = from_identity::<f64>();
+    assert_eq!(minimum_rotation_matrix(&[0.; 3], &[1., 0., 0.]), i);
+    assert_eq!(minimum_rotation_matrix(&[0., 1., 0.], &[0.; 3]), i);
+    assert_eq!(transform_lcl2world_given_local_z(&[0f64; 3]), i);
 }
 
 // -----------------------------------
@@ -831,7 +1069,6 @@ where
     let (u, _s, v) = svd(a, EigenDecompositionModes::JacobiNumIter(20)).unwrap();
     let v_t = transpose(&v);
     let u_vt = mult_mat_col_major(&u, &v_t);
-    // dbg!(determinant(&u_vt));
     if determinant(&u_vt) > T::zero() {
         u_vt
     } else {
@@ -856,6 +1093,47 @@ fn test_rotational_component() {
     }
 }
 
+/// project a matrix that is close to a rotation matrix (e.g., after integrating the
+/// angular velocity) back to the rotation matrix with two Newton iterations
+/// `R <- 1.5 * R - 0.5 * R * R^T * R`.
+/// This is much cheaper than the SVD-based [`rotational_component`] but the input
+/// should be close to a rotation matrix.
+pub fn orthonormalize_rotation<T>(r: &[T; 9]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let half = one / (one + one);
+    let one_and_half = one + half;
+    let mut r = *r;
+    for _itr in 0..2 {
+        let rrtr = mult_mat_col_major(&mult_mat_col_major(&r, &transpose(&r)), &r);
+        r = sub(&scale(&r, one_and_half), &scale(&rrtr, half));
+    }
+    r
+}
+
+#[test]
+fn test_orthonormalize_rotation() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    for _iter in 0..100 {
+        let r0: [f64; 9] = sample_uniform_rotation(&mut rng);
+        let noise: [f64; 9] = std::array::from_fn(|_| rng.random_range(-1.0e-3..1.0e-3));
+        let r1 = r0.add(&noise);
+        let r2 = orthonormalize_rotation(&r1);
+        let err = r2
+            .transpose()
+            .mult_mat_col_major(&r2)
+            .sub(&from_identity())
+            .norm();
+        assert!(err < 1.0e-8, "{err}");
+        assert!(r2.sub(&r0).norm() < 1.0e-2);
+        assert!(r2.sub(&rotational_component(&r1)).norm() < 1.0e-8);
+    }
+}
+
 /// Jacobian of singular value decomposition
 ///
 /// # Reference
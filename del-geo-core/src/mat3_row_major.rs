@@ -1,7 +1,10 @@
 //! functions and the trait for 3x3 matrix with row major storage
 
 /// trait for 3x3 matrix in row-major order
-pub trait Mat3RowMajor<Real: num_traits::Float> {
+pub trait Mat3RowMajor<Real: num_traits::Float>
+where
+    Self: Sized,
+{
     fn from_identity() -> Self;
     fn determinant(&self) -> Real;
     fn squared_norm(&self) -> Real;
@@ -11,6 +14,11 @@ pub trait Mat3RowMajor<Real: num_traits::Float> {
     fn scale(&self, s: Real) -> Self;
     fn mult_mat_row_major(&self, b: &Self) -> Self;
     fn mult_vec(&self, b: &[Real; 3]) -> [Real; 3];
+    fn from_diagonal(diagonal: &[Real; 3]) -> Self;
+    fn try_inverse(&self) -> Option<Self>;
+    fn transform_homogeneous(&self, x: &[Real; 2]) -> Option<[Real; 2]>;
+    fn norm(&self) -> Real;
+    fn to_mat3_array_of_array(&self) -> [[Real; 3]; 3];
 }
 impl<Real> Mat3RowMajor<Real> for [Real; 9]
 where
@@ -43,6 +51,21 @@ where
     fn mult_vec(&self, b: &[Real; 3]) -> [Real; 3] {
         mult_vec(self, b)
     }
+    fn from_diagonal(diagonal: &[Real; 3]) -> Self {
+        from_diagonal(diagonal)
+    }
+    fn try_inverse(&self) -> Option<Self> {
+        try_inverse(self)
+    }
+    fn transform_homogeneous(&self, x: &[Real; 2]) -> Option<[Real; 2]> {
+        transform_homogeneous(self, x)
+    }
+    fn norm(&self) -> Real {
+        norm(self)
+    }
+    fn to_mat3_array_of_array(&self) -> [[Real; 3]; 3] {
+        to_mat3_array_of_array(self)
+    }
 }
 
 /// returns an identity matrix
@@ -90,6 +113,104 @@ where
     ]
 }
 
+/// transformation converting normalized device coordinate (NDC) `[-1,+1]^2` to pixel coordinate
+/// * `image_shape` - (width, height)
+pub fn from_transform_ndc2pix(img_shape: (usize, usize)) -> [f32; 9] {
+    transpose(&crate::mat3_col_major::from_transform_ndc2pix(img_shape))
+}
+
+/// transformation converting unit coodinate `[0,+1]^2` to pixel coordinate
+/// * `image_shape` - (width, height)
+pub fn from_transform_unit2pix(img_shape: (usize, usize)) -> [f32; 9] {
+    transpose(&crate::mat3_col_major::from_transform_unit2pix(img_shape))
+}
+
+/// See [`crate::mat3_col_major::from_transform_world2pix_ortho_preserve_asp`]
+pub fn from_transform_world2pix_ortho_preserve_asp(
+    image_size: &(usize, usize),
+    aabb_world: &[f32; 4],
+) -> [f32; 9] {
+    transpose(
+        &crate::mat3_col_major::from_transform_world2pix_ortho_preserve_asp(image_size, aabb_world),
+    )
+}
+
+pub fn from_translate<Real>(v: &[Real; 2]) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    [one, zero, v[0], zero, one, v[1], zero, zero, one]
+}
+
+pub fn from_rotate_x<Real>(theta: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_rotate_x(theta))
+}
+
+pub fn from_rotate_y<Real>(theta: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_rotate_y(theta))
+}
+
+pub fn from_rotate_z<Real>(theta: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_rotate_z(theta))
+}
+
+/// rotation matrix where x-rotation, y-rotation and z-rotation is applied sequentially
+pub fn from_bryant_angles<Real>(rx: Real, ry: Real, rz: Real) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_bryant_angles(rx, ry, rz))
+}
+
+pub fn from_scaled_outer_product<T>(s: T, a: &[T; 3], b: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    crate::mat3_col_major::from_scaled_outer_product(s, b, a)
+}
+
+pub fn from_mat2_col_major_adding_z<T>(r: &[T; 4]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_mat2_col_major_adding_z(r))
+}
+
+pub fn from_affine_linear_and_translation<T>(r: &[T; 4], u_se: &[T; 2]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_affine_linear_and_translation(
+        r, u_se,
+    ))
+}
+
+pub fn from_projection_onto_plane<T>(n: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    // the matrix is symmetric
+    crate::mat3_col_major::from_projection_onto_plane(n)
+}
+
+pub fn from_axisangle_vec<T>(n: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float + std::fmt::Debug,
+{
+    transpose(&crate::mat3_col_major::from_axisangle_vec(n))
+}
+
 // above: from method
 // --------------------------
 // below: to method
@@ -130,6 +251,160 @@ where
     ([a[0], a[3], a[6]], [a[1], a[4], a[7]], [a[2], a[5], a[8]])
 }
 
+pub fn to_vec3_row<T>(m: &[T; 9], idx: usize) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    [m[idx * 3], m[idx * 3 + 1], m[idx * 3 + 2]]
+}
+
+pub fn to_vec3_column<T>(m: &[T; 9], idx: usize) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    [m[idx], m[idx + 3], m[idx + 6]]
+}
+
+/// Return a 2x3 matrix with column major storage by throwing away the last row
+pub fn to_mat2x3_col_major_xy(m: &[f32; 9]) -> [f32; 6] {
+    [m[0], m[3], m[1], m[4], m[2], m[5]]
+}
+
+pub fn to_mat3_array_of_array<T>(a: &[T; 9]) -> [[T; 3]; 3]
+where
+    T: num_traits::Float,
+{
+    [[a[0], a[1], a[2]], [a[3], a[4], a[5]], [a[6], a[7], a[8]]]
+}
+
+/// Return a quaternion with `[i,j,k,w]` storage
+/// the input must be a rotation matrix
+pub fn to_quaternion<Real>(p: &[Real; 9]) -> [Real; 4]
+where
+    Real: num_traits::Float + std::fmt::Debug,
+{
+    crate::mat3_col_major::to_quaternion(&transpose(p))
+}
+
+/// rotation matrix from Euler angles. See [`crate::mat3_col_major::from_euler`]
+pub fn from_euler<Real>(
+    order: crate::mat3_col_major::EulerOrder,
+    a: Real,
+    b: Real,
+    c: Real,
+) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::from_euler(order, a, b, c))
+}
+
+/// Euler angles from a rotation matrix. See [`crate::mat3_col_major::to_euler`]
+pub fn to_euler<Real>(m: &[Real; 9], order: crate::mat3_col_major::EulerOrder) -> [Real; 3]
+where
+    Real: num_traits::Float + num_traits::FloatConst + std::fmt::Debug,
+{
+    crate::mat3_col_major::to_euler(&transpose(m), order)
+}
+
+/// sample a rotation matrix uniformly from SO(3) (Haar measure)
+pub fn sample_uniform_rotation<Reng, T>(rng: &mut Reng) -> [T; 9]
+where
+    Reng: rand::Rng,
+    T: num_traits::Float + num_traits::FloatConst,
+    rand::distr::StandardUniform: rand::distr::Distribution<T>,
+{
+    transpose(&crate::mat3_col_major::sample_uniform_rotation(rng))
+}
+
+/// See [`crate::mat3_col_major::orthonormalize_rotation`]
+pub fn orthonormalize_rotation<T>(r: &[T; 9]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::orthonormalize_rotation(&transpose(
+        r,
+    )))
+}
+
+/// real eigenvalues of a general 3x3 matrix. See [`crate::mat3_col_major::eigenvalues_real`]
+pub fn eigenvalues_real<T>(m: &[T; 9]) -> ([T; 3], usize)
+where
+    T: num_traits::Float,
+{
+    crate::mat3_col_major::eigenvalues_real(m)
+}
+
+pub fn to_vec3_axisangle_from_rot_mat<T>(m: &[T; 9]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    crate::mat3_col_major::to_vec3_axisangle_from_rot_mat(&transpose(m))
+}
+
+#[test]
+fn test_parity_with_col_major() {
+    use crate::quaternion::Quaternion;
+    let ms = [
+        (
+            from_rotate_x(0.3f64),
+            crate::mat3_col_major::from_rotate_x(0.3),
+        ),
+        (
+            from_rotate_y(0.3),
+            crate::mat3_col_major::from_rotate_y(0.3),
+        ),
+        (
+            from_rotate_z(0.3),
+            crate::mat3_col_major::from_rotate_z(0.3),
+        ),
+        (
+            from_bryant_angles(0.1, 0.2, 0.3),
+            crate::mat3_col_major::from_bryant_angles(0.1, 0.2, 0.3),
+        ),
+        (
+            from_translate(&[1.5, -0.5]),
+            crate::mat3_col_major::from_translate(&[1.5, -0.5]),
+        ),
+        (
+            from_scaled_outer_product(2.0, &[1., 2., 3.], &[-1., 0.5, 4.]),
+            crate::mat3_col_major::from_scaled_outer_product(2.0, &[1., 2., 3.], &[-1., 0.5, 4.]),
+        ),
+    ];
+    for (m_row, m_col) in ms {
+        let v = [0.3, -1.2, 0.7];
+        let diff = crate::vec3::sub(
+            &mult_vec(&m_row, &v),
+            &crate::mat3_col_major::mult_vec(&m_col, &v),
+        );
+        assert!(crate::vec3::norm(&diff) < 1.0e-12);
+        let p0 = transform_homogeneous(&m_row, &[0.4, 0.9]).unwrap();
+        let p1 = crate::mat3_col_major::transform_homogeneous(&m_col, &[0.4, 0.9]).unwrap();
+        assert!(crate::vec2::length(&crate::vec2::sub(&p0, &p1)) < 1.0e-12);
+    }
+    {
+        let quat0 = [1f64, -2., 0.5, 3.].normalized();
+        let m_row = transpose(&quat0.to_mat3_col_major());
+        let quat1 = to_quaternion(&m_row);
+        let d = (0..4).fold(0f64, |acc, i| acc + (quat0[i] - quat1[i]).powi(2));
+        let e = (0..4).fold(0f64, |acc, i| acc + (quat0[i] + quat1[i]).powi(2));
+        assert!(d.min(e) < 1.0e-20);
+    }
+    {
+        let m: [f64; 9] = [1.7, 3., 2.3, 4.5, 5., 1.5, 3.3, 2., 4.2];
+        let mi = try_inverse(&m).unwrap();
+        let diff = mult_mat_row_major(&m, &mi)
+            .sub(&from_identity())
+            .squared_norm();
+        assert!(diff < 1.0e-20);
+    }
+    {
+        let v0 = [0.1f64, -0.2, 0.3];
+        let v1 = to_vec3_axisangle_from_rot_mat(&from_axisangle_vec(&v0));
+        assert!(crate::vec3::norm(&crate::vec3::sub(&v0, &v1)) < 1.0e-12);
+    }
+}
+
 // above: to method
 // ----------------------------
 
@@ -214,6 +489,71 @@ where
     ]
 }
 
+pub fn try_inverse<T>(b: &[T; 9]) -> Option<[T; 9]>
+where
+    T: num_traits::Float,
+{
+    // the inverse of the transpose is the transpose of the inverse
+    crate::mat3_col_major::try_inverse(b)
+}
+
+pub fn transform_homogeneous<Real>(transform: &[Real; 9], x: &[Real; 2]) -> Option<[Real; 2]>
+where
+    Real: num_traits::Float,
+{
+    let y2 = transform[6] * x[0] + transform[7] * x[1] + transform[8];
+    if y2.is_zero() {
+        return None;
+    }
+    //
+    let y0 = transform[0] * x[0] + transform[1] * x[1] + transform[2];
+    let y1 = transform[3] * x[0] + transform[4] * x[1] + transform[5];
+    Some([y0 / y2, y1 / y2])
+}
+
+pub fn transform_direction<Real>(transform: &[Real; 9], x: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [
+        transform[0] * x[0] + transform[1] * x[1],
+        transform[3] * x[0] + transform[4] * x[1],
+    ]
+}
+
+pub fn add_in_place_scaled_outer_product<T>(m: &mut [T; 9], s: T, a: &[T; 3], b: &[T; 3])
+where
+    T: num_traits::Float,
+{
+    crate::mat3_col_major::add_in_place_scaled_outer_product(m, s, b, a);
+}
+
+/// Add three matrices
+pub fn add_three<T>(a: &[T; 9], b: &[T; 9], c: &[T; 9]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    crate::mat3_col_major::add_three(a, b, c)
+}
+
+/// Return 3x3 rotation matrix as a row major storage.
+/// That rotation matrix rotate `v0: &[T;3]` to `v1: &[T;3]`.
+pub fn minimum_rotation_matrix<T>(v0: &[T; 3], v1: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::minimum_rotation_matrix(v0, v1))
+}
+
+/// # Argument
+/// * `n` - world 3D vector that corresponds local z (no need to be unit vector)
+pub fn transform_lcl2world_given_local_z<T>(n: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    transpose(&crate::mat3_col_major::transform_lcl2world_given_local_z(n))
+}
+
 // -------------------------------------------
 // below: SVD related functions
 
@@ -446,6 +786,113 @@ fn test_svd_differential() {
     }
 }
 
+/// gradient and hessian of the singular values with respect to the matrix
+/// * Returns `(diff_s, diff_diff_s)`
+///   - `diff_s[i*3+j]` : differentiation of the singular values w.r.t. the `(i,j)` entry
+///   - `diff_diff_s[(i*3+j)*9+(k*3+l)]` : differentiation of `diff_s[i*3+j]` w.r.t. the `(k,l)` entry
+#[allow(clippy::type_complexity)]
+pub fn gradient_and_hessian_of_svd_scale<Real>(
+    u: &[Real; 9],
+    s: &[Real; 3],
+    v: &[Real; 9],
+) -> ([[Real; 3]; 9], [[Real; 3]; 81])
+where
+    Real: num_traits::Float,
+{
+    // the same as the column major version except for the order of the entries
+    let (ds_cm, dds_cm) =
+        crate::mat3_col_major::gradient_and_hessian_of_svd_scale(&transpose(u), s, &transpose(v));
+    let mut ds = [[Real::zero(); 3]; 9];
+    let mut dds = [[Real::zero(); 3]; 81];
+    for (i, j) in itertools::iproduct!(0..3, 0..3) {
+        ds[i * 3 + j] = ds_cm[i + 3 * j];
+        for (k, l) in itertools::iproduct!(0..3, 0..3) {
+            dds[(i * 3 + j) * 9 + (k * 3 + l)] = dds_cm[(i + 3 * j) * 9 + (k + 3 * l)];
+        }
+    }
+    (ds, dds)
+}
+
+#[test]
+fn test_gradient_and_hessian_of_svd_scale() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    let eps = 1.0e-4;
+    for _iter in 0..100 {
+        let m0: [f64; 9] = std::array::from_fn(|_| rng.random::<f64>());
+        let mode = || crate::mat3_sym::EigenDecompositionModes::JacobiNumIter(100);
+        let (u0, s0, v0) = svd(&m0, mode()).unwrap();
+        let (ds0, dds) = gradient_and_hessian_of_svd_scale(&u0, &s0, &v0);
+        for (k, l) in itertools::iproduct!(0..3, 0..3) {
+            let mut m1 = m0;
+            m1[k * 3 + l] += eps;
+            let (u1, s1, v1) = svd(&m1, mode()).unwrap();
+            // the gradient of the singular values
+            let ds_num = s1.sub(&s0).scale(1. / eps);
+            assert!(ds_num.sub(&ds0[k * 3 + l]).norm() < 1.0e-3);
+            // the hessian of the singular values
+            let (ds1, _dds) = gradient_and_hessian_of_svd_scale(&u1, &s1, &v1);
+            for (i, j) in itertools::iproduct!(0..3, 0..3) {
+                let dds_ana = &dds[(i * 3 + j) * 9 + (k * 3 + l)];
+                let dds_num = ds1[i * 3 + j].sub(&ds0[i * 3 + j]).scale(1. / eps);
+                assert!(dds_ana.sub(&dds_num).norm() < 6.0e-3 * (1.0 + dds_ana.norm()));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parity_with_col_major_for_image_and_columns() {
+    use crate::mat3_col_major as cm;
+    let img_shape = (300, 200);
+    assert_eq!(
+        from_transform_ndc2pix(img_shape),
+        transpose(&cm::from_transform_ndc2pix(img_shape))
+    );
+    assert_eq!(
+        from_transform_unit2pix(img_shape),
+        transpose(&cm::from_transform_unit2pix(img_shape))
+    );
+    let aabb = [-1., 0., 2., 0.5];
+    let m = from_transform_world2pix_ortho_preserve_asp(&img_shape, &aabb);
+    let p = transform_homogeneous(&m, &[0.5, 0.25]).unwrap();
+    assert!((p[0] - 150.).abs() < 1.0e-4 && (p[1] - 100.).abs() < 1.0e-4);
+    let m: [f32; 9] = std::array::from_fn(|i| i as f32);
+    let m_cm = transpose(&m);
+    assert_eq!(
+        to_mat2x3_col_major_xy(&m),
+        cm::to_mat2x3_col_major_xy(&m_cm)
+    );
+    let order = cm::EulerOrder::Intrinsic(cm::EulerAxes::ZXZ);
+    let r = from_euler(order, 0.1f64, 0.4, 1.3);
+    assert_eq!(r, transpose(&cm::from_euler(order, 0.1, 0.4, 1.3)));
+    let angles = to_euler(&r, order);
+    assert!(crate::vec3::distance(&angles, &[0.1, 0.4, 1.3]) < 1.0e-10);
+    let r1 = crate::mat3_col_major::scale(&r, 1.01);
+    assert_eq!(
+        orthonormalize_rotation(&r1),
+        transpose(&cm::orthonormalize_rotation(&transpose(&r1)))
+    );
+    assert_eq!(
+        eigenvalues_real(&r).1,
+        cm::eigenvalues_real(&transpose(&r)).1
+    );
+    {
+        use rand::SeedableRng;
+        let r0: [f64; 9] =
+            sample_uniform_rotation(&mut rand_chacha::ChaChaRng::seed_from_u64(0u64));
+        let r1: [f64; 9] =
+            cm::sample_uniform_rotation(&mut rand_chacha::ChaChaRng::seed_from_u64(0u64));
+        assert_eq!(r0, transpose(&r1));
+    }
+    for i in 0..3 {
+        assert_eq!(to_vec3_column(&m, i), cm::to_vec3_column(&m_cm, i));
+        assert_eq!(to_vec3_row(&m, i), to_vec3_column(&m_cm, i));
+    }
+}
+
 /// when SVD of 3x3 matrix a is U*S*V^T, compute U*V^T
 /// determinant of the result is one
 pub fn rotational_component<T>(a: &[T; 9]) -> [T; 9]
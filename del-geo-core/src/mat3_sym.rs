@@ -29,6 +29,94 @@ where
     ]
 }
 
+/// symmetric matrix `s * a * a^t`
+pub fn from_scaled_outer_product<Real>(s: Real, a: &[Real; 3]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    [
+        s * a[0] * a[0],
+        s * a[1] * a[1],
+        s * a[2] * a[2],
+        s * a[1] * a[2],
+        s * a[2] * a[0],
+        s * a[0] * a[1],
+    ]
+}
+
+/// `m += s * a * a^t`
+pub fn add_in_place_scaled_outer_product<Real>(m: &mut [Real; 6], s: Real, a: &[Real; 3])
+where
+    Real: num_traits::Float,
+{
+    m[0] = m[0] + s * a[0] * a[0];
+    m[1] = m[1] + s * a[1] * a[1];
+    m[2] = m[2] + s * a[2] * a[2];
+    m[3] = m[3] + s * a[1] * a[2];
+    m[4] = m[4] + s * a[2] * a[0];
+    m[5] = m[5] + s * a[0] * a[1];
+}
+
+/// `m += sum_i w_i * a_i * a_i^t`
+/// (e.g., covariance matrix or structure tensor)
+/// * `vecs` - the vectors `a_i`
+/// * `weights` - the weights `w_i`. If `None`, all the weights are one.
+pub fn add_in_place_weighted_outer_products<Real>(
+    m: &mut [Real; 6],
+    vecs: &[[Real; 3]],
+    weights: Option<&[Real]>,
+) where
+    Real: num_traits::Float,
+{
+    match weights {
+        Some(weights) => {
+            assert_eq!(vecs.len(), weights.len());
+            for (a, &w) in vecs.iter().zip(weights.iter()) {
+                add_in_place_scaled_outer_product(m, w, a);
+            }
+        }
+        None => {
+            for a in vecs.iter() {
+                add_in_place_scaled_outer_product(m, Real::one(), a);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_add_in_place_weighted_outer_products() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let vecs: Vec<[f64; 3]> = (0..10)
+        .map(|_| std::array::from_fn(|_| rng.random_range(-1f64..1f64)))
+        .collect();
+    let weights: Vec<f64> = (0..10).map(|_| rng.random_range(0f64..1f64)).collect();
+    let mut sm = [0f64; 6];
+    add_in_place_weighted_outer_products(&mut sm, &vecs, Some(&weights));
+    let mut m = [0f64; 9];
+    for (a, &w) in vecs.iter().zip(weights.iter()) {
+        crate::mat3_col_major::add_in_place_scaled_outer_product(&mut m, w, a, a);
+    }
+    let m0 = from_mat3_by_symmetrization(&m);
+    assert!(
+        m0.iter()
+            .zip(sm.iter())
+            .all(|(a, b)| (a - b).abs() < 1.0e-12)
+    );
+    //
+    let mut sm = from_scaled_outer_product(2.0, &vecs[0]);
+    add_in_place_weighted_outer_products(&mut sm, &vecs[1..2], None);
+    let m0 = crate::mat3_col_major::from_scaled_outer_product(2.0, &vecs[0], &vecs[0]);
+    let m1 = crate::mat3_col_major::from_scaled_outer_product(1.0, &vecs[1], &vecs[1]);
+    let m0 = from_mat3_by_symmetrization(&crate::mat3_col_major::add(&m0, &m1));
+    assert!(
+        m0.iter()
+            .zip(sm.iter())
+            .all(|(a, b)| (a - b).abs() < 1.0e-12)
+    );
+}
+
 /// Frobenius norm squared
 pub fn squared_norm<Real>(sm: &[Real; 6]) -> Real
 where
@@ -304,6 +392,117 @@ where
     }
 }
 
+/// solve the generalized eigenvalue problem `A v = λ B v` where `B` is symmetric positive definite
+///
+/// `B = L * L^t` is Cholesky factorized and the standard symmetric problem for
+/// `L^{-1} * A * L^{-t}` is solved with `num_iter` Jacobi iterations.
+///
+/// # Returns
+/// `(V, λ)` where `V` is a row-major matrix whose columns are eigenvectors
+/// normalized such that `V^t * B * V = I`. `None` if `B` is not positive definite.
+/// The eigenvalues are zero and `V = L^{-t}` if `A` is zero.
+pub fn generalized_eigen_decomp<Real>(
+    a: &[Real; 6],
+    b: &[Real; 6],
+    num_iter: usize,
+) -> Option<([Real; 9], [Real; 3])>
+where
+    Real: num_traits::Float,
+{
+    use crate::mat3_row_major::Mat3RowMajor;
+    let zero = Real::zero();
+    // Cholesky factorization B = L * L^t
+    let l00 = b[0];
+    if l00 <= zero {
+        return None;
+    }
+    let l00 = l00.sqrt();
+    let l10 = b[5] / l00;
+    let l20 = b[4] / l00;
+    let l11 = b[1] - l10 * l10;
+    if l11 <= zero {
+        return None;
+    }
+    let l11 = l11.sqrt();
+    let l21 = (b[3] - l20 * l10) / l11;
+    let l22 = b[2] - l20 * l20 - l21 * l21;
+    if l22 <= zero {
+        return None;
+    }
+    let l22 = l22.sqrt();
+    // inverse of the lower triangular matrix L
+    let i00 = Real::one() / l00;
+    let i11 = Real::one() / l11;
+    let i22 = Real::one() / l22;
+    let i10 = -l10 * i00 * i11;
+    let i21 = -l21 * i11 * i22;
+    let i20 = -(l20 * i00 + l21 * i10) * i22;
+    let li = [i00, zero, zero, i10, i11, zero, i20, i21, i22];
+    let c = li
+        .mult_mat_row_major(&to_mat3_row_major(a))
+        .mult_mat_row_major(&li.transpose());
+    let c = from_mat3_by_symmetrization(&c);
+    // normalize so that the small `A` is not rejected by the Jacobi method
+    let c_norm = squared_norm(&c).sqrt();
+    if c_norm == zero {
+        return Some((li.transpose(), [zero; 3]));
+    }
+    let (w, lambda) = eigen_decomposition_jacobi(&c.map(|x| x / c_norm), num_iter)?;
+    let v = li.transpose().mult_mat_row_major(&w);
+    Some((v, lambda.map(|x| x * c_norm)))
+}
+
+#[test]
+fn test_generalized_eigen_decomp() {
+    use crate::mat3_row_major::Mat3RowMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..1000 {
+        let a: [f64; 6] = std::array::from_fn(|_| rng.random_range(-3f64..3f64));
+        let b = {
+            // make positive definite matrix
+            let m: [f64; 9] = std::array::from_fn(|_| rng.random_range(-1f64..1f64));
+            let mtm = m.transpose().mult_mat_row_major(&m);
+            let mut b = from_mat3_by_symmetrization(&mtm);
+            b[0] += 0.1;
+            b[1] += 0.1;
+            b[2] += 0.1;
+            b
+        };
+        let (v, lambda) = generalized_eigen_decomp(&a, &b, 100).unwrap();
+        let a = to_mat3_row_major(&a);
+        let b = to_mat3_row_major(&b);
+        let vtbv = v.transpose().mult_mat_row_major(&b).mult_mat_row_major(&v);
+        let err = vtbv
+            .sub(&crate::mat3_row_major::from_identity())
+            .squared_norm();
+        assert!(err < 1.0e-16, "{err}");
+        let l = crate::mat3_row_major::from_diagonal(&lambda);
+        let av = a.mult_mat_row_major(&v);
+        let bvl = b.mult_mat_row_major(&v).mult_mat_row_major(&l);
+        let err = av.sub(&bvl).squared_norm();
+        assert!(err < 1.0e-14 * (1.0 + a.squared_norm()), "{err}");
+    }
+    assert!(generalized_eigen_decomp(&[1f64; 6], &[1., 1., -1., 0., 0., 0.], 10).is_none());
+    // zero or tiny `A`
+    let b = [2f64, 3., 4., 0.5, 0.1, 0.2];
+    let (v, lambda) = generalized_eigen_decomp(&[0.; 6], &b, 10).unwrap();
+    assert_eq!(lambda, [0.; 3]);
+    let vtbv = v
+        .transpose()
+        .mult_mat_row_major(&to_mat3_row_major(&b))
+        .mult_mat_row_major(&v);
+    assert!(
+        vtbv.sub(&crate::mat3_row_major::from_identity())
+            .squared_norm()
+            < 1.0e-20
+    );
+    let a = [1.0e-12, 2.0e-12, 3.0e-12, 0., 0., 0.];
+    let (_v, lambda) = generalized_eigen_decomp(&a, &[1., 1., 1., 0., 0., 0.], 10).unwrap();
+    assert!(crate::vec3::distance(&lambda, &[1.0e-12, 2.0e-12, 3.0e-12]) < 1.0e-24);
+}
+
 /*
        {
            let Some((_u, l_num)) = eigen_decomposition_jacobi(&sm, 20) else {
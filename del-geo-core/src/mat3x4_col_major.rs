@@ -1,3 +1,29 @@
+//! methods for 3x4 matrix (column major) used for the affine transformation in 3D.
+//! The first nine elements are the 3x3 linear part and the last three elements are the translation.
+//! This is the 4x4 affine matrix with the bottom row `[0,0,0,1]` omitted.
+
+pub fn from_identity<Real>() -> [Real; 12]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    [
+        one, zero, zero, zero, one, zero, zero, zero, one, zero, zero, zero,
+    ]
+}
+
+/// the affine transformation `x -> a * x + t`
+pub fn from_mat3_col_major_and_translation<Real>(a: &[Real; 9], t: &[Real; 3]) -> [Real; 12]
+where
+    Real: Copy,
+{
+    [
+        a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8], t[0], t[1], t[2],
+    ]
+}
+
+/// the bottom row of the input is ignored
 pub fn from_mat4_col_major<Real>(a: &[Real; 16]) -> [Real; 12]
 where
     Real: num_traits::Float,
@@ -7,6 +33,53 @@ where
     ]
 }
 
+// above: from methods
+// ---------------------------------
+
+pub fn to_mat4_col_major<Real>(a: &[Real; 12]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [
+        a[0],
+        a[1],
+        a[2],
+        zero,
+        a[3],
+        a[4],
+        a[5],
+        zero,
+        a[6],
+        a[7],
+        a[8],
+        zero,
+        a[9],
+        a[10],
+        a[11],
+        Real::one(),
+    ]
+}
+
+/// the 3x3 linear part
+pub fn to_mat3_col_major<Real>(a: &[Real; 12]) -> [Real; 9]
+where
+    Real: Copy,
+{
+    [a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8]]
+}
+
+pub fn to_vec3_translation<Real>(a: &[Real; 12]) -> [Real; 3]
+where
+    Real: Copy,
+{
+    [a[9], a[10], a[11]]
+}
+
+// above: to methods
+// ---------------------------------
+
+/// transform the point
 pub fn transform_affine<Real>(a: &[Real; 12], v: &[Real; 3]) -> [Real; 3]
 where
     Real: num_traits::Float,
@@ -17,3 +90,73 @@ where
         a[2] * v[0] + a[5] * v[1] + a[8] * v[2] + a[11],
     ]
 }
+
+/// transform the direction (i.e., the translation is not applied)
+pub fn transform_direction<Real>(a: &[Real; 12], v: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    [
+        a[0] * v[0] + a[3] * v[1] + a[6] * v[2],
+        a[1] * v[0] + a[4] * v[1] + a[7] * v[2],
+        a[2] * v[0] + a[5] * v[1] + a[8] * v[2],
+    ]
+}
+
+/// the transformation `a * b` that applies `b` first then `a`
+pub fn mult_mat<Real>(a: &[Real; 12], b: &[Real; 12]) -> [Real; 12]
+where
+    Real: num_traits::Float,
+{
+    let l = crate::mat3_col_major::mult_mat_col_major(&to_mat3_col_major(a), &to_mat3_col_major(b));
+    let t = transform_affine(a, &to_vec3_translation(b));
+    from_mat3_col_major_and_translation(&l, &t)
+}
+
+/// Returns `None` if the linear part is singular
+pub fn try_inverse<Real>(a: &[Real; 12]) -> Option<[Real; 12]>
+where
+    Real: num_traits::Float,
+{
+    let li = crate::mat3_col_major::try_inverse(&to_mat3_col_major(a))?;
+    let t = crate::mat3_col_major::mult_vec(&li, &to_vec3_translation(a));
+    Some(from_mat3_col_major_and_translation(
+        &li,
+        &[-t[0], -t[1], -t[2]],
+    ))
+}
+
+#[test]
+fn test_mat3x4() {
+    use crate::mat4_col_major::Mat4ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 12] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let b: [f64; 12] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let (ma, mb) = (to_mat4_col_major(&a), to_mat4_col_major(&b));
+        assert_eq!(from_mat4_col_major(&ma), a);
+        let p = [0.3, -0.5, 0.8];
+        {
+            let p0 = transform_affine(&a, &p);
+            let p1 = ma.transform_homogeneous(&p).unwrap();
+            assert!(crate::vec3::distance(&p0, &p1) < 1.0e-10);
+            let v0 = transform_direction(&a, &p);
+            let v1 = ma.transform_direction(&p);
+            assert!(crate::vec3::distance(&v0, &v1) < 1.0e-10);
+        }
+        {
+            let mab0 = to_mat4_col_major(&mult_mat(&a, &b));
+            let mab1 = ma.mult_mat(&mb);
+            assert!((0..16).all(|i| (mab0[i] - mab1[i]).abs() < 1.0e-10));
+        }
+        if let Some(ai) = try_inverse(&a) {
+            let e = mult_mat(&ai, &a);
+            let i = from_identity::<f64>();
+            assert!((0..12).all(|k| (e[k] - i[k]).abs() < 1.0e-6));
+        }
+    }
+    let a = from_mat3_col_major_and_translation(&[1., 2., 3., 2., 4., 6., 0., 0., 1.], &[0.; 3]);
+    assert!(try_inverse::<f64>(&a).is_none());
+}
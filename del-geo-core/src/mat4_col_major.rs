@@ -275,6 +275,86 @@ where
     ]
 }
 
+/// reflection across the plane `a*x + b*y + c*z + d = 0` given as `[a, b, c, d]`.
+/// The normal `[a, b, c]` does not need to be normalized.
+pub fn from_reflection_across_plane<Real>(plane: &[Real; 4]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    let n = [plane[0], plane[1], plane[2]];
+    let s = two / crate::vec3::squared_norm(&n);
+    let mut m = [zero; 16];
+    for i in 0..3 {
+        for j in 0..3 {
+            m[i + j * 4] = -s * n[i] * n[j];
+        }
+        m[i + i * 4] = m[i + i * 4] + one;
+        m[i + 12] = -s * plane[3] * n[i];
+    }
+    m[15] = one;
+    m
+}
+
+/// projection of the points onto the plane `[a, b, c, d]` along the rays from the light.
+/// The light is the homogeneous coordinate: `[x, y, z, 1]` for a point light and
+/// `[dx, dy, dz, 0]` for a directional light going toward `-[dx, dy, dz]`.
+pub fn from_planar_shadow_projection<Real>(plane: &[Real; 4], light: &[Real; 4]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let dot = plane[0] * light[0] + plane[1] * light[1] + plane[2] * light[2] + plane[3] * light[3];
+    std::array::from_fn(|k| {
+        let (i, j) = (k % 4, k / 4);
+        let d = if i == j { dot } else { Real::zero() };
+        d - light[i] * plane[j]
+    })
+}
+
+#[test]
+fn test_reflection_and_planar_shadow() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let plane: [f64; 4] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let p: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let height = |q: &[f64; 3]| plane[0] * q[0] + plane[1] * q[1] + plane[2] * q[2] + plane[3];
+        {
+            let m = from_reflection_across_plane(&plane);
+            let q = transform_homogeneous(&m, &p).unwrap();
+            assert!((height(&p) + height(&q)).abs() < 1.0e-10);
+            let mid = crate::vec3::scale(&crate::vec3::add(&p, &q), 0.5);
+            assert!(height(&mid).abs() < 1.0e-10);
+            let mm = mult_mat_col_major(&m, &m);
+            for i in 0..16 {
+                assert!((mm[i] - from_identity::<f64>()[i]).abs() < 1.0e-10);
+            }
+        }
+        {
+            // point light
+            let light = [p[0] + 0.5, p[1] + 1.0, p[2] - 0.3, 1.0];
+            let m = from_planar_shadow_projection(&plane, &light);
+            let q = transform_homogeneous(&m, &p).unwrap();
+            assert!(height(&q).abs() < 1.0e-8);
+            let l = [light[0], light[1], light[2]];
+            let c = crate::vec3::cross(&crate::vec3::sub(&q, &l), &crate::vec3::sub(&p, &l));
+            assert!(crate::vec3::norm(&c) < 1.0e-8 * (1. + crate::vec3::norm(&q)));
+        }
+        {
+            // directional light
+            let light = [0.5, 1.0, -0.3, 0.0];
+            let m = from_planar_shadow_projection(&plane, &light);
+            let q = transform_homogeneous(&m, &p).unwrap();
+            assert!(height(&q).abs() < 1.0e-8);
+            let c = crate::vec3::cross(&crate::vec3::sub(&q, &p), &[0.5, 1.0, -0.3]);
+            assert!(crate::vec3::norm(&c) < 1.0e-8 * (1. + crate::vec3::norm(&q)));
+        }
+    }
+}
+
 // above: from method (making 4x4 matrix)
 // ----------------------------------------
 
@@ -292,6 +372,94 @@ where
     [m[12], m[13], m[14]]
 }
 
+/// decompose an affine transformation `M = T * R * S` into the translation `T`,
+/// the rotation `R` as a quaternion (`[i,j,k,w]` storage), and the axis-aligned scale `S`.
+/// A negative determinant (reflection) is represented by negating the x-scale.
+///
+/// Returns `None` if the matrix is not affine (bottom row is not `[0,0,0,1]`),
+/// is degenerate (zero scale), or has a shear component.
+#[allow(clippy::type_complexity)]
+pub fn decompose_trs<T>(m: &[T; 16]) -> Option<([T; 3], [T; 4], [T; 3])>
+where
+    T: num_traits::Float + std::fmt::Debug,
+{
+    use crate::vec3::Vec3;
+    let zero = T::zero();
+    let one = T::one();
+    let tol = T::epsilon().sqrt();
+    if m[3] != zero || m[7] != zero || m[11] != zero || m[15] != one {
+        return None;
+    }
+    let translation = to_vec3_translation(m);
+    let c0 = [m[0], m[1], m[2]];
+    let c1 = [m[4], m[5], m[6]];
+    let c2 = [m[8], m[9], m[10]];
+    let mut scale = [c0.norm(), c1.norm(), c2.norm()];
+    if scale.iter().any(|&s| s <= T::epsilon()) {
+        return None;
+    }
+    if crate::mat3_col_major::determinant(&to_mat3_col_major_xyz(m)) < zero {
+        scale[0] = -scale[0];
+    }
+    let c0 = c0.scale(one / scale[0]);
+    let c1 = c1.scale(one / scale[1]);
+    let c2 = c2.scale(one / scale[2]);
+    // the columns of the rotation should be orthogonal unless there is a shear
+    if c0.dot(&c1).abs() > tol || c1.dot(&c2).abs() > tol || c2.dot(&c0).abs() > tol {
+        return None;
+    }
+    let r = crate::mat3_col_major::from_columns(&c0, &c1, &c2);
+    let quaternion = crate::mat3_col_major::to_quaternion(&r);
+    Some((translation, quaternion, scale))
+}
+
+#[test]
+fn test_decompose_trs() {
+    use crate::quaternion::Quaternion;
+    let t0 = [1.0f64, -2.0, 0.5];
+    let q0 = [0.3, -0.2, 0.5, 0.8].normalized();
+    for s0 in [[2.0, 0.5, 1.3], [-2.0, 0.5, 1.3], [1.0, 1.0, 1.0]] {
+        let m = mult_three_mats_col_major(
+            &from_translate(&t0),
+            &q0.to_mat4_col_major(),
+            &from_diagonal(s0[0], s0[1], s0[2], 1.0),
+        );
+        let (t1, q1, s1) = decompose_trs(&m).unwrap();
+        let m1 = mult_three_mats_col_major(
+            &from_translate(&t1),
+            &q1.to_mat4_col_major(),
+            &from_diagonal(s1[0], s1[1], s1[2], 1.0),
+        );
+        assert!(
+            m.iter()
+                .zip(m1.iter())
+                .all(|(a, b)| (a - b).abs() < 1.0e-10)
+        );
+        assert!(
+            t0.iter()
+                .zip(t1.iter())
+                .all(|(a, b)| (a - b).abs() < 1.0e-10)
+        );
+        assert!(
+            s0.iter()
+                .zip(s1.iter())
+                .all(|(a, b)| (a.abs() - b.abs()).abs() < 1.0e-10)
+        );
+    }
+    {
+        // shear is rejected
+        let mut m = from_translate(&t0);
+        m[4] = 0.3;
+        assert!(decompose_trs(&m).is_none());
+    }
+    {
+        // projective matrix is rejected
+        let mut m = from_identity::<f64>();
+        m[3] = 0.3;
+        assert!(decompose_trs(&m).is_none());
+    }
+}
+
 // above: to method
 // ----------------------------------------
 
@@ -383,6 +551,42 @@ where
     crate::matn_row_major::try_inverse::<Real, 4, 16>(b)
 }
 
+/// inverse of the affine matrix `[A|t; 0 0 0 1]` as `[A^{-1}| -A^{-1}*t; 0 0 0 1]`.
+/// This is faster than the general [`try_inverse`].
+/// Returns `None` if the bottom row is not `[0,0,0,1]` or `A` is singular.
+pub fn try_inverse_affine<Real>(b: &[Real; 16]) -> Option<[Real; 16]>
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    if b[3] != zero || b[7] != zero || b[11] != zero || b[15] != Real::one() {
+        return None;
+    }
+    let a = to_mat3_col_major_xyz(b);
+    let ai = crate::mat3_col_major::try_inverse(&a)?;
+    let t = crate::mat3_col_major::mult_vec(&ai, &to_vec3_translation(b));
+    let mut o = from_mat3_col_major_adding_w(&ai, Real::one());
+    o[12] = -t[0];
+    o[13] = -t[1];
+    o[14] = -t[2];
+    Some(o)
+}
+
+#[test]
+fn test_try_inverse_affine() {
+    let a: [f64; 16] = [
+        1., 3., 4., 0., 3., 5., 5., 0., 5., 7., 8., 0., 8., 4., 5., 1.,
+    ];
+    let ainv0 = try_inverse(&a).unwrap();
+    let ainv1 = try_inverse_affine(&a).unwrap();
+    for i in 0..16 {
+        assert!((ainv0[i] - ainv1[i]).abs() < 1.0e-10);
+    }
+    let mut b = a;
+    b[3] = 1.0;
+    assert!(try_inverse_affine(&b).is_none());
+}
+
 /// perspective transformation matrix (column major) compatible with blender
 /// * asp - aspect ratio (width / height)
 /// * lens - the focus distance (unit: mm) where the sensor size for longest edge is 18*2 mm.
@@ -444,6 +648,723 @@ where
     }
 }
 
+/// range of the depth in the normalized device coordinate (NDC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthRange {
+    /// `[-1, +1]` (OpenGL)
+    NegOneToOne,
+    /// `[0, +1]` (WebGPU, Vulkan, Direct3D, Metal)
+    ZeroToOne,
+}
+
+/// handedness of the view coordinate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    /// the camera looks at the -Z direction (OpenGL convention)
+    Right,
+    /// the camera looks at the +Z direction (Direct3D convention)
+    Left,
+}
+
+/// perspective projection matrix (column major) from the view coordinate to the clip coordinate
+/// * `fovy` - vertical field of view (radian)
+/// * `aspect` - aspect ratio (width / height)
+/// * `near` - distance to the near clipping plane (>0)
+/// * `far` - distance to the far clipping plane (>near)
+pub fn perspective_fov<Real>(
+    fovy: Real,
+    aspect: Real,
+    near: Real,
+    far: Real,
+    handedness: Handedness,
+    depth_range: DepthRange,
+) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    let f = one / (fovy / two).tan();
+    // depth in NDC is `(a * z_view + b) / w` where `w = -z_view` or `w = z_view`
+    let (a, b) = match depth_range {
+        DepthRange::NegOneToOne => ((far + near) / (far - near), two * far * near / (far - near)),
+        DepthRange::ZeroToOne => (far / (far - near), far * near / (far - near)),
+    };
+    let (a, w) = match handedness {
+        Handedness::Right => (-a, -one),
+        Handedness::Left => (a, one),
+    };
+    [
+        f / aspect,
+        zero,
+        zero,
+        zero,
+        zero,
+        f,
+        zero,
+        zero,
+        zero,
+        zero,
+        a,
+        w,
+        zero,
+        zero,
+        -b,
+        zero,
+    ]
+}
+
+/// perspective projection matrix (column major) with the reversed depth where
+/// the near plane is mapped to depth one and the far plane is mapped to depth zero.
+/// This distributes the floating point precision evenly along the depth.
+/// The depth range of the NDC is `[0, 1]`.
+/// * `far` - distance to the far clipping plane. `Real::infinity()` is allowed.
+pub fn perspective_reversed_z<Real>(
+    fovy: Real,
+    aspect: Real,
+    near: Real,
+    far: Real,
+    handedness: Handedness,
+) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let (a, b) = if far.is_infinite() {
+        (Real::zero(), near)
+    } else {
+        (near / (far - near), far * near / (far - near))
+    };
+    let mut m = perspective_fov(fovy, aspect, near, far, handedness, DepthRange::ZeroToOne);
+    m[10] = match handedness {
+        Handedness::Right => a,
+        Handedness::Left => -a,
+    };
+    m[14] = b;
+    m
+}
+
+/// perspective projection matrix (column major) where the far clipping plane is at infinity
+pub fn perspective_infinite_far<Real>(
+    fovy: Real,
+    aspect: Real,
+    near: Real,
+    handedness: Handedness,
+    depth_range: DepthRange,
+) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let mut m = perspective_fov(fovy, aspect, near, one, handedness, depth_range);
+    m[10] = match handedness {
+        Handedness::Right => -one,
+        Handedness::Left => one,
+    };
+    m[14] = match depth_range {
+        DepthRange::NegOneToOne => -(one + one) * near,
+        DepthRange::ZeroToOne => -near,
+    };
+    m
+}
+
+/// distance from the camera along the viewing direction from the depth in the NDC
+/// for the projection matrix from [`perspective_fov`]
+pub fn linearize_depth<Real>(depth: Real, near: Real, far: Real, depth_range: DepthRange) -> Real
+where
+    Real: num_traits::Float,
+{
+    let two = Real::one() + Real::one();
+    // depth = a - b / distance
+    let (a, b) = match depth_range {
+        DepthRange::NegOneToOne => ((far + near) / (far - near), two * far * near / (far - near)),
+        DepthRange::ZeroToOne => (far / (far - near), far * near / (far - near)),
+    };
+    b / (a - depth)
+}
+
+/// distance from the camera along the viewing direction from the depth in the NDC
+/// for the projection matrix from [`perspective_reversed_z`]
+pub fn linearize_depth_reversed_z<Real>(depth: Real, near: Real, far: Real) -> Real
+where
+    Real: num_traits::Float,
+{
+    if far.is_infinite() {
+        return near / depth;
+    }
+    // depth = -a + b / distance
+    let a = near / (far - near);
+    let b = far * near / (far - near);
+    b / (depth + a)
+}
+
+/// distance from the camera along the viewing direction from the depth in the NDC
+/// for the projection matrix from [`perspective_infinite_far`]
+pub fn linearize_depth_infinite_far<Real>(depth: Real, near: Real, depth_range: DepthRange) -> Real
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    match depth_range {
+        DepthRange::NegOneToOne => (one + one) * near / (one - depth),
+        DepthRange::ZeroToOne => near / (one - depth),
+    }
+}
+
+#[test]
+fn test_perspective_reversed_z_and_infinite_far() {
+    let (near, far) = (0.5f64, 10.0);
+    for (handedness, depth_range) in itertools::iproduct!(
+        [Handedness::Right, Handedness::Left],
+        [DepthRange::NegOneToOne, DepthRange::ZeroToOne]
+    ) {
+        let sign = match handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        };
+        let m0 = perspective_fov(1.0, 1.5, near, far, handedness, depth_range);
+        let m1 = perspective_reversed_z(1.0, 1.5, near, far, handedness);
+        let m2 = perspective_reversed_z(1.0, 1.5, near, f64::INFINITY, handedness);
+        let m3 = perspective_infinite_far(1.0, 1.5, near, handedness, depth_range);
+        {
+            let p = transform_homogeneous(&m1, &[0., 0., sign * near]).unwrap();
+            assert!((p[2] - 1.0).abs() < 1.0e-10);
+            let p = transform_homogeneous(&m1, &[0., 0., sign * far]).unwrap();
+            assert!(p[2].abs() < 1.0e-10);
+            let p = transform_homogeneous(&m2, &[0., 0., sign * near]).unwrap();
+            assert!((p[2] - 1.0).abs() < 1.0e-10);
+            let p = transform_homogeneous(&m3, &[0., 0., sign * 1.0e+10]).unwrap();
+            assert!((p[2] - 1.0).abs() < 1.0e-8);
+        }
+        for dist in [0.7, 1.3, 5.0, 9.9] {
+            let q = [0.1, -0.2, sign * dist];
+            let d0 = transform_homogeneous(&m0, &q).unwrap()[2];
+            assert!((linearize_depth(d0, near, far, depth_range) - dist).abs() < 1.0e-10);
+            let d1 = transform_homogeneous(&m1, &q).unwrap()[2];
+            assert!((linearize_depth_reversed_z(d1, near, far) - dist).abs() < 1.0e-10);
+            let d2 = transform_homogeneous(&m2, &q).unwrap()[2];
+            let dist2 = linearize_depth_reversed_z(d2, near, f64::INFINITY);
+            assert!((dist2 - dist).abs() < 1.0e-10);
+            let d3 = transform_homogeneous(&m3, &q).unwrap()[2];
+            let dist3 = linearize_depth_infinite_far(d3, near, depth_range);
+            assert!((dist3 - dist).abs() < 1.0e-10);
+        }
+    }
+}
+
+/// shift the projected image by the sub-pixel offset `jitter_px` (e.g., for the temporal
+/// anti-aliasing). The offset is in the pixel coordinate of [`from_transform_ndc2pix`]
+/// where the y-axis points downward. This works both for the perspective and the orthographic
+/// projections.
+pub fn with_pixel_jitter<Real>(
+    proj: &[Real; 16],
+    jitter_px: &[Real; 2],
+    img_shape: (usize, usize),
+) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let two = Real::one() + Real::one();
+    let dx = two * jitter_px[0] / Real::from(img_shape.0).unwrap();
+    let dy = -two * jitter_px[1] / Real::from(img_shape.1).unwrap();
+    // x_clip += dx * w_clip, y_clip += dy * w_clip
+    let mut m = *proj;
+    for j in 0..4 {
+        m[j * 4] = m[j * 4] + dx * proj[j * 4 + 3];
+        m[j * 4 + 1] = m[j * 4 + 1] + dy * proj[j * 4 + 3];
+    }
+    m
+}
+
+/// radical inverse of `index` in the `base` (the Halton sequence) in `[0, 1)`
+fn radical_inverse<Real>(mut index: usize, base: usize) -> Real
+where
+    Real: num_traits::Float,
+{
+    let inv_base = Real::one() / Real::from(base).unwrap();
+    let mut f = inv_base;
+    let mut r = Real::zero();
+    while index > 0 {
+        r = r + f * Real::from(index % base).unwrap();
+        index /= base;
+        f = f * inv_base;
+    }
+    r
+}
+
+/// `index`-th sub-pixel jitter in `[-0.5, 0.5)^2` from the Halton(2,3) sequence.
+/// The sequence starts from `index = 1` because the zeroth element is the corner `(-0.5, -0.5)`.
+pub fn halton_jitter_2_3<Real>(index: usize) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let half = Real::one() / (Real::one() + Real::one());
+    [
+        radical_inverse::<Real>(index, 2) - half,
+        radical_inverse::<Real>(index, 3) - half,
+    ]
+}
+
+#[test]
+fn test_with_pixel_jitter() {
+    let img_shape = (300usize, 200usize);
+    let ndc2pix = from_transform_ndc2pix(img_shape).map(|v| v as f64);
+    let projs = [
+        perspective_fov(
+            1.0,
+            1.5,
+            0.5,
+            20.0,
+            Handedness::Right,
+            DepthRange::ZeroToOne,
+        ),
+        orthographic(
+            -1.5,
+            1.5,
+            -1.,
+            1.,
+            0.5,
+            20.0,
+            Handedness::Right,
+            DepthRange::ZeroToOne,
+        ),
+    ];
+    for proj in projs {
+        for i in 1..10 {
+            let jitter = halton_jitter_2_3::<f64>(i);
+            assert!(jitter.iter().all(|&v| (-0.5..0.5).contains(&v)));
+            let proj1 = with_pixel_jitter(&proj, &jitter, img_shape);
+            let p = [0.3, -0.2, -4.0];
+            let q0 = transform_homogeneous(&ndc2pix, &transform_homogeneous(&proj, &p).unwrap());
+            let q1 = transform_homogeneous(&ndc2pix, &transform_homogeneous(&proj1, &p).unwrap());
+            let (q0, q1) = (q0.unwrap(), q1.unwrap());
+            assert!((q1[0] - q0[0] - jitter[0]).abs() < 1.0e-10);
+            assert!((q1[1] - q0[1] - jitter[1]).abs() < 1.0e-10);
+            assert!((q1[2] - q0[2]).abs() < 1.0e-10);
+        }
+    }
+    let h: [f64; 2] = halton_jitter_2_3(5);
+    assert!((h[0] - (5. / 8. - 0.5)).abs() < 1.0e-15);
+    assert!((h[1] - (7. / 9. - 0.5)).abs() < 1.0e-15);
+}
+
+/// parameters of the (possibly off-center) perspective projection.
+/// `left`, `right`, `bottom` and `top` are the extents of the view volume on the near plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerspectiveParams<Real> {
+    pub left: Real,
+    pub right: Real,
+    pub bottom: Real,
+    pub top: Real,
+    pub near: Real,
+    /// `Real::infinity()` if the far clipping plane is at infinity
+    pub far: Real,
+    pub handedness: Handedness,
+}
+
+impl<Real> PerspectiveParams<Real>
+where
+    Real: num_traits::Float,
+{
+    /// symmetric view volume, the same as [`perspective_fov`]
+    pub fn from_fov(
+        fovy: Real,
+        aspect: Real,
+        near: Real,
+        far: Real,
+        handedness: Handedness,
+    ) -> Self {
+        let two = Real::one() + Real::one();
+        let top = near * (fovy / two).tan();
+        let right = top * aspect;
+        Self {
+            left: -right,
+            right,
+            bottom: -top,
+            top,
+            near,
+            far,
+            handedness,
+        }
+    }
+
+    /// vertical field of view (radian)
+    pub fn fovy(&self) -> Real {
+        (self.top / self.near).atan() - (self.bottom / self.near).atan()
+    }
+
+    /// aspect ratio (width / height)
+    pub fn aspect(&self) -> Real {
+        (self.right - self.left) / (self.top - self.bottom)
+    }
+
+    /// perspective projection matrix (column major) from the view coordinate to the clip coordinate
+    pub fn to_mat4_col_major(&self, depth_range: DepthRange) -> [Real; 16] {
+        let zero = Real::zero();
+        let one = Real::one();
+        let two = one + one;
+        let (n, f) = (self.near, self.far);
+        // depth in NDC is `(w * a * z_view - b) / (w * z_view)`
+        let (a, b) = match (depth_range, f.is_infinite()) {
+            (DepthRange::NegOneToOne, false) => ((f + n) / (f - n), two * f * n / (f - n)),
+            (DepthRange::ZeroToOne, false) => (f / (f - n), f * n / (f - n)),
+            (DepthRange::NegOneToOne, true) => (one, two * n),
+            (DepthRange::ZeroToOne, true) => (one, n),
+        };
+        let w = match self.handedness {
+            Handedness::Right => -one,
+            Handedness::Left => one,
+        };
+        let (rl, tb) = (self.right - self.left, self.top - self.bottom);
+        let mut m = [zero; 16];
+        m[0] = two * n / rl;
+        m[5] = two * n / tb;
+        m[8] = -w * (self.right + self.left) / rl;
+        m[9] = -w * (self.top + self.bottom) / tb;
+        m[10] = w * a;
+        m[11] = w;
+        m[14] = -b;
+        m
+    }
+}
+
+/// recover the parameters of the perspective projection matrix, which is
+/// the inverse of [`PerspectiveParams::to_mat4_col_major`].
+/// The matrices from [`perspective_fov`] and [`perspective_infinite_far`] are supported.
+///
+/// Returns `None` if the matrix does not have the structure of the perspective projection,
+/// or the recovered clipping planes do not satisfy `0 < near < far`.
+pub fn decompose_perspective<Real>(
+    m: &[Real; 16],
+    depth_range: DepthRange,
+) -> Option<PerspectiveParams<Real>>
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    if [1, 2, 3, 4, 6, 7, 12, 13, 15].iter().any(|&i| m[i] != zero) || m[11] == zero {
+        return None;
+    }
+    // the projection is invariant under the scaling of the matrix
+    let m = m.map(|v| v / m[11].abs());
+    let (w, handedness) = if m[11] < zero {
+        (-one, Handedness::Right)
+    } else {
+        (one, Handedness::Left)
+    };
+    let (a, b) = (m[10] * w, -m[14]);
+    let (near, far) = match depth_range {
+        DepthRange::NegOneToOne => (b / (a + one), b / (a - one)),
+        DepthRange::ZeroToOne => (b / a, b / (a - one)),
+    };
+    let far = if a == one { Real::infinity() } else { far };
+    if !(near > zero && far > near) || m[0] == zero || m[5] == zero {
+        return None;
+    }
+    let rl = two * near / m[0];
+    let tb = two * near / m[5];
+    let (rpl, tpb) = (-w * m[8] * rl, -w * m[9] * tb);
+    Some(PerspectiveParams {
+        left: (rpl - rl) / two,
+        right: (rpl + rl) / two,
+        bottom: (tpb - tb) / two,
+        top: (tpb + tb) / two,
+        near,
+        far,
+        handedness,
+    })
+}
+
+#[test]
+fn test_decompose_perspective() {
+    let eq = |a: &[f64; 16], b: &[f64; 16]| (0..16).all(|i| (a[i] - b[i]).abs() < 1.0e-10);
+    for depth_range in [DepthRange::NegOneToOne, DepthRange::ZeroToOne] {
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let m0 = perspective_fov(0.8f64, 1.5, 0.3, 40.0, handedness, depth_range);
+            let p = decompose_perspective(&m0, depth_range).unwrap();
+            assert!((p.fovy() - 0.8).abs() < 1.0e-10);
+            assert!((p.aspect() - 1.5).abs() < 1.0e-10);
+            assert!((p.near - 0.3).abs() < 1.0e-10);
+            assert!((p.far - 40.0).abs() < 1.0e-8);
+            assert_eq!(p.handedness, handedness);
+            let p1 = PerspectiveParams::from_fov(0.8, 1.5, 0.3, 40.0, handedness);
+            assert!(eq(&m0, &p1.to_mat4_col_major(depth_range)));
+            // change the clipping planes
+            let p2 = PerspectiveParams {
+                near: 0.5,
+                far: 10.0,
+                ..p
+            };
+            let m2 = perspective_fov(0.8, 1.5, 0.5, 10.0, handedness, depth_range);
+            assert!(!eq(&m2, &p2.to_mat4_col_major(depth_range)));
+            let p3 = PerspectiveParams::from_fov(p.fovy(), p.aspect(), 0.5, 10.0, handedness);
+            assert!(eq(&m2, &p3.to_mat4_col_major(depth_range)));
+            // infinite far
+            let m4 = perspective_infinite_far(0.8f64, 1.5, 0.3, handedness, depth_range);
+            let p4 = decompose_perspective(&m4, depth_range).unwrap();
+            assert!(p4.far.is_infinite());
+            assert!(eq(&m4, &p4.to_mat4_col_major(depth_range)));
+            // off-center
+            let p5 = PerspectiveParams {
+                left: -0.1,
+                right: 0.3,
+                bottom: -0.05,
+                top: 0.2,
+                near: 0.2,
+                far: 30.0,
+                handedness,
+            };
+            let m5 = p5.to_mat4_col_major(depth_range);
+            let p6 = decompose_perspective(&m5, depth_range).unwrap();
+            assert!(eq(&m5, &p6.to_mat4_col_major(depth_range)));
+            assert!((p6.left + 0.1).abs() < 1.0e-10 && (p6.top - 0.2).abs() < 1.0e-10);
+            // the corner of the view volume on the near plane maps to the corner of the NDC
+            let z = match handedness {
+                Handedness::Right => -0.2,
+                Handedness::Left => 0.2,
+            };
+            let q = transform_homogeneous(&m5, &[0.3, -0.05, z]).unwrap();
+            assert!((q[0] - 1.).abs() < 1.0e-10 && (q[1] + 1.).abs() < 1.0e-10);
+        }
+    }
+    assert!(decompose_perspective(&from_identity::<f64>(), DepthRange::ZeroToOne).is_none());
+}
+
+/// replace the near plane of the projection matrix with the arbitrary plane in the view coordinate
+/// with the technique of Lengyel (2005), "Oblique View Frustum Depth Projection and Clipping".
+/// The far plane is moved so that the frustum still encloses the original one as tight as possible.
+/// This is typically used to clip the geometry behind the mirror or the water surface.
+/// * `plane_view_space` - `[a, b, c, d]` where `a*x + b*y + c*z + d >= 0` is kept. The camera
+///   (the origin of the view coordinate) must be on the negative side (i.e., `d < 0`)
+/// * `depth_range` - depth range of the normalized device coordinate of `proj`
+/// * Return `None` if `proj` is singular
+pub fn apply_oblique_clip_plane<Real>(
+    proj: &[Real; 16],
+    plane_view_space: &[Real; 4],
+    depth_range: DepthRange,
+) -> Option<[Real; 16]>
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let c = plane_view_space;
+    let inv_proj = try_inverse(proj)?;
+    // the plane in the clip coordinate is `inv_proj^T * c`
+    let c_clip = mult_vec(&transpose(&inv_proj), c);
+    let sgn = |v: Real| if v < Real::zero() { -one } else { one };
+    // the corner of the frustum opposite to the plane
+    let q = mult_vec(&inv_proj, &[sgn(c_clip[0]), sgn(c_clip[1]), one, one]);
+    let cq = c[0] * q[0] + c[1] * q[1] + c[2] * q[2] + c[3] * q[3];
+    let mut m = *proj;
+    match depth_range {
+        DepthRange::NegOneToOne => {
+            let s = (one + one) / cq;
+            for j in 0..4 {
+                m[2 + j * 4] = c[j] * s - proj[3 + j * 4];
+            }
+        }
+        DepthRange::ZeroToOne => {
+            for j in 0..4 {
+                m[2 + j * 4] = c[j] / cq;
+            }
+        }
+    }
+    Some(m)
+}
+
+#[test]
+fn test_apply_oblique_clip_plane() {
+    for depth_range in [DepthRange::NegOneToOne, DepthRange::ZeroToOne] {
+        let z_near = match depth_range {
+            DepthRange::NegOneToOne => -1f64,
+            DepthRange::ZeroToOne => 0f64,
+        };
+        let proj = perspective_fov(1.0, 1.5, 0.5, 20.0, Handedness::Right, depth_range);
+        // tilted plane in front of the camera, the camera is on the negative side
+        let n = crate::vec3::normalize(&[0.2f64, -0.3, -1.0]);
+        let plane = [n[0], n[1], n[2], -3.0];
+        let height = |p: &[f64; 3]| n[0] * p[0] + n[1] * p[1] + n[2] * p[2] + plane[3];
+        let m = apply_oblique_clip_plane(&proj, &plane, depth_range).unwrap();
+        for p in [[0.3, 0.1, -2.0], [0.3, 0.1, -4.0], [-1.0, 0.5, -8.0]] {
+            let ndc0 = transform_homogeneous(&proj, &p).unwrap();
+            let ndc1 = transform_homogeneous(&m, &p).unwrap();
+            assert!((ndc0[0] - ndc1[0]).abs() < 1.0e-10);
+            assert!((ndc0[1] - ndc1[1]).abs() < 1.0e-10);
+            assert_eq!(height(&p) > 0., ndc1[2] > z_near);
+            assert!(ndc1[2] < 1.);
+        }
+        {
+            // point on the plane is mapped to the near plane
+            let p = crate::vec3::scale(&n, 3.0);
+            let p = [
+                p[0] + 0.1,
+                p[1] + 0.2,
+                p[2] + (0.1 * n[0] + 0.2 * n[1]) / -n[2],
+            ];
+            assert!(height(&p).abs() < 1.0e-10);
+            let ndc1 = transform_homogeneous(&m, &p).unwrap();
+            assert!((ndc1[2] - z_near).abs() < 1.0e-10);
+        }
+    }
+    let plane = [0., 0., -1., -3.];
+    let res = apply_oblique_clip_plane(&[0f64; 16], &plane, DepthRange::ZeroToOne);
+    assert!(res.is_none());
+}
+
+/// orthographic projection matrix (column major) from the view coordinate to the clip coordinate
+/// that maps the box `[left, right] x [bottom, top] x [near, far]` (the depth is measured
+/// along the viewing direction) to the NDC
+#[allow(clippy::too_many_arguments)]
+pub fn orthographic<Real>(
+    left: Real,
+    right: Real,
+    bottom: Real,
+    top: Real,
+    near: Real,
+    far: Real,
+    handedness: Handedness,
+    depth_range: DepthRange,
+) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    let (a, b) = match depth_range {
+        DepthRange::NegOneToOne => (two / (far - near), -(far + near) / (far - near)),
+        DepthRange::ZeroToOne => (one / (far - near), -near / (far - near)),
+    };
+    let a = match handedness {
+        Handedness::Right => -a,
+        Handedness::Left => a,
+    };
+    [
+        two / (right - left),
+        zero,
+        zero,
+        zero,
+        zero,
+        two / (top - bottom),
+        zero,
+        zero,
+        zero,
+        zero,
+        a,
+        zero,
+        -(right + left) / (right - left),
+        -(top + bottom) / (top - bottom),
+        b,
+        one,
+    ]
+}
+
+/// view matrix (column major) for the right-handed coordinate where the camera looks at -Z
+pub fn look_at_rh<Real>(eye: &[Real; 3], target: &[Real; 3], up: &[Real; 3]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let f = target.sub(eye).normalize();
+    let s = f.cross(up).normalize();
+    let u = s.cross(&f);
+    from_view_basis(&s, &u, &f.scale(-Real::one()), eye)
+}
+
+/// view matrix (column major) for the left-handed coordinate where the camera looks at +Z
+pub fn look_at_lh<Real>(eye: &[Real; 3], target: &[Real; 3], up: &[Real; 3]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let f = target.sub(eye).normalize();
+    let s = up.cross(&f).normalize();
+    let u = f.cross(&s);
+    from_view_basis(&s, &u, &f, eye)
+}
+
+/// view matrix whose rows are the basis vectors `x`, `y`, `z` of the camera located at `eye`
+fn from_view_basis<Real>(x: &[Real; 3], y: &[Real; 3], z: &[Real; 3], eye: &[Real; 3]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let zero = Real::zero();
+    [
+        x[0],
+        y[0],
+        z[0],
+        zero,
+        x[1],
+        y[1],
+        z[1],
+        zero,
+        x[2],
+        y[2],
+        z[2],
+        zero,
+        -x.dot(eye),
+        -y.dot(eye),
+        -z.dot(eye),
+        Real::one(),
+    ]
+}
+
+#[test]
+fn test_projection_builders() {
+    let (near, far) = (0.5f64, 10.0);
+    for (handedness, depth_range) in itertools::iproduct!(
+        [Handedness::Right, Handedness::Left],
+        [DepthRange::NegOneToOne, DepthRange::ZeroToOne]
+    ) {
+        let sign = match handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        };
+        let depth_near = match depth_range {
+            DepthRange::NegOneToOne => -1.0,
+            DepthRange::ZeroToOne => 0.0,
+        };
+        let persp = perspective_fov(1.0, 1.5, near, far, handedness, depth_range);
+        let ortho = orthographic(-1.0, 2.0, -0.5, 1.5, near, far, handedness, depth_range);
+        for m in [persp, ortho] {
+            let p = transform_homogeneous(&m, &[0., 0., sign * near]).unwrap();
+            assert!((p[2] - depth_near).abs() < 1.0e-10);
+            let p = transform_homogeneous(&m, &[0., 0., sign * far]).unwrap();
+            assert!((p[2] - 1.0).abs() < 1.0e-10);
+        }
+        {
+            // the top edge of the frustum is mapped to y=1
+            let y = near * 0.5f64.tan();
+            let p = transform_homogeneous(&persp, &[0., y, sign * near]).unwrap();
+            assert!((p[1] - 1.0).abs() < 1.0e-10);
+        }
+        {
+            let p = transform_homogeneous(&ortho, &[2.0, 1.5, sign * near]).unwrap();
+            assert!((p[0] - 1.0).abs() < 1.0e-10 && (p[1] - 1.0).abs() < 1.0e-10);
+        }
+    }
+    {
+        let eye = [1.0f64, 2.0, 3.0];
+        let target = [-1.0, 0.5, 0.0];
+        let up = [0., 1., 0.];
+        let dist = crate::edge3::length(&eye, &target);
+        let v = look_at_rh(&eye, &target, &up);
+        let p = transform_homogeneous(&v, &eye).unwrap();
+        assert!(crate::vec3::norm(&p) < 1.0e-10);
+        let p = transform_homogeneous(&v, &target).unwrap();
+        assert!(crate::edge3::length(&p, &[0., 0., -dist]) < 1.0e-10);
+        let v = look_at_lh(&eye, &target, &up);
+        let p = transform_homogeneous(&v, &target).unwrap();
+        assert!(crate::edge3::length(&p, &[0., 0., dist]) < 1.0e-10);
+        // up vector stays upward
+        let p = transform_direction(&v, &up);
+        assert!(p[1] > 0.);
+    }
+}
+
 pub fn camera_external_blender<Real>(
     cam_location: &[Real; 3],
     cam_rot_x_deg: Real,
@@ -556,15 +1477,213 @@ pub fn ray_from_transform_ndc2world_and_pixel_coordinate(
     image_size: &(f32, f32),
     transform_ndc2world: &[f32; 16],
 ) -> ([f32; 3], [f32; 3]) {
-    let x0 = 2. * (pix_coord.0 + 0.5f32) / (image_size.0) - 1.;
-    let y0 = 1. - 2. * (pix_coord.1 + 0.5f32) / (image_size.1);
-    let p0 = transform_homogeneous(transform_ndc2world, &[x0, y0, 1.]).unwrap();
-    let p1 = transform_homogeneous(transform_ndc2world, &[x0, y0, -1.]).unwrap();
+    let img_shape = (image_size.0 as usize, image_size.1 as usize);
+    let pix = [pix_coord.0 + 0.5, pix_coord.1 + 0.5];
+    let p0 = unproject(&pix, 1., transform_ndc2world, img_shape).unwrap();
+    let p1 = unproject(&pix, -1., transform_ndc2world, img_shape).unwrap();
     let ray_org = p0;
     let ray_dir = crate::vec3::sub(&p1, &p0);
     (ray_org, ray_dir)
 }
 
+/// the world coordinate of the point at the pixel coordinate `pix` with the NDC depth `depth_ndc`.
+/// The pixel coordinate is continuous (use `i + 0.5` for the center of the `i`-th pixel)
+/// and the conversion between the pixel and the NDC follows [`crate::mat3_col_major::from_transform_ndc2pix`].
+/// * `inv_viewproj` - inverse of the view-projection matrix (i.e., NDC to world transformation)
+/// * Return `None` if the point is at the infinity (e.g., the far plane of [`perspective_infinite_far`])
+///   or the image is empty
+pub fn unproject<Real>(
+    pix: &[Real; 2],
+    depth_ndc: Real,
+    inv_viewproj: &[Real; 16],
+    img_shape: (usize, usize),
+) -> Option<[Real; 3]>
+where
+    Real: num_traits::Float,
+{
+    transform_homogeneous(inv_viewproj, &pix2ndc(pix, depth_ndc, img_shape)?)
+}
+
+/// pixel to NDC by the inverse of [`crate::mat3_col_major::from_transform_ndc2pix`].
+/// `None` if the image is empty
+fn pix2ndc<Real>(pix: &[Real; 2], depth_ndc: Real, img_shape: (usize, usize)) -> Option<[Real; 3]>
+where
+    Real: num_traits::Float,
+{
+    let ndc2pix =
+        crate::mat3_col_major::from_transform_ndc2pix(img_shape).map(|v| Real::from(v).unwrap());
+    let pix2ndc = crate::mat3_col_major::try_inverse(&ndc2pix)?;
+    let [x, y] = crate::mat3_col_major::transform_homogeneous(&pix2ndc, pix)?;
+    Some([x, y, depth_ndc])
+}
+
+/// the ray in the world coordinate that goes through the pixel coordinate `pix`.
+/// The ray starts from the near plane and the direction is normalized toward the far plane
+/// (the far plane can be at the infinity).
+/// * `inv_viewproj` - inverse of the view-projection matrix (i.e., NDC to world transformation)
+/// * `depth_range` - the NDC depth range where the near plane is at the lower end
+///
+/// return `(ray_org, ray_dir)`
+///
+/// # Panics
+/// if the width or the height of the image is zero
+pub fn ray_from_pixel<Real>(
+    pix: &[Real; 2],
+    inv_viewproj: &[Real; 16],
+    img_shape: (usize, usize),
+    depth_range: DepthRange,
+) -> ([Real; 3], [Real; 3])
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let depth_near = match depth_range {
+        DepthRange::NegOneToOne => -one,
+        DepthRange::ZeroToOne => Real::zero(),
+    };
+    let p0 = pix2ndc(pix, depth_near, img_shape).expect("the image is empty");
+    let p1 = pix2ndc(pix, one, img_shape).expect("the image is empty");
+    let h0 = mult_vec(inv_viewproj, &[p0[0], p0[1], p0[2], one]);
+    let h1 = mult_vec(inv_viewproj, &[p1[0], p1[1], p1[2], one]);
+    let ray_org = [h0[0] / h0[3], h0[1] / h0[3], h0[2] / h0[3]];
+    // (h1.xyz / h1.w - h0.xyz / h0.w) multiplied by h0.w * h1.w, valid also for h1.w == 0
+    let ray_dir: [Real; 3] = std::array::from_fn(|i| h1[i] * h0[3] - h0[i] * h1[3]);
+    let sign = if h1[3] == Real::zero() {
+        h0[3]
+    } else {
+        h0[3] * h1[3]
+    };
+    let ray_dir = if sign < Real::zero() {
+        crate::vec3::scale(&ray_dir, -one)
+    } else {
+        ray_dir
+    };
+    (ray_org, crate::vec3::normalize(&ray_dir))
+}
+
+#[test]
+fn test_unproject_and_ray_from_pixel() {
+    let img_shape = (300usize, 200usize);
+    let view = look_at_rh(&[1., 2., 5.], &[0., 0., 0.], &[0., 1., 0.]);
+    let eye = [1f64, 2., 5.];
+    for depth_range in [DepthRange::NegOneToOne, DepthRange::ZeroToOne] {
+        let projs = [
+            perspective_fov(1.0, 1.5, 0.5, 20.0, Handedness::Right, depth_range),
+            perspective_infinite_far(1.0, 1.5, 0.5, Handedness::Right, depth_range),
+        ];
+        for proj in projs {
+            let viewproj = mult_mat_col_major(&proj, &view);
+            let inv_viewproj = try_inverse(&viewproj).unwrap();
+            let ndc2pix = from_transform_ndc2pix(img_shape).map(|v| v as f64);
+            let p_world = [0.3, -0.2, 0.4];
+            let p_ndc = transform_homogeneous(&viewproj, &p_world).unwrap();
+            let p_pix = transform_homogeneous(&ndc2pix, &p_ndc).unwrap();
+            let pix = [p_pix[0], p_pix[1]];
+            let q_world = unproject(&pix, p_ndc[2], &inv_viewproj, img_shape).unwrap();
+            assert!(crate::vec3::distance(&p_world, &q_world) < 1.0e-8);
+            let (ray_org, ray_dir) = ray_from_pixel(&pix, &inv_viewproj, img_shape, depth_range);
+            // the ray goes through the point away from the eye
+            let d = crate::vec3::sub(&p_world, &ray_org);
+            assert!(crate::vec3::norm(&crate::vec3::cross(&d, &ray_dir)) < 1.0e-8);
+            assert!(crate::vec3::dot(&d, &ray_dir) > 0.);
+            let e = crate::vec3::sub(&ray_org, &eye);
+            assert!(crate::vec3::norm(&crate::vec3::cross(&e, &ray_dir)) < 1.0e-8);
+            assert!((crate::vec3::norm(&ray_dir) - 1.).abs() < 1.0e-10);
+        }
+        // the far plane of the infinite perspective is at the infinity
+        let proj = perspective_infinite_far(1.0, 1.5, 0.5, Handedness::Right, depth_range);
+        let inv_viewproj = try_inverse(&mult_mat_col_major(&proj, &view)).unwrap();
+        assert_eq!(unproject(&[150., 100.], 1., &inv_viewproj, img_shape), None);
+        assert_eq!(unproject(&[0., 0.], 0., &inv_viewproj, (0, 200)), None);
+    }
+    // the same as the legacy function taking the integer pixel coordinate
+    let proj = perspective_fov(
+        1.0f32,
+        1.5,
+        0.5,
+        20.0,
+        Handedness::Right,
+        DepthRange::NegOneToOne,
+    );
+    let view = look_at_rh(&[1., 2., 5.], &[0., 0., 0.], &[0., 1., 0.]);
+    let inv_viewproj = try_inverse(&mult_mat_col_major(&proj, &view)).unwrap();
+    let (org, dir) =
+        ray_from_transform_ndc2world_and_pixel_coordinate((3., 4.), &(30., 20.), &inv_viewproj);
+    let p0 = unproject(&[3.5f32, 4.5], 1., &inv_viewproj, (30, 20)).unwrap();
+    let p1 = unproject(&[3.5f32, 4.5], -1., &inv_viewproj, (30, 20)).unwrap();
+    assert!(crate::vec3::distance(&org, &p0) < 1.0e-5);
+    assert!(crate::vec3::distance(&dir, &crate::vec3::sub(&p1, &p0)) < 1.0e-5);
+}
+
+/// eight corners of the view frustum in the world coordinate.
+/// The first four corners are on the near plane and the last four corners are on the far plane.
+/// On each plane, the order is `(-1,-1)`, `(+1,-1)`, `(+1,+1)`, `(-1,+1)` in the NDC.
+/// The far plane needs to be finite.
+/// * `inv_viewproj` - inverse of the view-projection matrix (i.e., NDC to world transformation)
+/// * `depth_range` - the NDC depth range where the near plane is at the lower end
+pub fn frustum_corners_world<Real>(
+    inv_viewproj: &[Real; 16],
+    depth_range: DepthRange,
+) -> [[Real; 3]; 8]
+where
+    Real: num_traits::Float,
+{
+    let depth_near = match depth_range {
+        DepthRange::NegOneToOne => -Real::one(),
+        DepthRange::ZeroToOne => Real::zero(),
+    };
+    frustum_corners_world_sliced(inv_viewproj, depth_near, Real::one())
+}
+
+/// eight corners of the slice of the view frustum between the NDC depths
+/// `depth_ndc_near` and `depth_ndc_far` in the world coordinate
+/// (e.g., a cascade of the cascaded shadow map).
+/// The order of the corners is the same as [`frustum_corners_world`].
+/// Note that the NDC depth is not linear to the distance from the camera for the
+/// perspective projection. Convert the distance to the NDC depth before slicing.
+pub fn frustum_corners_world_sliced<Real>(
+    inv_viewproj: &[Real; 16],
+    depth_ndc_near: Real,
+    depth_ndc_far: Real,
+) -> [[Real; 3]; 8]
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    std::array::from_fn(|i| {
+        let z = if i < 4 { depth_ndc_near } else { depth_ndc_far };
+        let x = if i % 4 == 1 || i % 4 == 2 { one } else { -one };
+        let y = if i % 4 >= 2 { one } else { -one };
+        transform_homogeneous(inv_viewproj, &[x, y, z]).unwrap()
+    })
+}
+
+#[test]
+fn test_frustum_corners_world() {
+    let eye = [1f64, 2., 5.];
+    let view = look_at_rh(&eye, &[1., 2., 0.], &[0., 1., 0.]);
+    for depth_range in [DepthRange::NegOneToOne, DepthRange::ZeroToOne] {
+        let (near, far) = (0.5, 20.0);
+        let proj = perspective_fov(1.0, 1.5, near, far, Handedness::Right, depth_range);
+        let inv_viewproj = try_inverse(&mult_mat_col_major(&proj, &view)).unwrap();
+        let corners = frustum_corners_world(&inv_viewproj, depth_range);
+        let h = |d: f64| d * (0.5f64).tan();
+        for (i, c) in corners.iter().enumerate() {
+            let d = if i < 4 { near } else { far };
+            let x = if i % 4 == 1 || i % 4 == 2 { 1. } else { -1. };
+            let y = if i % 4 >= 2 { 1. } else { -1. };
+            let c0 = [eye[0] + x * h(d) * 1.5, eye[1] + y * h(d), eye[2] - d];
+            assert!(crate::vec3::distance(c, &c0) < 1.0e-8);
+        }
+        // slice between the view distances 2 and 5
+        let to_ndc = |d: f64| transform_homogeneous(&proj, &[0., 0., -d]).unwrap()[2];
+        let corners = frustum_corners_world_sliced(&inv_viewproj, to_ndc(2.0), to_ndc(5.0));
+        assert!((corners[0][2] - (eye[2] - 2.0)).abs() < 1.0e-8);
+        assert!((corners[7][2] - (eye[2] - 5.0)).abs() < 1.0e-8);
+        assert!((corners[6][0] - (eye[0] + h(5.) * 1.5)).abs() < 1.0e-8);
+    }
+}
+
 pub fn mult_three_mats_col_major<Real>(a: &[Real; 16], b: &[Real; 16], c: &[Real; 16]) -> [Real; 16]
 where
     Real: num_traits::Float,
@@ -0,0 +1,131 @@
+//! conversions between the arrays of this crate and the types of the `mint` crate.
+//! * `[T;2]`, `[T;3]` <-> `mint::Vector2`, `mint::Vector3`
+//! * `[T;9]`, `[T;16]` (column major) <-> `mint::ColumnMatrix3`, `mint::ColumnMatrix4`
+//! * `[T;4]` quaternion `[i,j,k,w]` <-> `mint::Quaternion`
+//!
+//! The `From` conversions between the raw arrays and the `mint` types are provided by `mint` itself
+//! with the same conventions, and the functions below are the thin wrappers to make the conventions explicit.
+//! The `From` conversions are implemented for the wrappers in [`crate::newtype`].
+
+use crate::newtype::{M3, M4, V2, V3};
+
+pub fn vec2_to_mint<T: Copy>(v: &[T; 2]) -> ::mint::Vector2<T> {
+    ::mint::Vector2::from(*v)
+}
+
+pub fn vec2_from_mint<T: Copy>(v: &::mint::Vector2<T>) -> [T; 2] {
+    (*v).into()
+}
+
+pub fn vec3_to_mint<T: Copy>(v: &[T; 3]) -> ::mint::Vector3<T> {
+    ::mint::Vector3::from(*v)
+}
+
+pub fn vec3_from_mint<T: Copy>(v: &::mint::Vector3<T>) -> [T; 3] {
+    (*v).into()
+}
+
+/// the column-major 3x3 matrix to the matrix with the columns `x`, `y`, and `z`
+pub fn mat3_col_major_to_mint<T: Copy>(m: &[T; 9]) -> ::mint::ColumnMatrix3<T> {
+    ::mint::ColumnMatrix3::from(*m)
+}
+
+pub fn mat3_col_major_from_mint<T: Copy>(m: &::mint::ColumnMatrix3<T>) -> [T; 9] {
+    (*m).into()
+}
+
+/// the column-major 4x4 matrix to the matrix with the columns `x`, `y`, `z`, and `w`
+pub fn mat4_col_major_to_mint<T: Copy>(m: &[T; 16]) -> ::mint::ColumnMatrix4<T> {
+    ::mint::ColumnMatrix4::from(*m)
+}
+
+pub fn mat4_col_major_from_mint<T: Copy>(m: &::mint::ColumnMatrix4<T>) -> [T; 16] {
+    (*m).into()
+}
+
+/// the quaternion `[i,j,k,w]` to the quaternion with the vector part `v` and the scalar part `s`
+pub fn quaternion_to_mint<T: Copy>(q: &[T; 4]) -> ::mint::Quaternion<T> {
+    ::mint::Quaternion::from(*q)
+}
+
+pub fn quaternion_from_mint<T: Copy>(q: &::mint::Quaternion<T>) -> [T; 4] {
+    (*q).into()
+}
+
+impl<T> From<V2<T>> for ::mint::Vector2<T> {
+    fn from(v: V2<T>) -> Self {
+        v.0.into()
+    }
+}
+
+impl<T> From<::mint::Vector2<T>> for V2<T> {
+    fn from(v: ::mint::Vector2<T>) -> Self {
+        V2(v.into())
+    }
+}
+
+impl<T> From<V3<T>> for ::mint::Vector3<T> {
+    fn from(v: V3<T>) -> Self {
+        v.0.into()
+    }
+}
+
+impl<T> From<::mint::Vector3<T>> for V3<T> {
+    fn from(v: ::mint::Vector3<T>) -> Self {
+        V3(v.into())
+    }
+}
+
+impl<T: Clone> From<M3<T>> for ::mint::ColumnMatrix3<T> {
+    fn from(m: M3<T>) -> Self {
+        m.0.into()
+    }
+}
+
+impl<T> From<::mint::ColumnMatrix3<T>> for M3<T> {
+    fn from(m: ::mint::ColumnMatrix3<T>) -> Self {
+        M3(m.into())
+    }
+}
+
+impl<T: Clone> From<M4<T>> for ::mint::ColumnMatrix4<T> {
+    fn from(m: M4<T>) -> Self {
+        m.0.into()
+    }
+}
+
+impl<T> From<::mint::ColumnMatrix4<T>> for M4<T> {
+    fn from(m: ::mint::ColumnMatrix4<T>) -> Self {
+        M4(m.into())
+    }
+}
+
+#[test]
+fn test_mint() {
+    let v = [1f64, 2., 3.];
+    let mv = vec3_to_mint(&v);
+    assert_eq!((mv.x, mv.y, mv.z), (1., 2., 3.));
+    assert_eq!(vec3_from_mint(&mv), v);
+    assert_eq!(V3::from(::mint::Vector3::from(V3(v))), V3(v));
+    let mv = vec2_to_mint(&[4f64, 5.]);
+    assert_eq!((mv.x, mv.y), (4., 5.));
+    assert_eq!(vec2_from_mint(&mv), [4., 5.]);
+    assert_eq!(V2::from(::mint::Vector2::from(V2([4., 5.]))), V2([4., 5.]));
+    // the first column is the image of the x-axis
+    let m: [f64; 9] = std::array::from_fn(|i| i as f64);
+    let mm = mat3_col_major_to_mint(&m);
+    let ex = crate::mat3_col_major::mult_vec(&m, &[1., 0., 0.]);
+    assert_eq!([mm.x.x, mm.x.y, mm.x.z], ex);
+    assert_eq!(mat3_col_major_from_mint(&mm), m);
+    assert_eq!(M3::from(::mint::ColumnMatrix3::from(M3(m))), M3(m));
+    let m: [f64; 16] = crate::mat4_col_major::from_translate(&[1., 2., 3.]);
+    let mm = mat4_col_major_to_mint(&m);
+    assert_eq!([mm.w.x, mm.w.y, mm.w.z, mm.w.w], [1., 2., 3., 1.]);
+    assert_eq!(mat4_col_major_from_mint(&mm), m);
+    assert_eq!(M4::from(::mint::ColumnMatrix4::from(M4(m))), M4(m));
+    // the real part is the last element
+    let q = crate::quaternion::from_axisangle(&[0.3f64, -0.2, 0.1]);
+    let mq = quaternion_to_mint(&q);
+    assert_eq!([mq.v.x, mq.v.y, mq.v.z, mq.s], q);
+    assert_eq!(quaternion_from_mint(&mq), q);
+}
@@ -0,0 +1,265 @@
+//! Morton code (Z-order curve) of the points quantized inside an AABB
+//! e.g., for the construction of the linear BVH (LBVH) and the spatial sorting of the point cloud.
+//! * 3D: 10 bits per axis in `u32` or 21 bits per axis in `u64`
+//! * 2D: 16 bits per axis in `u32` or 32 bits per axis in `u64`
+//!
+//! The bits of the x-coordinate come at the lowest position.
+
+/// spread the lower 10 bits so that there are two zeros between each bit
+pub fn split_by_3_u32(v: u32) -> u32 {
+    let mut x = v & 0x3ff;
+    x = (x | (x << 16)) & 0x030000ff;
+    x = (x | (x << 8)) & 0x0300f00f;
+    x = (x | (x << 4)) & 0x030c30c3;
+    x = (x | (x << 2)) & 0x09249249;
+    x
+}
+
+/// inverse of [`split_by_3_u32`]
+pub fn compact_by_3_u32(v: u32) -> u32 {
+    let mut x = v & 0x09249249;
+    x = (x | (x >> 2)) & 0x030c30c3;
+    x = (x | (x >> 4)) & 0x0300f00f;
+    x = (x | (x >> 8)) & 0x030000ff;
+    x = (x | (x >> 16)) & 0x3ff;
+    x
+}
+
+/// spread the lower 21 bits so that there are two zeros between each bit
+pub fn split_by_3_u64(v: u64) -> u64 {
+    let mut x = v & 0x1fffff;
+    x = (x | (x << 32)) & 0x001f00000000ffff;
+    x = (x | (x << 16)) & 0x001f0000ff0000ff;
+    x = (x | (x << 8)) & 0x100f00f00f00f00f;
+    x = (x | (x << 4)) & 0x10c30c30c30c30c3;
+    x = (x | (x << 2)) & 0x1249249249249249;
+    x
+}
+
+/// inverse of [`split_by_3_u64`]
+pub fn compact_by_3_u64(v: u64) -> u64 {
+    let mut x = v & 0x1249249249249249;
+    x = (x | (x >> 2)) & 0x10c30c30c30c30c3;
+    x = (x | (x >> 4)) & 0x100f00f00f00f00f;
+    x = (x | (x >> 8)) & 0x001f0000ff0000ff;
+    x = (x | (x >> 16)) & 0x001f00000000ffff;
+    x = (x | (x >> 32)) & 0x1fffff;
+    x
+}
+
+/// spread the lower 16 bits so that there is a zero between each bit
+pub fn split_by_2_u32(v: u32) -> u32 {
+    let mut x = v & 0xffff;
+    x = (x | (x << 8)) & 0x00ff00ff;
+    x = (x | (x << 4)) & 0x0f0f0f0f;
+    x = (x | (x << 2)) & 0x33333333;
+    x = (x | (x << 1)) & 0x55555555;
+    x
+}
+
+/// inverse of [`split_by_2_u32`]
+pub fn compact_by_2_u32(v: u32) -> u32 {
+    let mut x = v & 0x55555555;
+    x = (x | (x >> 1)) & 0x33333333;
+    x = (x | (x >> 2)) & 0x0f0f0f0f;
+    x = (x | (x >> 4)) & 0x00ff00ff;
+    x = (x | (x >> 8)) & 0xffff;
+    x
+}
+
+/// spread the lower 32 bits so that there is a zero between each bit
+pub fn split_by_2_u64(v: u64) -> u64 {
+    let mut x = v & 0xffffffff;
+    x = (x | (x << 16)) & 0x0000ffff0000ffff;
+    x = (x | (x << 8)) & 0x00ff00ff00ff00ff;
+    x = (x | (x << 4)) & 0x0f0f0f0f0f0f0f0f;
+    x = (x | (x << 2)) & 0x3333333333333333;
+    x = (x | (x << 1)) & 0x5555555555555555;
+    x
+}
+
+/// inverse of [`split_by_2_u64`]
+pub fn compact_by_2_u64(v: u64) -> u64 {
+    let mut x = v & 0x5555555555555555;
+    x = (x | (x >> 1)) & 0x3333333333333333;
+    x = (x | (x >> 2)) & 0x0f0f0f0f0f0f0f0f;
+    x = (x | (x >> 4)) & 0x00ff00ff00ff00ff;
+    x = (x | (x >> 8)) & 0x0000ffff0000ffff;
+    x = (x | (x >> 16)) & 0xffffffff;
+    x
+}
+
+// ---------------------------------
+// below: quantization against the AABB
+
+/// the integer coordinate in `[0, 2^num_bits)` of the point in the AABB.
+/// The points outside the AABB are clamped.
+fn quantize<T, const NDIM: usize, const SIZE_AABB: usize>(
+    p: &[T; NDIM],
+    aabb: &[T; SIZE_AABB],
+    num_bits: u32,
+) -> [u64; NDIM]
+where
+    T: num_traits::Float + num_traits::AsPrimitive<u64>,
+{
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let n = T::from(1u64 << num_bits).unwrap();
+    let max = (1u64 << num_bits) - 1;
+    std::array::from_fn(|i| {
+        let t = (p[i] - aabb[i]) / (aabb[i + NDIM] - aabb[i]);
+        let q: u64 = (t * n).floor().max(T::zero()).as_();
+        q.min(max)
+    })
+}
+
+/// center of the cell of the integer coordinate
+fn dequantize<T, const NDIM: usize, const SIZE_AABB: usize>(
+    q: &[u64; NDIM],
+    aabb: &[T; SIZE_AABB],
+    num_bits: u32,
+) -> [T; NDIM]
+where
+    T: num_traits::Float + 'static,
+    u64: num_traits::AsPrimitive<T>,
+{
+    use num_traits::AsPrimitive;
+    assert_eq!(NDIM * 2, SIZE_AABB);
+    let n: T = (1u64 << num_bits).as_();
+    let half = T::one() / (T::one() + T::one());
+    std::array::from_fn(|i| {
+        let t = (q[i].as_() + half) / n;
+        aabb[i] + t * (aabb[i + NDIM] - aabb[i])
+    })
+}
+
+/// 30-bit Morton code of the 3D point with 10 bits per axis
+pub fn encode3<T>(p: &[T; 3], aabb: &[T; 6]) -> u32
+where
+    T: num_traits::Float + num_traits::AsPrimitive<u64>,
+{
+    let q = quantize(p, aabb, 10);
+    split_by_3_u32(q[0] as u32)
+        | (split_by_3_u32(q[1] as u32) << 1)
+        | (split_by_3_u32(q[2] as u32) << 2)
+}
+
+/// center of the cell of the code computed by [`encode3`]
+pub fn decode3<T>(code: u32, aabb: &[T; 6]) -> [T; 3]
+where
+    T: num_traits::Float + 'static,
+    u64: num_traits::AsPrimitive<T>,
+{
+    let q = [0, 1, 2].map(|i| compact_by_3_u32(code >> i) as u64);
+    dequantize(&q, aabb, 10)
+}
+
+/// 63-bit Morton code of the 3D point with 21 bits per axis
+pub fn encode3_u64<T>(p: &[T; 3], aabb: &[T; 6]) -> u64
+where
+    T: num_traits::Float + num_traits::AsPrimitive<u64>,
+{
+    let q = quantize(p, aabb, 21);
+    split_by_3_u64(q[0]) | (split_by_3_u64(q[1]) << 1) | (split_by_3_u64(q[2]) << 2)
+}
+
+/// center of the cell of the code computed by [`encode3_u64`]
+pub fn decode3_u64<T>(code: u64, aabb: &[T; 6]) -> [T; 3]
+where
+    T: num_traits::Float + 'static,
+    u64: num_traits::AsPrimitive<T>,
+{
+    let q = [0, 1, 2].map(|i| compact_by_3_u64(code >> i));
+    dequantize(&q, aabb, 21)
+}
+
+/// 32-bit Morton code of the 2D point with 16 bits per axis
+pub fn encode2<T>(p: &[T; 2], aabb: &[T; 4]) -> u32
+where
+    T: num_traits::Float + num_traits::AsPrimitive<u64>,
+{
+    let q = quantize(p, aabb, 16);
+    split_by_2_u32(q[0] as u32) | (split_by_2_u32(q[1] as u32) << 1)
+}
+
+/// center of the cell of the code computed by [`encode2`]
+pub fn decode2<T>(code: u32, aabb: &[T; 4]) -> [T; 2]
+where
+    T: num_traits::Float + 'static,
+    u64: num_traits::AsPrimitive<T>,
+{
+    let q = [0, 1].map(|i| compact_by_2_u32(code >> i) as u64);
+    dequantize(&q, aabb, 16)
+}
+
+/// 64-bit Morton code of the 2D point with 32 bits per axis
+pub fn encode2_u64<T>(p: &[T; 2], aabb: &[T; 4]) -> u64
+where
+    T: num_traits::Float + num_traits::AsPrimitive<u64>,
+{
+    let q = quantize(p, aabb, 32);
+    split_by_2_u64(q[0]) | (split_by_2_u64(q[1]) << 1)
+}
+
+/// center of the cell of the code computed by [`encode2_u64`]
+pub fn decode2_u64<T>(code: u64, aabb: &[T; 4]) -> [T; 2]
+where
+    T: num_traits::Float + 'static,
+    u64: num_traits::AsPrimitive<T>,
+{
+    let q = [0, 1].map(|i| compact_by_2_u64(code >> i));
+    dequantize(&q, aabb, 32)
+}
+
+#[test]
+fn test_split_compact() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    assert_eq!(split_by_3_u32(0b1011), 0b001_000_001_001);
+    assert_eq!(split_by_2_u32(0b1011), 0b01_00_01_01);
+    assert_eq!(split_by_3_u32(0x3ff), 0x09249249);
+    assert_eq!(split_by_3_u64(0x1fffff), 0x1249249249249249);
+    for _iter in 0..1000 {
+        let v = rng.random::<u64>();
+        assert_eq!(compact_by_3_u32(split_by_3_u32(v as u32)), v as u32 & 0x3ff);
+        assert_eq!(compact_by_3_u64(split_by_3_u64(v)), v & 0x1fffff);
+        assert_eq!(
+            compact_by_2_u32(split_by_2_u32(v as u32)),
+            v as u32 & 0xffff
+        );
+        assert_eq!(compact_by_2_u64(split_by_2_u64(v)), v & 0xffffffff);
+    }
+}
+
+#[test]
+fn test_encode_decode() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb3 = [-1.0f64, 0.0, 2.0, 3.0, 0.5, 4.0];
+    let aabb2 = [-1.0f64, 0.0, 3.0, 0.5];
+    for _iter in 0..100 {
+        let p: [f64; 3] =
+            std::array::from_fn(|i| aabb3[i] + rng.random::<f64>() * (aabb3[i + 3] - aabb3[i]));
+        let q = decode3(encode3(&p, &aabb3), &aabb3);
+        assert!((0..3).all(|i| (p[i] - q[i]).abs() <= (aabb3[i + 3] - aabb3[i]) / 2048.));
+        let q = decode3_u64(encode3_u64(&p, &aabb3), &aabb3);
+        assert!(crate::vec3::distance(&p, &q) < 1.0e-5);
+        let p = [p[0], p[1]];
+        let q = decode2(encode2(&p, &aabb2), &aabb2);
+        assert!(crate::vec2::length(&crate::vec2::sub(&p, &q)) < 1.0e-4);
+        let q = decode2_u64(encode2_u64(&p, &aabb2), &aabb2);
+        assert!(crate::vec2::length(&crate::vec2::sub(&p, &q)) < 1.0e-9);
+    }
+    // corners and the points outside are clamped
+    assert_eq!(encode3(&[-1.0, 0.0, 2.0], &aabb3), 0);
+    assert_eq!(encode3(&[3.0, 0.5, 4.0], &aabb3), (1 << 30) - 1);
+    assert_eq!(encode3(&[5.0, 1.0, 9.0], &aabb3), (1 << 30) - 1);
+    assert_eq!(encode3_u64(&[3.0, 0.5, 4.0], &aabb3), (1 << 63) - 1);
+    assert_eq!(encode2(&[-5.0, -1.0], &aabb2), 0);
+    assert_eq!(encode2_u64(&[3.0, 0.5], &aabb2), u64::MAX);
+    // the order along the Z-curve
+    let aabb = [0f32, 0., 1., 1.];
+    let c = [[0.1, 0.1], [0.9, 0.1], [0.1, 0.9], [0.9, 0.9]].map(|p| encode2(&p, &aabb));
+    assert!(c[0] < c[1] && c[1] < c[2] && c[2] < c[3]);
+}
@@ -0,0 +1,222 @@
+//! newtype wrappers of the arrays with the operator overloading
+//! so that long formulas can be written as `a + (b - c) * 0.5`.
+//! The wrappers dereference to the arrays, so the free functions and the traits
+//! of the other modules can be applied to them directly (e.g., `a.dot(&b)`).
+//! The matrices are column major.
+
+/// 2D vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct V2<T>(pub [T; 2]);
+
+/// 3D vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct V3<T>(pub [T; 3]);
+
+/// 3x3 matrix (column major)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct M3<T>(pub [T; 9]);
+
+/// 4x4 matrix (column major)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct M4<T>(pub [T; 16]);
+
+/// element-wise operators, indexing and conversions shared by the wrappers
+macro_rules! impl_newtype {
+    ($name:ident, $n:literal) => {
+        // SAFETY: transparent wrapper of an array of `Pod`
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for $name<T> {}
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $name<T> {}
+
+        impl<T> std::ops::Deref for $name<T> {
+            type Target = [T; $n];
+            fn deref(&self) -> &[T; $n] {
+                &self.0
+            }
+        }
+
+        impl<T> std::ops::DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut [T; $n] {
+                &mut self.0
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from(v: [T; $n]) -> Self {
+                Self(v)
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $n] {
+            fn from(v: $name<T>) -> Self {
+                v.0
+            }
+        }
+
+        impl<T> std::ops::Index<usize> for $name<T> {
+            type Output = T;
+            fn index(&self, i: usize) -> &T {
+                &self.0[i]
+            }
+        }
+
+        impl<T> std::ops::IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, i: usize) -> &mut T {
+                &mut self.0[i]
+            }
+        }
+
+        impl<T> std::ops::Add for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+            }
+        }
+
+        impl<T> std::ops::Sub for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+            }
+        }
+
+        impl<T> std::ops::Neg for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(self.0.map(|v| -v))
+            }
+        }
+
+        /// multiplication by the scalar
+        impl<T> std::ops::Mul<T> for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            type Output = Self;
+            fn mul(self, s: T) -> Self {
+                Self(self.0.map(|v| v * s))
+            }
+        }
+
+        /// division by the scalar
+        impl<T> std::ops::Div<T> for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            type Output = Self;
+            fn div(self, s: T) -> Self {
+                Self(self.0.map(|v| v / s))
+            }
+        }
+
+        impl<T> std::ops::AddAssign for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T> std::ops::SubAssign for $name<T>
+        where
+            T: num_traits::Float,
+        {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_newtype!(V2, 2);
+impl_newtype!(V3, 3);
+impl_newtype!(M3, 9);
+impl_newtype!(M4, 16);
+
+/// matrix-matrix product
+impl<T> std::ops::Mul for M3<T>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self(crate::mat3_col_major::mult_mat_col_major(&self.0, &rhs.0))
+    }
+}
+
+/// matrix-vector product
+impl<T> std::ops::Mul<V3<T>> for M3<T>
+where
+    T: num_traits::Float,
+{
+    type Output = V3<T>;
+    fn mul(self, rhs: V3<T>) -> V3<T> {
+        V3(crate::mat3_col_major::mult_vec(&self.0, &rhs.0))
+    }
+}
+
+/// matrix-matrix product
+impl<T> std::ops::Mul for M4<T>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self(crate::mat4_col_major::mult_mat_col_major(&self.0, &rhs.0))
+    }
+}
+
+#[test]
+fn test_newtype() {
+    use crate::mat3_col_major::Mat3ColMajor;
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let a: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    let b: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    let m: [f64; 9] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    let n: [f64; 9] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+    {
+        let c = V3(a) + (V3(b) - V3(a)) * 0.5;
+        assert!(crate::vec3::distance(&c, &a.add(&b).scale(0.5)) < 1.0e-15);
+        let d = -V3(a) / 2.;
+        assert!(crate::vec3::distance(&d, &a.scale(-0.5)) < 1.0e-15);
+        // the wrapper dereferences to the array
+        assert_eq!(V3(a).dot(&b), a.dot(&b));
+        let mut e = V3(a);
+        e += V3(b);
+        e -= V3(a);
+        e[2] = 1.0;
+        assert_eq!(<[f64; 3]>::from(e), [b[0], b[1], 1.0]);
+    }
+    {
+        let c = M3(m) * V3(a);
+        assert_eq!(c.0, m.mult_vec(&a));
+        let d = M3(m) * M3(n);
+        assert_eq!(d.0, m.mult_mat_col_major(&n));
+        let m4 = M4(crate::mat4_col_major::from_mat3_col_major_adding_w(&m, 1.));
+        let n4 = M4(crate::mat4_col_major::from_mat3_col_major_adding_w(&n, 1.));
+        let d4 = crate::mat4_col_major::from_mat3_col_major_adding_w(&d.0, 1.);
+        assert!((0..16).all(|i| ((m4 * n4)[i] - d4[i]).abs() < 1.0e-15));
+    }
+    {
+        let c = V2([1., 2.]) + V2([3., 4.]) * 2.;
+        assert_eq!(c.0, [7., 10.]);
+    }
+}
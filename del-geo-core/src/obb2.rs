@@ -230,3 +230,164 @@ fn test_is_intersect_obb1() {
         assert!(is_intersect_obb2(&obb1, &obb2));
     }
 }
+
+/// convex hull of the points in the counter-clockwise order (Andrew's monotone chain).
+/// The points on the edges of the hull are removed.
+fn convex_hull_ccw(points: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let mut ps = points.to_vec();
+    ps.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    ps.dedup();
+    if ps.len() <= 2 {
+        return ps;
+    }
+    // the chain turning left. The last point is dropped as it starts the other chain.
+    let chain = |ps: &mut dyn Iterator<Item = &[f32; 2]>| {
+        let mut chain: Vec<[f32; 2]> = vec![];
+        for p in ps {
+            while let [.., a, b] = chain[..] {
+                if crate::vec2::cross(&b.sub(&a), &p.sub(&a)) > 0. {
+                    break;
+                }
+                chain.pop();
+            }
+            chain.push(*p);
+        }
+        chain.pop();
+        chain
+    };
+    let mut hull = chain(&mut ps.iter()); // lower hull
+    hull.extend(chain(&mut ps.iter().rev())); // upper hull
+    hull
+}
+
+/// minimum-area OBB containing the points computed with the rotating calipers
+/// on the convex hull (Toussaint 1983). The major axis is the longer one.
+/// Returns `None` if there is no point.
+pub fn fit_from_convex_hull(points: &[[f32; 2]]) -> Option<[f32; 6]> {
+    let hull = convex_hull_ccw(points);
+    let n = hull.len();
+    match n {
+        0 => return None,
+        1 => return Some([hull[0][0], hull[0][1], 0., 0., 0., 0.]),
+        2 => {
+            let c = hull[0].add(&hull[1]).scale(0.5);
+            let h = hull[1].sub(&hull[0]).scale(0.5);
+            return Some([c[0], c[1], h[0], h[1], 0., 0.]);
+        }
+        _ => {}
+    }
+    let dot = |j: usize, u: &[f32; 2]| hull[j % n].dot(u);
+    let mut best: Option<(f32, [f32; 6])> = None;
+    // indices of the extreme points along the edge direction (max and min) and its normal (max)
+    let (mut j_max, mut j_min, mut j_top) = (0, 0, 0);
+    for i in 0..n {
+        let u = hull[(i + 1) % n].sub(&hull[i]).normalize();
+        let v = u.perp(); // pointing inside for the counter-clockwise hull
+        if i == 0 {
+            let arg =
+                |f: &dyn Fn(usize) -> f32| (0..n).max_by(|&a, &b| f(a).total_cmp(&f(b))).unwrap();
+            j_max = arg(&|j| dot(j, &u));
+            j_min = arg(&|j| -dot(j, &u));
+            j_top = arg(&|j| dot(j, &v));
+        }
+        // the extreme points move monotonically as the edge rotates
+        while dot(j_max + 1, &u) > dot(j_max, &u) {
+            j_max = (j_max + 1) % n;
+        }
+        while dot(j_min + 1, &u) < dot(j_min, &u) {
+            j_min = (j_min + 1) % n;
+        }
+        while dot(j_top + 1, &v) > dot(j_top, &v) {
+            j_top = (j_top + 1) % n;
+        }
+        let (u_min, u_max) = (dot(j_min, &u), dot(j_max, &u));
+        let (v_min, v_max) = (dot(i, &v), dot(j_top, &v));
+        let area = (u_max - u_min) * (v_max - v_min);
+        if best.is_some_and(|(a, _)| a <= area) {
+            continue;
+        }
+        let c = u
+            .scale((u_min + u_max) * 0.5)
+            .add(&v.scale((v_min + v_max) * 0.5));
+        let mut a0 = u.scale((u_max - u_min) * 0.5);
+        let mut a1 = v.scale((v_max - v_min) * 0.5);
+        if a0.dot(&a0) < a1.dot(&a1) {
+            std::mem::swap(&mut a0, &mut a1);
+        }
+        best = Some((area, [c[0], c[1], a0[0], a0[1], a1[0], a1[1]]));
+    }
+    best.map(|(_, obb)| obb)
+}
+
+#[test]
+fn test_fit_from_convex_hull() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let area = |obb: &[f32; 6]| {
+        4. * crate::vec2::length(&[obb[2], obb[3]]) * crate::vec2::length(&[obb[4], obb[5]])
+    };
+    for _iter in 0..100 {
+        let obb0 = from_random(&mut reng);
+        let num_point = reng.random_range(3..30);
+        let ps: Vec<[f32; 2]> = (0..num_point)
+            .map(|_| {
+                let s = [
+                    2. * reng.random::<f32>() - 1.,
+                    2. * reng.random::<f32>() - 1.,
+                ];
+                [
+                    obb0[0] + s[0] * obb0[2] + s[1] * obb0[4],
+                    obb0[1] + s[0] * obb0[3] + s[1] * obb0[5],
+                ]
+            })
+            .collect();
+        let obb = fit_from_convex_hull(&ps).unwrap();
+        assert!(crate::vec2::dot(&[obb[2], obb[3]], &[obb[4], obb[5]]).abs() < 1.0e-5);
+        let scaled = [
+            obb[0],
+            obb[1],
+            obb[2] * 1.001,
+            obb[3] * 1.001,
+            obb[4] * 1.001,
+            obb[5] * 1.001,
+        ];
+        assert!(ps.iter().all(|p| is_include_point2(&scaled, p)));
+        // brute force over the directions of all the pairs of the points
+        let mut area_min = f32::INFINITY;
+        for p0 in &ps {
+            for p1 in &ps {
+                let Some(u) = crate::vec2::normalize_checked(&p1.sub(p0), 1.0e-10) else {
+                    continue;
+                };
+                let v = u.perp();
+                let (mut u0, mut u1, mut v0, mut v1) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+                for p in &ps {
+                    (u0, u1) = (u0.min(p.dot(&u)), u1.max(p.dot(&u)));
+                    (v0, v1) = (v0.min(p.dot(&v)), v1.max(p.dot(&v)));
+                }
+                area_min = area_min.min((u1 - u0) * (v1 - v0));
+            }
+        }
+        assert!(
+            (area(&obb) - area_min).abs() < 1.0e-4 * area_min.max(1.0e-3),
+            "{} {}",
+            area(&obb),
+            area_min
+        );
+        assert!(area(&obb) <= area(&obb0) * 1.0001);
+    }
+    // degenerate inputs
+    assert_eq!(fit_from_convex_hull(&[]), None);
+    // a NaN point does not panic
+    let _ = fit_from_convex_hull(&[[0., 0.], [f32::NAN, 1.], [1., 0.], [1., 1.]]);
+    assert_eq!(
+        fit_from_convex_hull(&[[1., 2.]; 3]),
+        Some([1., 2., 0., 0., 0., 0.])
+    );
+    let obb = fit_from_convex_hull(&[[0., 0.], [1., 1.], [2., 2.]]).unwrap();
+    assert_eq!(obb, [1., 1., 1., 1., 0., 0.]);
+    // square
+    let obb = fit_from_convex_hull(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.5]]).unwrap();
+    assert!((area(&obb) - 1.).abs() < 1.0e-6);
+}
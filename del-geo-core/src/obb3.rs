@@ -9,6 +9,9 @@ pub trait OBB3Trait<T> {
     fn is_include_point(&self, p: &[T; 3], eps: T) -> bool;
     fn unit_axes_and_half_edge_lengths(&self) -> ([[T; 3]; 3], [T; 3]);
     fn nearest_to_point3(&self, p: &[T; 3]) -> [T; 3];
+    fn to_aabb3(&self) -> [T; 6];
+    fn intersections_against_ray(&self, ray_org: &[T; 3], ray_dir: &[T; 3]) -> Option<(T, T)>;
+    fn transform_by_isometry(&self, iso: &[T; 7]) -> Self;
 }
 impl<Real> OBB3Trait<Real> for [Real; 12]
 where
@@ -23,6 +26,19 @@ where
     fn nearest_to_point3(&self, p: &[Real; 3]) -> [Real; 3] {
         nearest_to_point3(self, p)
     }
+    fn to_aabb3(&self) -> [Real; 6] {
+        to_aabb3(self)
+    }
+    fn intersections_against_ray(
+        &self,
+        ray_org: &[Real; 3],
+        ray_dir: &[Real; 3],
+    ) -> Option<(Real, Real)> {
+        intersections_against_ray(self, ray_org, ray_dir)
+    }
+    fn transform_by_isometry(&self, iso: &[Real; 7]) -> Self {
+        transform_by_isometry(self, iso)
+    }
 }
 
 pub fn from_random<RAND, Real>(reng: &mut RAND) -> [Real; 12]
@@ -45,6 +61,162 @@ where
     ]
 }
 
+/// OBB from the center, the half lengths of the edges, and the rotation matrix (column major)
+/// whose columns are the directions of the local axes
+pub fn from_center_half_extents_rotation<Real>(
+    center: &[Real; 3],
+    half_extents: &[Real; 3],
+    rot: &[Real; 9],
+) -> [Real; 12]
+where
+    Real: num_traits::Float,
+{
+    let mut obb = [Real::zero(); 12];
+    obb[..3].copy_from_slice(center);
+    for i in 0..3 {
+        for k in 0..3 {
+            obb[3 + i * 3 + k] = rot[i * 3 + k] * half_extents[i];
+        }
+    }
+    obb
+}
+
+/// OBB from the center, the half lengths of the edges, and the unit quaternion `[i,j,k,w]`
+pub fn from_center_half_extents_quaternion<Real>(
+    center: &[Real; 3],
+    half_extents: &[Real; 3],
+    quat: &[Real; 4],
+) -> [Real; 12]
+where
+    Real: num_traits::Float,
+{
+    let rot = crate::quaternion::to_mat3_col_major(quat);
+    from_center_half_extents_rotation(center, half_extents, &rot)
+}
+
+/// tight AABB of the OBB
+pub fn to_aabb3<Real>(obb: &[Real; 12]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let h: [Real; 3] =
+        std::array::from_fn(|k| obb[3 + k].abs() + obb[6 + k].abs() + obb[9 + k].abs());
+    [
+        obb[0] - h[0],
+        obb[1] - h[1],
+        obb[2] - h[2],
+        obb[0] + h[0],
+        obb[1] + h[1],
+        obb[2] + h[2],
+    ]
+}
+
+/// compute intersection against ray
+/// * `ray_dir` - direction of ray (general non-zero vector, not necessarily unitary)
+///
+/// * Return
+///     * `None`: if there is no intersection
+///     * `(t_min, t_max)` the ratios of `ray_dir` at the entry and exit.
+///       See [`crate::aabb::intersections_against_ray`]
+pub fn intersections_against_ray<Real>(
+    obb: &[Real; 12],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> Option<(Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    intersections_against_line(obb, ray_org, ray_dir).filter(|(_tmin, tmax)| *tmax >= Real::zero())
+}
+
+/// the same as [`intersections_against_ray`] but for the infinite line
+/// (`t_min` can be negative). See [`crate::aabb::intersections_against_line`]
+pub fn intersections_against_line<Real>(
+    obb: &[Real; 12],
+    line_org: &[Real; 3],
+    line_dir: &[Real; 3],
+) -> Option<(Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    // line in the local coordinate where the OBB is `[-1,1]^3`
+    let d = line_org.sub(obb[..3].try_into().unwrap());
+    let mut org = [Real::zero(); 3];
+    let mut dir = [Real::zero(); 3];
+    for i in 0..3 {
+        let a: &[Real; 3] = obb[3 + i * 3..6 + i * 3].try_into().unwrap();
+        let sq_len = a.squared_norm();
+        org[i] = a.dot(&d) / sq_len;
+        dir[i] = a.dot(line_dir) / sq_len;
+    }
+    let one = Real::one();
+    let aabb = [-one, -one, -one, one, one, one];
+    crate::aabb::intersections_against_line::<Real, 3, 6>(&aabb, &org, &dir)
+}
+
+/// OBB transformed by the rigid transformation (see [`crate::isometry3`])
+pub fn transform_by_isometry<Real>(obb: &[Real; 12], iso: &[Real; 7]) -> [Real; 12]
+where
+    Real: num_traits::Float,
+{
+    use crate::isometry3::Isometry3;
+    let mut o = [Real::zero(); 12];
+    o[..3].copy_from_slice(&iso.transform_point(obb[..3].try_into().unwrap()));
+    for i in 0..3 {
+        let a = iso.transform_vector(obb[3 + i * 3..6 + i * 3].try_into().unwrap());
+        o[3 + i * 3..6 + i * 3].copy_from_slice(&a);
+    }
+    o
+}
+
+#[test]
+fn test_construction_and_queries() {
+    use crate::mat4_col_major::Mat4ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut reng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let q = crate::quaternion::sample_uniform::<_, f64>(&mut reng);
+        let c = [-1., -1., -1., 1., 1., 1.].sample(&mut reng);
+        let h = [0.1, 0.1, 0.1, 1., 1., 1.].sample(&mut reng);
+        let obb = from_center_half_extents_quaternion(&c, &h, &q);
+        let (_axes, hlen) = obb.unit_axes_and_half_edge_lengths();
+        assert!(hlen.sub(&h).norm() < 1.0e-10);
+        // AABB touching the corners
+        let aabb = obb.to_aabb3();
+        let aabb1 = crate::aabb3::from_points(&corner_points(&obb));
+        assert!((0..6).all(|i| (aabb[i] - aabb1[i]).abs() < 1.0e-10));
+        // ray
+        let org = [-3., -3., -3., 3., 3., 3.].sample(&mut reng);
+        let dir = [-1., -1., -1., 1., 1., 1.].sample(&mut reng);
+        if let Some((t0, t1)) = obb.intersections_against_ray(&org, &dir) {
+            assert!(t1 >= 0.);
+            // the points slightly before the entry and after the exit are outside
+            for (t, sign) in [(t0, -1.), (t1, 1.)] {
+                let p = org.add(&dir.scale(t));
+                assert!(obb.is_include_point(&p, 1.0e-8));
+                let q = org.add(&dir.scale(t + sign * 1.0e-4));
+                assert!(!obb.is_include_point(&q, 0.));
+            }
+        } else {
+            for _ in 0..100 {
+                let t = reng.random::<f64>() * 10.;
+                assert!(!obb.is_include_point(&org.add(&dir.scale(t)), 0.));
+            }
+        }
+        // isometry
+        let iso = crate::isometry3::from_quaternion_and_translation(
+            &crate::quaternion::sample_uniform::<_, f64>(&mut reng),
+            &[-1., -1., -1., 1., 1., 1.].sample(&mut reng),
+        );
+        let obb1 = obb.transform_by_isometry(&iso);
+        let m = crate::isometry3::to_mat4_col_major(&iso);
+        let cs0 = corner_points(&obb1);
+        let cs1 = corner_points(&obb).map(|p| m.transform_homogeneous(&p).unwrap());
+        assert!((0..8).all(|i| cs0[i].sub(&cs1[i]).norm() < 1.0e-10));
+    }
+}
+
 pub fn is_include_point<Real>(obb: &[Real; 12], p: &[Real; 3], eps: Real) -> bool
 where
     Real: num_traits::Float,
@@ -41,3 +41,144 @@ where
     let n0 = n.normalize();
     p.add(&n0.scale(o.sub(p).dot(&n0)))
 }
+
+/// signed distance from the point `p` to the plane. Positive on the side of the normal.
+/// The normal `n` does not need to be normalized.
+pub fn signed_distance<T>(o: &[T; 3], n: &[T; 3], p: &[T; 3]) -> T
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    p.sub(o).dot(n) / n.norm()
+}
+
+/// orthogonal projection of the point `p` onto the plane (the same as [`nearest_to_point3`])
+pub fn project_point<T>(o: &[T; 3], n: &[T; 3], p: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    nearest_to_point3(p, o, n)
+}
+
+/// two unit vectors spanning the plane such that `(b1, b2, n)` is right-handed orthonormal basis
+pub fn basis<T>(n: &[T; 3]) -> ([T; 3], [T; 3])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    crate::vec3::orthonormal_basis(&n.normalize())
+}
+
+/// column major 4x4 matrix of the reflection across the plane
+pub fn mirror_mat4<T>(o: &[T; 3], n: &[T; 3]) -> [T; 16]
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    crate::mat4_col_major::from_reflection_across_plane(&[n[0], n[1], n[2], -n.dot(o)])
+}
+
+#[test]
+fn test_plane_utility() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let o: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let n: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let p: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let q = project_point(&o, &n, &p);
+        assert!(signed_distance(&o, &n, &q).abs() < 1.0e-10);
+        let h = signed_distance(&o, &n, &p);
+        assert!((p.sub(&q).norm() - h.abs()).abs() < 1.0e-10);
+        assert!(p.sub(&q).dot(&n) * h >= 0.);
+        // basis
+        let (b1, b2) = basis(&n);
+        assert!(b1.dot(&n).abs() < 1.0e-10 && b2.dot(&n).abs() < 1.0e-10);
+        assert!(b1.cross(&b2).sub(&n.normalize()).norm() < 1.0e-10);
+        // the mirrored point has the opposite signed distance with the same projection
+        let m = mirror_mat4(&o, &n);
+        let r = crate::mat4_col_major::transform_homogeneous(&m, &p).unwrap();
+        assert!((signed_distance(&o, &n, &r) + h).abs() < 1.0e-10);
+        assert!(project_point(&o, &n, &r).sub(&q).norm() < 1.0e-10);
+    }
+}
+
+/// best-fit plane minimizing the sum of the squared distances from the points
+/// by the principal component analysis of the covariance matrix
+/// * Return `(origin, normal)` where `origin` is the centroid and `normal` is the unit normal
+/// * Return `None` if the points are collinear or less than three
+pub fn fit_from_points<T>(points: &[[T; 3]]) -> Option<([T; 3], [T; 3])>
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    use crate::vec3::Vec3;
+    if points.len() < 3 {
+        return None;
+    }
+    let n = T::from(points.len()).unwrap();
+    let origin = points
+        .iter()
+        .fold([T::zero(); 3], |a, p| a.add(p))
+        .scale(T::one() / n);
+    let cov = points.iter().fold([T::zero(); 6], |a, p| {
+        let d = p.sub(&origin);
+        [
+            a[0] + d[0] * d[0],
+            a[1] + d[1] * d[1],
+            a[2] + d[2] * d[2],
+            a[3] + d[1] * d[2],
+            a[4] + d[2] * d[0],
+            a[5] + d[0] * d[1],
+        ]
+    });
+    let (u, lambda) = crate::mat3_sym::eigen_decomposition(
+        &cov,
+        crate::mat3_sym::EigenDecompositionModes::JacobiNumIter(100),
+    )?;
+    // the eigen values are not sorted
+    let mut idx = [0, 1, 2];
+    idx.sort_by(|&i, &j| crate::polynomial_root::cmp_nan_last(&lambda[i], &lambda[j]));
+    if lambda[idx[1]] <= T::epsilon() * lambda[idx[2]] {
+        return None;
+    }
+    // `u` is row major and the eigen vectors are the columns
+    let i = idx[0];
+    Some((origin, [u[i], u[3 + i], u[6 + i]].normalize()))
+}
+
+#[test]
+fn test_fit_from_points() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let o0: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let n0 = crate::sphere::sample_surface_uniform::<f64>(&[rng.random(), rng.random()]);
+        let (b1, b2) = basis(&n0);
+        let points: Vec<[f64; 3]> = (0..50)
+            .map(|_| {
+                let (s, t) = (2. * rng.random::<f64>() - 1., rng.random::<f64>() - 0.5);
+                let h = 1.0e-3 * (2. * rng.random::<f64>() - 1.);
+                o0.add(&b1.scale(s)).add(&b2.scale(t)).add(&n0.scale(h))
+            })
+            .collect();
+        let (o, n) = fit_from_points(&points).unwrap();
+        assert!((n.norm() - 1.).abs() < 1.0e-10);
+        assert!(n.cross(&n0).norm() < 1.0e-2);
+        assert!(signed_distance(&o0, &n0, &o).abs() < 1.0e-3);
+    }
+    // axis-aligned
+    let (o, n) = fit_from_points::<f64>(&[[0., 0., 1.], [2., 0., 1.], [0., 3., 1.]]).unwrap();
+    assert!((o[2] - 1.).abs() < 1.0e-10 && (n[2].abs() - 1.).abs() < 1.0e-10);
+    // collinear
+    assert_eq!(
+        fit_from_points(&[[0., 0., 0.], [1., 1., 1.], [2., 2., 2.]]),
+        None
+    );
+    assert_eq!(fit_from_points(&[[0., 0., 0.], [1., 1., 1.]]), None);
+    // a NaN point does not panic
+    let _ = fit_from_points(&[[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [f64::NAN, 0., 0.]]);
+}
@@ -0,0 +1,34 @@
+//! methods for 2D polygon represented as the list of the vertices `&[[Real;2]]`
+
+/// clip the polygon by the half-plane `a*x + b*y + c >= 0` given as `[a, b, c]`.
+/// This is the 2D version of [`crate::polygon3::clip_by_plane`].
+pub fn clip_by_half_plane<T>(verts: &[[T; 2]], half_plane: &[T; 3]) -> Vec<[T; 2]>
+where
+    T: num_traits::Float,
+{
+    crate::polygon3::clip_by_signed_distance(verts, |p| {
+        half_plane[0] * p[0] + half_plane[1] * p[1] + half_plane[2]
+    })
+}
+
+#[test]
+fn test_clip_by_half_plane() {
+    let area = |poly: &[[f64; 2]]| -> f64 {
+        (1..poly.len().max(2) - 1)
+            .map(|i| crate::tri2::area(&poly[0], &poly[i], &poly[i + 1]))
+            .sum()
+    };
+    let quad = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]];
+    // clipping by the four half-planes of the unit square
+    let poly = [[1., 0., 0.], [0., 1., 0.], [-1., 0., 1.], [0., -1., 1.]]
+        .iter()
+        .fold(quad.to_vec(), |poly, h| clip_by_half_plane(&poly, h));
+    assert_eq!(poly.len(), 4);
+    assert!((area(&poly) - 1.).abs() < 1.0e-10);
+    // cutting the corner off
+    let poly = clip_by_half_plane(&quad, &[-1., -1., 3.]);
+    assert_eq!(poly.len(), 5);
+    assert!((area(&poly) - 3.5).abs() < 1.0e-10);
+    assert!(clip_by_half_plane(&quad, &[1., 0., 5.]).len() == 4);
+    assert!(clip_by_half_plane(&quad, &[1., 0., -5.]).is_empty());
+}
@@ -0,0 +1,82 @@
+//! methods for 3D polygon represented as the list of the vertices `&[[Real;3]]`
+
+/// one step of the Sutherland–Hodgman clipping for the polygon in any dimension.
+/// The part of the polygon where `signed_distance` is non-negative is kept.
+pub(crate) fn clip_by_signed_distance<T, const N: usize, F>(
+    verts: &[[T; N]],
+    signed_distance: F,
+) -> Vec<[T; N]>
+where
+    T: num_traits::Float,
+    F: Fn(&[T; N]) -> T,
+{
+    let zero = T::zero();
+    let dists: Vec<T> = verts.iter().map(&signed_distance).collect();
+    let mut res = Vec::with_capacity(verts.len() + 1);
+    for i0 in 0..verts.len() {
+        let i1 = (i0 + 1) % verts.len();
+        let (d0, d1) = (dists[i0], dists[i1]);
+        if d0 >= zero {
+            res.push(verts[i0]);
+        }
+        if (d0 > zero && d1 < zero) || (d0 < zero && d1 > zero) {
+            let r = d0 / (d0 - d1);
+            let (p0, p1) = (&verts[i0], &verts[i1]);
+            res.push(std::array::from_fn(|i| p0[i] + (p1[i] - p0[i]) * r));
+        }
+    }
+    res
+}
+
+/// clip the polygon by the plane `a*x + b*y + c*z + d = 0` given as `[a, b, c, d]`
+/// and keep the side `a*x + b*y + c*z + d >= 0` (the same convention as [`crate::frustum`]).
+/// The vertices on the plane are kept as they are.
+/// The convex polygon results in the convex polygon, which is empty if the polygon is outside.
+pub fn clip_by_plane<T>(verts: &[[T; 3]], plane: &[T; 4]) -> Vec<[T; 3]>
+where
+    T: num_traits::Float,
+{
+    clip_by_signed_distance(verts, |p| {
+        plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3]
+    })
+}
+
+#[test]
+fn test_clip_by_plane() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    // clipping the triangle by the plane keeps the area of the triangle on each side
+    for _iter in 0..100 {
+        let tri: [[f64; 3]; 3] =
+            std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.));
+        let plane: [f64; 4] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let area = |poly: &[[f64; 3]]| -> f64 {
+            (1..poly.len().max(2) - 1)
+                .map(|i| crate::tri3::area(&poly[0], &poly[i], &poly[i + 1]))
+                .sum()
+        };
+        let poly0 = clip_by_plane(&tri, &plane);
+        let poly1 = clip_by_plane(&tri, &plane.map(|v| -v));
+        assert!((area(&poly0) + area(&poly1) - area(&tri)).abs() < 1.0e-10);
+        assert!(poly0.len() <= 4 && poly1.len() <= 4);
+        for p in poly0.iter() {
+            assert!(plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3] > -1.0e-10);
+            // on the plane of the triangle
+            let n = crate::tri3::normal(&tri[0], &tri[1], &tri[2]);
+            assert!(p.sub(&tri[0]).dot(&n).abs() < 1.0e-10);
+        }
+    }
+    let quad = [[0., 0., 0.], [2., 0., 0.], [2., 2., 0.], [0., 2., 0.]];
+    assert_eq!(
+        clip_by_plane(&quad, &[-1., 0., 0., 1.]),
+        vec![[0., 0., 0.], [1., 0., 0.], [1., 2., 0.], [0., 2., 0.]]
+    );
+    // the vertex on the plane is not duplicated
+    assert_eq!(
+        clip_by_plane(&quad, &[-1., -1., 0., 2.]),
+        vec![[0., 0., 0.], [2., 0., 0.], [0., 2., 0.]]
+    );
+    assert!(clip_by_plane(&quad, &[0., 0., 1., -1.]).is_empty());
+}
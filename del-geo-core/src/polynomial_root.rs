@@ -4,13 +4,13 @@
 /// f(x) = c0 + c1*x + c2*x^2
 pub fn quadratic_root<T>(c0: T, c1: T, c2: T) -> Option<[T; 2]>
 where
-    T: num_traits::Float + std::fmt::Debug,
+    T: num_traits::Float,
 {
     let zero = T::zero();
     let one = T::one();
     let two = one + one;
     let four = two + two;
-    assert_ne!(c2, zero);
+    assert!(c2 != zero);
     let det = c1 * c1 - four * c2 * c0;
     if det < zero {
         return None;
@@ -25,10 +25,6 @@ where
         x2
     };
     let (x1, x2) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
-    if x1 <= x2 {
-    } else {
-        dbg!(c0, c1, c2, det, tmp, x1, x2);
-    }
     assert!(x1 <= x2);
     Some([x1, x2])
 }
@@ -57,6 +53,260 @@ fn test_quadratic_root() {
     }
 }
 
+/// ascending order with NaN at the end, so that sorting never panics
+pub(crate) fn cmp_nan_last<T>(x: &T, y: &T) -> std::cmp::Ordering
+where
+    T: num_traits::Float,
+{
+    x.partial_cmp(y)
+        .unwrap_or_else(|| x.is_nan().cmp(&y.is_nan()))
+}
+
+/// find all the real roots of cubic function
+/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3
+///
+/// One real root is computed with Cardano's formula and polished with Newton's method,
+/// then the remaining quadratic factor is solved. A pair of roots whose discriminant is
+/// negative only by the rounding error is treated as a double real root.
+///
+/// # Returns
+/// `(roots, num_roots)` where the first `num_roots` elements of `roots` are the real roots in
+/// ascending order (repeated roots are repeated). `num_roots` is either one or three.
+pub fn cubic_roots_real<T>(c0: T, c1: T, c2: T, c3: T) -> ([T; 3], usize)
+where
+    T: num_traits::Float,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+    let three = two + one;
+    let four = two + two;
+    assert!(c3 != zero);
+    // monic cubic x^3 + a*x^2 + b*x + c
+    let (a, b, c) = (c2 / c3, c1 / c3, c0 / c3);
+    let eval_f = |x: T| ((x + a) * x + b) * x + c;
+    let eval_df = |x: T| (three * x + two * a) * x + b;
+    // depressed cubic t^3 + p*t + q where x = t - a/3
+    let p = b - a * a / three;
+    let q = (two * a * a * a - T::from(9).unwrap() * a * b) / T::from(27).unwrap() + c;
+    let disc = (q / two) * (q / two) + (p / three) * (p / three) * (p / three);
+    let t = if disc >= zero {
+        let sgnq = if q < zero { -one } else { one };
+        let u = (-q / two - sgnq * disc.sqrt()).cbrt();
+        if u == zero { zero } else { u - p / (three * u) }
+    } else {
+        // three real roots. pick the largest one with the trigonometric formula
+        let m = two * (-p / three).sqrt();
+        let theta = (three * q / (p * m)).clamp(-one, one).acos() / three;
+        m * theta.cos()
+    };
+    let mut r = t - a / three;
+    for _itr in 0..3 {
+        let df = eval_df(r);
+        if df == zero {
+            break;
+        }
+        let dr = eval_f(r) / df;
+        if !dr.is_finite() {
+            break;
+        }
+        r = r - dr;
+    }
+    // deflation: x^3 + a*x^2 + b*x + c = (x - r) * (x^2 + h*x + k)
+    let h = a + r;
+    let k = b + r * h;
+    let disc2 = h * h - four * k;
+    let tol = T::epsilon() * T::from(1024).unwrap() * (h * h + k.abs());
+    let sort3 = |mut v: [T; 3]| {
+        v.sort_by(cmp_nan_last);
+        v
+    };
+    if disc2 >= zero {
+        let [x0, x1] = quadratic_root(k, h, one).unwrap();
+        (sort3([r, x0, x1]), 3)
+    } else if disc2 > -tol {
+        let x = -h / two;
+        (sort3([r, x, x]), 3)
+    } else {
+        ([r, zero, zero], 1)
+    }
+}
+
+#[test]
+fn test_cubic_roots_real() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..10000 {
+        let c3 = 4. * rng.random::<f64>() - 2.;
+        let mut x: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        x.sort_by(f64::total_cmp);
+        // three real roots
+        let (c0, c1, c2) = (
+            -c3 * x[0] * x[1] * x[2],
+            c3 * (x[0] * x[1] + x[1] * x[2] + x[2] * x[0]),
+            -c3 * (x[0] + x[1] + x[2]),
+        );
+        let (y, n) = cubic_roots_real(c0, c1, c2, c3);
+        assert_eq!(n, 3);
+        for i in 0..3 {
+            assert!((x[i] - y[i]).abs() < 1.0e-5, "{x:?} {y:?}");
+        }
+        // one real root and a complex pair
+        let im = rng.random::<f64>() + 0.1;
+        let (re, x0) = (x[1], x[0]);
+        let (d0, d1) = (re * re + im * im, -2. * re);
+        let (c0, c1, c2) = (-c3 * x0 * d0, c3 * (d0 - x0 * d1), c3 * (d1 - x0));
+        let (y, n) = cubic_roots_real(c0, c1, c2, c3);
+        assert_eq!(n, 1);
+        assert!((x0 - y[0]).abs() < 1.0e-8);
+    }
+    // double root
+    let (y, n) = cubic_roots_real(-2f64, 5., -4., 1.);
+    assert_eq!(n, 3);
+    assert!((y[0] - 1.).abs() < 1.0e-6 && (y[1] - 1.).abs() < 1.0e-6);
+    assert!((y[2] - 2.).abs() < 1.0e-10);
+    // triple root
+    let (y, n) = cubic_roots_real(-1f64, 3., -3., 1.);
+    assert_eq!(n, 3);
+    assert!(y.iter().all(|&v| (v - 1.).abs() < 1.0e-4));
+}
+
+/// find all the real roots of quartic function
+/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3 + c4*x^4
+///
+/// The depressed quartic is factorized into two quadratics by Ferrari's method
+/// using the largest root of the resolvent cubic, and each root is polished with Newton's method.
+/// A pair of roots that are very close (e.g., the tangent ray to a surface) may be missed.
+///
+/// # Returns
+/// `(roots, num_roots)` where the first `num_roots` elements of `roots` are the real roots in
+/// ascending order (repeated roots are repeated). `num_roots` is either zero, two or four.
+pub fn quartic_roots_real<T>(c0: T, c1: T, c2: T, c3: T, c4: T) -> ([T; 4], usize)
+where
+    T: num_traits::Float,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+    let three = two + one;
+    let four = two + two;
+    let eight = four + four;
+    assert!(c4 != zero);
+    // monic quartic x^4 + a*x^3 + b*x^2 + c*x + d
+    let (a, b, c, d) = (c3 / c4, c2 / c4, c1 / c4, c0 / c4);
+    let eval_f = |x: T| (((x + a) * x + b) * x + c) * x + d;
+    let eval_df = |x: T| ((four * x + three * a) * x + two * b) * x + c;
+    // depressed quartic y^4 + p*y^2 + q*y + r where x = y - a/4
+    let a4 = a / four;
+    let p = b - T::from(6).unwrap() * a4 * a4;
+    let q = c - two * b * a4 + eight * a4 * a4 * a4;
+    let r = d - c * a4 + b * a4 * a4 - three * a4 * a4 * a4 * a4;
+    let mut ys = [zero; 4];
+    let mut num_y = 0;
+    let mut push_quadratic_roots = |k0: T, k1: T| {
+        if let Some([y0, y1]) = quadratic_root(k0, k1, one) {
+            ys[num_y] = y0;
+            ys[num_y + 1] = y1;
+            num_y += 2;
+        }
+    };
+    // resolvent cubic 8*m^3 + 8*p*m^2 + (2*p^2 - 8*r)*m - q^2 = 0 has a positive root if q != 0
+    let m = cubic_roots_real(-q * q, two * p * p - eight * r, eight * p, eight);
+    let m = m.0[m.1 - 1];
+    if m > zero && q.abs() > T::epsilon() * (p * p + r.abs()) {
+        // (y^2 + s*y + p/2 + m - q/(2s)) * (y^2 - s*y + p/2 + m + q/(2s))
+        let s = (two * m).sqrt();
+        let h = p / two + m;
+        push_quadratic_roots(h - q / (two * s), s);
+        push_quadratic_roots(h + q / (two * s), -s);
+    } else {
+        // biquadratic z^2 + p*z + r = 0 where z = y^2
+        if let Some([z0, z1]) = quadratic_root(r, p, one) {
+            for z in [z1, z0] {
+                if z >= zero {
+                    push_quadratic_roots(-z, zero);
+                } else if z > -T::epsilon() * (p.abs() + r.abs().sqrt()) {
+                    push_quadratic_roots(zero, zero);
+                }
+            }
+        }
+    }
+    let mut roots = [zero; 4];
+    for (i, y) in ys.iter().take(num_y).enumerate() {
+        let mut x = *y - a4;
+        for _itr in 0..3 {
+            let df = eval_df(x);
+            if df == zero {
+                break;
+            }
+            let dx = eval_f(x) / df;
+            if !dx.is_finite() {
+                break;
+            }
+            x = x - dx;
+        }
+        roots[i] = x;
+    }
+    roots[..num_y].sort_by(cmp_nan_last);
+    (roots, num_y)
+}
+
+#[test]
+fn test_quartic_roots_real() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    // coefficients of c4 * (x^2 + e1 * x + e0) * (x^2 + g1 * x + g0)
+    let mul = |c4: f64, e0: f64, e1: f64, g0: f64, g1: f64| {
+        (
+            c4 * e0 * g0,
+            c4 * (e0 * g1 + e1 * g0),
+            c4 * (e0 + e1 * g1 + g0),
+            c4 * (e1 + g1),
+            c4,
+        )
+    };
+    for _ in 0..10000 {
+        let c4 = 4. * rng.random::<f64>() - 2.;
+        let mut x: [f64; 4] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 2.);
+        x.sort_by(f64::total_cmp);
+        // four real roots
+        let (c0, c1, c2, c3, c4) = mul(c4, x[0] * x[1], -x[0] - x[1], x[2] * x[3], -x[2] - x[3]);
+        let (y, n) = quartic_roots_real(c0, c1, c2, c3, c4);
+        if (x[1] - x[0]).min(x[2] - x[1]).min(x[3] - x[2]) > 1.0e-3 {
+            assert_eq!(n, 4, "{x:?} {y:?}");
+            for i in 0..4 {
+                assert!((x[i] - y[i]).abs() < 1.0e-6, "{x:?} {y:?}");
+            }
+        }
+        // two real roots and a complex pair
+        let im = rng.random::<f64>() + 0.1;
+        let re = x[2];
+        let (c0, c1, c2, c3, c4) = mul(c4, x[0] * x[1], -x[0] - x[1], re * re + im * im, -2. * re);
+        let (y, n) = quartic_roots_real(c0, c1, c2, c3, c4);
+        if x[1] - x[0] > 1.0e-3 {
+            assert_eq!(n, 2, "{x:?} {y:?}");
+            assert!((x[0] - y[0]).abs() < 1.0e-6 && (x[1] - y[1]).abs() < 1.0e-6);
+        }
+        // two complex pairs
+        let im1 = rng.random::<f64>() + 0.1;
+        let re1 = x[0];
+        let (c0, c1, c2, c3, c4) = mul(
+            c4,
+            re1 * re1 + im1 * im1,
+            -2. * re1,
+            re * re + im * im,
+            -2. * re,
+        );
+        assert_eq!(quartic_roots_real(c0, c1, c2, c3, c4).1, 0);
+    }
+    // biquadratic (x^2 - 1) * (x^2 - 4)
+    let (y, n) = quartic_roots_real(4f64, 0., -5., 0., 1.);
+    assert_eq!(n, 4);
+    assert_eq!(y, [-2., -1., 1., 2.]);
+}
+
 /// f(x) = c0 + c1*x + c2*x^2 + c3*x^3
 pub fn cubic_roots_in_range_zero_to_t<T>(c0: T, c1: T, c2: T, c3: T, t: T, epsilon: T) -> Vec<T>
 where
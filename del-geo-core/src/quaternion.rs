@@ -77,6 +77,50 @@ where
     ]
 }
 
+/// rotation matrix of the quaternion after normalization.
+/// [`to_mat3_col_major`] assumes the unit quaternion and gives a skewed matrix otherwise.
+pub fn to_mat3_col_major_normalized<Real>(q: &[Real; 4]) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    to_mat3_col_major(&normalized(q))
+}
+
+/// 4x4 rotation matrix of the quaternion after normalization
+pub fn to_mat4_col_major_normalized<Real>(q: &[Real; 4]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    to_mat4_col_major(&normalized(q))
+}
+
+/// matrix of the rotation and the uniform scale where the norm of the quaternion is the scale
+pub fn to_mat3_col_major_scaled<Real>(q: &[Real; 4]) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let len = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+    to_mat3_col_major(&q.map(|v| v / len)).map(|v| v * len)
+}
+
+#[test]
+fn test_to_mat3_col_major_non_unit() {
+    use crate::mat3_col_major::Mat3ColMajor;
+    let q0 = [0.3f64, -0.2, 0.5, 0.8];
+    let q1 = q0.map(|v| v * 1.7);
+    let r0 = normalized(&q0).to_mat3_col_major();
+    let r1 = to_mat3_col_major_normalized(&q1);
+    assert!(r0.sub(&r1).norm() < 1.0e-12);
+    // the matrix without the normalization is not a rotation
+    assert!(to_mat3_col_major(&q1).determinant() > 1.1);
+    assert!((r1.determinant() - 1.).abs() < 1.0e-12);
+    let r4 = to_mat4_col_major_normalized(&q1);
+    assert!((0..3).all(|i| (0..3).all(|j| (r4[i + j * 4] - r1[i + j * 3]).abs() < 1.0e-12)));
+    let len = q1.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let s = to_mat3_col_major_scaled(&q1);
+    assert!(s.sub(&r0.scale(len)).norm() < 1.0e-12);
+}
+
 pub fn normalized<Real>(q: &[Real; 4]) -> [Real; 4]
 where
     Real: num_traits::Float,
@@ -187,3 +231,792 @@ where
     let sin = half.sin();
     [v[0] * sin, v[1] * sin, v[2] * sin, half.cos()]
 }
+
+/// rotate the vector `v` with the unit quaternion `q`
+pub fn transform_vec<Real>(q: &[Real; 4], v: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    // v + 2w(u x v) + 2u x (u x v) where u is the imaginary part
+    let two = Real::one() + Real::one();
+    let u = [q[0], q[1], q[2]];
+    let t = u.cross(v).scale(two);
+    v.add(&t.scale(q[3])).add(&u.cross(&t))
+}
+
+/// spherical linear interpolation between the unit quaternions along the shorter arc
+pub fn slerp<Real>(q0: &[Real; 4], q1: &[Real; 4], t: Real) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let dot = q0[0] * q1[0] + q0[1] * q1[1] + q0[2] * q1[2] + q0[3] * q1[3];
+    let (q1, dot) = if dot < Real::zero() {
+        (q1.map(|v| -v), -dot)
+    } else {
+        (*q1, dot)
+    };
+    if dot > one - Real::epsilon().sqrt() {
+        // nearly parallel. linear interpolation is accurate enough
+        let q: [Real; 4] = std::array::from_fn(|i| q0[i] + (q1[i] - q0[i]) * t);
+        return normalized(&q);
+    }
+    let theta = dot.min(one).acos();
+    let sin_theta = theta.sin();
+    let w0 = ((one - t) * theta).sin() / sin_theta;
+    let w1 = (t * theta).sin() / sin_theta;
+    std::array::from_fn(|i| q0[i] * w0 + q1[i] * w1)
+}
+
+#[test]
+fn test_transform_vec_and_slerp() {
+    use crate::mat3_col_major::Mat3ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let q = from_axisangle(&a);
+        let v: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let v0 = q.to_mat3_col_major().mult_vec(&v);
+        let v1 = transform_vec(&q, &v);
+        assert!(crate::vec3::distance(&v0, &v1) < 1.0e-10);
+        // slerp follows the constant angular velocity
+        let t = rng.random::<f64>();
+        let q0 = from_axisangle(&a.map(|v| v * 0.3));
+        let q1 = mult_quaternion(&q, &q0);
+        let q2 = slerp(&q0, &q1, t);
+        let q3 = mult_quaternion(&from_axisangle(&a.map(|v| v * t)), &q0);
+        let d = (0..4).fold(0f64, |acc, i| acc + (q2[i] - q3[i]).powi(2));
+        assert!(d < 1.0e-20);
+        let q4 = slerp(&q0, &q1.map(|v| -v), t);
+        let d = (0..4).fold(0f64, |acc, i| acc + (q2[i] - q4[i]).powi(2));
+        assert!(d < 1.0e-20);
+    }
+}
+
+/// exponential map of the pure imaginary quaternion `[v, 0]` to the unit quaternion.
+/// `exp(v)` is the rotation around `v` by the angle `2|v|`.
+pub fn exp<Real>(v: &[Real; 3]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let sqt = v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    let t = sqt.sqrt();
+    let (c, sinc) = if sqt < Real::epsilon() {
+        // Taylor expansion around zero
+        let two = Real::one() + Real::one();
+        let c6 = Real::from(6).unwrap();
+        (Real::one() - sqt / two, Real::one() - sqt / c6)
+    } else {
+        (t.cos(), t.sin() / t)
+    };
+    [v[0] * sinc, v[1] * sinc, v[2] * sinc, c]
+}
+
+/// logarithm of the unit quaternion that is the inverse of [`exp`].
+/// The norm of the output is in `[0, pi]` (i.e., the rotation angle in `[0, 2pi]`).
+/// For `q = [0,0,0,-1]` where the axis is not unique, the x-axis is chosen.
+pub fn log<Real>(q: &[Real; 4]) -> [Real; 3]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    let sqs = q[0] * q[0] + q[1] * q[1] + q[2] * q[2];
+    let s = sqs.sqrt();
+    let c = if s < Real::epsilon() && q[3] > Real::zero() {
+        // Taylor expansion around the identity
+        let three = Real::from(3).unwrap();
+        (Real::one() - sqs / (three * q[3] * q[3])) / q[3]
+    } else if s.is_zero() {
+        return [Real::PI(), Real::zero(), Real::zero()];
+    } else {
+        s.atan2(q[3]) / s
+    };
+    [q[0] * c, q[1] * c, q[2] * c]
+}
+
+/// power of the unit quaternion `q^t = exp(t * log(q))`
+pub fn powf<Real>(q: &[Real; 4], t: Real) -> [Real; 4]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    exp(&log(q).map(|v| v * t))
+}
+
+#[test]
+fn test_exp_log_powf() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let dist =
+        |a: &[f64; 4], b: &[f64; 4]| (0..4).fold(0f64, |acc, i| acc.max((a[i] - b[i]).abs()));
+    for i_iter in 0..100 {
+        let v: [f64; 3] = match i_iter {
+            0 => [0., 0., 0.],
+            1 => [1.0e-9, -2.0e-9, 3.0e-10],
+            _ => std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.),
+        };
+        let q = exp(&v);
+        assert!((q.iter().map(|x| x * x).sum::<f64>() - 1.).abs() < 1.0e-12);
+        // exp(v) is the rotation around v by the angle 2|v|
+        assert!(dist(&q, &from_axisangle(&v.map(|x| x * 2.))) < 1.0e-12);
+        let v1 = log(&q);
+        assert!(crate::vec3::distance(&v, &v1) < 1.0e-12);
+        let t = rng.random::<f64>();
+        let q0 = powf(&q, t);
+        if q[3] >= 0. {
+            // slerp takes the shorter arc
+            let q1 = slerp(&identity(), &q, t);
+            assert!(dist(&q0, &q1) < 1.0e-10);
+        }
+        let q2 = powf(&q, 0.5);
+        assert!(dist(&mult_quaternion(&q2, &q2), &q) < 1.0e-12);
+    }
+    let v = log(&[0f64, 0., 0., -1.]);
+    assert!(dist(&exp(&v), &[0., 0., 0., -1.]) < 1.0e-12);
+}
+
+/// the rotation with the smallest angle that rotates the unit vector `a` to the unit vector `b`.
+/// If `a` and `b` are antiparallel, the rotation by pi around an axis orthogonal to `a` is returned.
+/// See [`crate::mat3_col_major::minimum_rotation_matrix`] for the matrix version.
+pub fn from_two_unit_vectors<Real>(a: &[Real; 3], b: &[Real; 3]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let zero = Real::zero();
+    // [a x b, 1 + a.b] is the quaternion for the double of the angle, then normalize
+    let w = Real::one() + a.dot(b);
+    if w < Real::epsilon() {
+        // antiparallel. cross `a` with the axis of its smallest component
+        let axis = if a[0].abs() <= a[1].abs() && a[0].abs() <= a[2].abs() {
+            [zero, -a[2], a[1]]
+        } else if a[1].abs() <= a[2].abs() {
+            [-a[2], zero, a[0]]
+        } else {
+            [-a[1], a[0], zero]
+        };
+        let axis = axis.normalize();
+        return [axis[0], axis[1], axis[2], zero];
+    }
+    let c = a.cross(b);
+    normalized(&[c[0], c[1], c[2], w])
+}
+
+#[test]
+fn test_from_two_unit_vectors() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..100 {
+        let a: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let a = a.normalize();
+        let b: [f64; 3] = match i_iter {
+            0 => a,
+            1 => a.scale(-1.),
+            2 => [-a[0] + 1.0e-9, -a[1], -a[2]].normalize(),
+            _ => std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.),
+        };
+        let b = b.normalize();
+        let q = from_two_unit_vectors(&a, &b);
+        assert!((q.iter().map(|x| x * x).sum::<f64>() - 1.).abs() < 1.0e-12);
+        let b1 = transform_vec(&q, &a);
+        assert!(
+            crate::vec3::distance(&b, &b1) < 1.0e-7,
+            "{i_iter} {b:?} {b1:?}"
+        );
+        if i_iter > 2 {
+            let m0 = crate::mat3_col_major::minimum_rotation_matrix(&a, &b);
+            let m1 = to_mat3_col_major(&q);
+            assert!((0..9).all(|i| (m0[i] - m1[i]).abs() < 1.0e-10));
+        }
+    }
+}
+
+/// decompose the unit quaternion `q` into the twist around the unit vector `axis` and
+/// the swing around an axis orthogonal to `axis` such that `q = swing * twist`.
+/// If the twist is undefined (i.e., the swing is the rotation by pi), the twist is the identity.
+///
+/// return `(twist, swing)`
+pub fn twist_swing<Real>(q: &[Real; 4], axis: &[Real; 3]) -> ([Real; 4], [Real; 4])
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let u = [q[0], q[1], q[2]];
+    let p = axis.scale(u.dot(axis));
+    let twist = [p[0], p[1], p[2], q[3]];
+    let sqlen = twist.iter().fold(Real::zero(), |acc, &v| acc + v * v);
+    let twist = if sqlen < Real::epsilon() * Real::epsilon() {
+        identity()
+    } else {
+        normalized(&twist)
+    };
+    let swing = mult_quaternion(q, &inverse(twist));
+    (twist, swing)
+}
+
+/// the rotation around the unit vector `axis` nearest to the unit quaternion `q`,
+/// which is the twist of [`twist_swing`]
+pub fn constrain_to_axis<Real>(q: &[Real; 4], axis: &[Real; 3]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    twist_swing(q, axis).0
+}
+
+#[test]
+fn test_twist_swing() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..100 {
+        let axis: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let axis = axis.normalize();
+        let q = match i_iter {
+            0 => {
+                // swing by pi
+                let (x, _) = crate::vec3::basis_xy_from_basis_z(&axis);
+                around_axis(&x, std::f64::consts::PI)
+            }
+            _ => {
+                let aa: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+                from_axisangle(&aa)
+            }
+        };
+        let (twist, swing) = twist_swing(&q, &axis);
+        let q1 = mult_quaternion(&swing, &twist);
+        assert!((0..4).all(|i| (q[i] - q1[i]).abs() < 1.0e-10));
+        // the twist keeps the axis, the swing axis is orthogonal to the axis
+        assert!(crate::vec3::distance(&transform_vec(&twist, &axis), &axis) < 1.0e-10);
+        assert!([swing[0], swing[1], swing[2]].dot(&axis).abs() < 1.0e-10);
+        // the twist is the nearest rotation around the axis
+        let d0 = (0..4).fold(0f64, |acc, i| acc + q[i] * twist[i]).abs();
+        for t in [-0.1, 0.1] {
+            let q1 = mult_quaternion(&around_axis(&axis, t), &constrain_to_axis(&q, &axis));
+            let d1 = (0..4).fold(0f64, |acc, i| acc + q[i] * q1[i]).abs();
+            assert!(d1 <= d0 + 1.0e-12);
+        }
+    }
+}
+
+/// sample a unit quaternion uniformly on S^3, which corresponds to the uniform
+/// sampling of the rotation (Haar measure), with the subgroup algorithm of Shoemake,
+/// "Uniform random rotations", Graphics Gems III (1992).
+pub fn sample_uniform<Reng, T>(rng: &mut Reng) -> [T; 4]
+where
+    Reng: rand::Rng,
+    T: num_traits::Float + num_traits::FloatConst,
+    rand::distr::StandardUniform: rand::distr::Distribution<T>,
+{
+    let one = T::one();
+    let two_pi = T::PI() + T::PI();
+    let u0: T = rng.random();
+    let u1: T = rng.random();
+    let u2: T = rng.random();
+    let r0 = (one - u0).sqrt();
+    let r1 = u0.sqrt();
+    let t1 = two_pi * u1;
+    let t2 = two_pi * u2;
+    [r0 * t1.sin(), r0 * t1.cos(), r1 * t2.sin(), r1 * t2.cos()]
+}
+
+#[test]
+fn test_sample_uniform() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let num_sample = 10000;
+    // the second moment of the uniform distribution on S^3 is `I/4`
+    let mut m2 = [[0f64; 4]; 4];
+    for _iter in 0..num_sample {
+        let q: [f64; 4] = sample_uniform(&mut rng);
+        assert!((q.iter().map(|x| x * x).sum::<f64>() - 1.).abs() < 1.0e-12);
+        for i in 0..4 {
+            for j in 0..4 {
+                m2[i][j] += q[i] * q[j] / num_sample as f64;
+            }
+        }
+    }
+    for i in 0..4 {
+        for j in 0..4 {
+            let e = if i == j { 0.25 } else { 0. };
+            assert!((m2[i][j] - e).abs() < 0.02, "{m2:?}");
+        }
+    }
+}
+
+/// eigenvector of the largest eigenvalue of the 4x4 symmetric matrix with the cyclic Jacobi method
+fn largest_eigenvector_sym4<Real>(mut a: [[Real; 4]; 4]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    let mut v = [[zero; 4]; 4];
+    for i in 0..4 {
+        v[i][i] = one;
+    }
+    for _itr in 0..20 {
+        let off = (0..4).fold(zero, |acc, i| {
+            acc + (0..4)
+                .filter(|&j| j != i)
+                .fold(zero, |acc, j| acc + a[i][j] * a[i][j])
+        });
+        if off < Real::min_positive_value() {
+            break;
+        }
+        for p in 0..4 {
+            for q in p + 1..4 {
+                if a[p][q] == zero {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                let t = one / (theta.abs() + (theta * theta + one).sqrt());
+                let t = if theta < zero { -t } else { t };
+                let c = one / (t * t + one).sqrt();
+                let s = t * c;
+                for k in 0..4 {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = c * akp - s * akq;
+                    a[k][q] = s * akp + c * akq;
+                }
+                for k in 0..4 {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for k in 0..4 {
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = c * vkp - s * vkq;
+                    v[k][q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    let i_max = (1..4).fold(
+        0,
+        |i_max, i| if a[i][i] > a[i_max][i_max] { i } else { i_max },
+    );
+    [v[0][i_max], v[1][i_max], v[2][i_max], v[3][i_max]]
+}
+
+/// weighted average of the unit quaternions with the method of Markley et al. (2007),
+/// "Averaging Quaternions". The average is the eigenvector of the largest eigenvalue
+/// of `sum_i w_i * q_i * q_i^T`, which is invariant to the sign of each quaternion.
+/// The output is in the same hemisphere as the first quaternion.
+pub fn average<Real>(quats: &[[Real; 4]], weights: Option<&[Real]>) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    assert!(!quats.is_empty());
+    if let Some(weights) = weights {
+        assert_eq!(quats.len(), weights.len());
+    }
+    let mut m = [[Real::zero(); 4]; 4];
+    for (i_quat, q) in quats.iter().enumerate() {
+        let w = weights.map_or(Real::one(), |weights| weights[i_quat]);
+        for i in 0..4 {
+            for j in 0..4 {
+                m[i][j] = m[i][j] + w * q[i] * q[j];
+            }
+        }
+    }
+    let q = normalized(&largest_eigenvector_sym4(m));
+    let dot = (0..4).fold(Real::zero(), |acc, i| acc + q[i] * quats[0][i]);
+    if dot < Real::zero() { q.map(|v| -v) } else { q }
+}
+
+#[test]
+fn test_average() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let dist =
+        |a: &[f64; 4], b: &[f64; 4]| (0..4).fold(0f64, |acc, i| acc.max((a[i] - b[i]).abs()));
+    for _itr in 0..100 {
+        let q0: [f64; 4] = sample_uniform(&mut rng);
+        // single quaternion with the flipped signs
+        let q1 = average(&[q0, q0.map(|v| -v), q0], None);
+        assert!(dist(&q0, &q1) < 1.0e-10);
+        // rotations around the same axis
+        let axis: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let axis = axis.normalize();
+        let qs = [0.1, 0.3, 0.5].map(|t| mult_quaternion(&around_axis(&axis, t), &q0));
+        let q2 = average(&qs, Some(&[1.0, 1.0, 1.0]));
+        let q3 = mult_quaternion(&around_axis(&axis, 0.3), &q0);
+        assert!(dist(&q2, &q3) < 1.0e-10);
+        // weights
+        let q4 = average(&qs[0..2], Some(&[1.0, 0.0]));
+        assert!(dist(&q4, &qs[0]) < 1.0e-10);
+        // the weighted average is between the two
+        let q5 = average(&[qs[0], qs[2].map(|v| -v)], Some(&[0.3, 0.7]));
+        let d0 = (0..4).fold(0f64, |acc, i| acc + q5[i] * qs[0][i]).abs();
+        let d2 = (0..4).fold(0f64, |acc, i| acc + q5[i] * qs[2][i]).abs();
+        assert!(d2 > d0);
+    }
+}
+
+/// update the orientation `q` by rotating with the constant angular velocity `omega`
+/// (in the world coordinate) for the time step `dt` using the exponential map, i.e.,
+/// `exp(omega * dt / 2) * q`. The output is normalized to suppress the drift.
+pub fn integrate_angular_velocity<Real>(q: &[Real; 4], omega: &[Real; 3], dt: Real) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let half_dt = dt / (Real::one() + Real::one());
+    let dq = exp(&omega.map(|v| v * half_dt));
+    normalized(&mult_quaternion(&dq, q))
+}
+
+/// constant angular velocity (in the world coordinate) that rotates `q0` to `q1` in the
+/// time `dt` along the shorter arc. This is the inverse of [`integrate_angular_velocity`].
+pub fn angular_velocity_from_pair<Real>(q0: &[Real; 4], q1: &[Real; 4], dt: Real) -> [Real; 3]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    let dq = mult_quaternion(q1, &inverse(*q0));
+    let dq = if dq[3] < Real::zero() {
+        dq.map(|v| -v)
+    } else {
+        dq
+    };
+    let two = Real::one() + Real::one();
+    log(&dq).map(|v| v * two / dt)
+}
+
+#[test]
+fn test_angular_velocity() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let q0: [f64; 4] = sample_uniform(&mut rng);
+        let omega: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let dt = 0.1;
+        let q1 = integrate_angular_velocity(&q0, &omega, dt);
+        let omega1 = angular_velocity_from_pair(&q0, &q1, dt);
+        assert!(crate::vec3::distance(&omega, &omega1) < 1.0e-10);
+        // the time derivative of the quaternion is `[omega, 0] * q / 2`
+        let eps = 1.0e-6;
+        let q2 = integrate_angular_velocity(&q0, &omega, eps);
+        let dqdt = mult_quaternion(&[omega[0], omega[1], omega[2], 0.], &q0).map(|v| v * 0.5);
+        assert!((0..4).all(|i| ((q2[i] - q0[i]) / eps - dqdt[i]).abs() < 1.0e-5));
+        // the rotation matrix follows `dR/dt = [omega]x * R`
+        let r0 = to_mat3_col_major(&q0);
+        let r2 = to_mat3_col_major(&q2);
+        let drdt = crate::mat3_col_major::mult_mat_col_major(
+            &crate::mat3_col_major::from_vec3_to_skew_mat(&omega),
+            &r0,
+        );
+        assert!((0..9).all(|i| ((r2[i] - r0[i]) / eps - drdt[i]).abs() < 1.0e-5));
+    }
+}
+
+/// uniform cubic B-spline on the unit quaternions in the cumulative form by
+/// Kim et al. (1995), "A General Construction Scheme for Unit Quaternion Curves with Simple
+/// High Order Derivatives". The curve is C2-continuous and lies between `q[1]` and `q[2]` for
+/// `t` in `[0, 1]`. The consecutive control points are connected along the shorter arc.
+pub fn cubic_bspline<Real>(q: &[[Real; 4]; 4], t: Real) -> [Real; 4]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    let one = Real::one();
+    let three = Real::from(3).unwrap();
+    let six = Real::from(6).unwrap();
+    let (t2, t3) = (t * t, t * t * t);
+    // cumulative basis functions
+    let b = [
+        (Real::from(5).unwrap() + three * t - three * t2 + t3) / six,
+        (one + three * t + three * t2 - (one + one) * t3) / six,
+        t3 / six,
+    ];
+    let mut res = q[0];
+    for j in 1..4 {
+        let dq = mult_quaternion(&inverse(q[j - 1]), &q[j]);
+        let dq = if dq[3] < Real::zero() {
+            dq.map(|v| -v)
+        } else {
+            dq
+        };
+        let omega = log(&dq);
+        res = mult_quaternion(&res, &exp(&omega.map(|v| v * b[j - 1])));
+    }
+    normalized(&res)
+}
+
+#[test]
+fn test_cubic_bspline() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let dist =
+        |a: &[f64; 4], b: &[f64; 4]| (0..4).fold(0f64, |acc, i| acc.max((a[i] - b[i]).abs()));
+    for _itr in 0..100 {
+        // rotations around the same axis follow the scalar B-spline of the angles
+        let axis: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let axis = axis.normalize();
+        let angles: [f64; 4] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let q = angles.map(|a| around_axis(&axis, a));
+        let t = rng.random::<f64>();
+        let b = [
+            (1. - t).powi(3) / 6.,
+            (3. * t.powi(3) - 6. * t * t + 4.) / 6.,
+            (-3. * t.powi(3) + 3. * t * t + 3. * t + 1.) / 6.,
+            t.powi(3) / 6.,
+        ];
+        let angle = (0..4).fold(0., |acc, i| acc + b[i] * angles[i]);
+        let q0 = cubic_bspline(&q, t);
+        let q1 = around_axis(&axis, angle);
+        assert!(dist(&q0, &q1) < 1.0e-10);
+        // continuity across the segments
+        let q: [[f64; 4]; 5] = std::array::from_fn(|_| sample_uniform(&mut rng));
+        let c0 = cubic_bspline(&[q[0], q[1], q[2], q[3]], 1.0);
+        let c1 = cubic_bspline(&[q[1], q[2], q[3], q[4]], 0.0);
+        let d0 = dist(&c0, &c1).min(dist(&c0, &c1.map(|v| -v)));
+        assert!(d0 < 1.0e-10);
+        let eps = 1.0e-5;
+        let v0 = angular_velocity_from_pair(
+            &cubic_bspline(&[q[0], q[1], q[2], q[3]], 1.0 - eps),
+            &c0,
+            eps,
+        );
+        let v1 =
+            angular_velocity_from_pair(&c1, &cubic_bspline(&[q[1], q[2], q[3], q[4]], eps), eps);
+        assert!(crate::vec3::distance(&v0, &v1) < 1.0e-3 * (1. + v0.norm()));
+    }
+}
+
+/// angle (radian) of the rotation between the unit quaternions `a` and `b` in `[0, pi]`.
+/// `q` and `-q` are regarded as the same rotation.
+pub fn geodesic_angle<Real>(a: &[Real; 4], b: &[Real; 4]) -> Real
+where
+    Real: num_traits::Float,
+{
+    let d = mult_quaternion(&inverse(*a), b);
+    let s = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+    (s.atan2(d[3].abs())) * (Real::one() + Real::one())
+}
+
+/// the quaternion of the same rotation in the hemisphere `w >= 0`.
+/// If `w == 0`, the first non-zero imaginary component is made positive.
+pub fn canonicalize<Real>(q: &[Real; 4]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let key = [q[3], q[0], q[1], q[2]]
+        .into_iter()
+        .find(|&v| v != zero)
+        .unwrap_or(zero);
+    if key < zero { q.map(|v| -v) } else { *q }
+}
+
+/// flip the signs of the quaternions in the sequence so that
+/// each quaternion is in the same hemisphere as the previous one
+/// (e.g., before the interpolation or the averaging)
+pub fn make_hemisphere_consistent<Real>(quats: &mut [[Real; 4]])
+where
+    Real: num_traits::Float,
+{
+    for i in 1..quats.len() {
+        let dot = (0..4).fold(Real::zero(), |acc, k| acc + quats[i - 1][k] * quats[i][k]);
+        if dot < Real::zero() {
+            quats[i] = quats[i].map(|v| -v);
+        }
+    }
+}
+
+/// the unit quaternions represent the rotations closer than `angle_tolerance` (radian)
+pub fn are_close<Real>(a: &[Real; 4], b: &[Real; 4], angle_tolerance: Real) -> bool
+where
+    Real: num_traits::Float,
+{
+    geodesic_angle(a, b) <= angle_tolerance
+}
+
+#[test]
+fn test_geodesic_angle_and_canonicalize() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let q0: [f64; 4] = sample_uniform(&mut rng);
+        let axis: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let angle = 3.0 * rng.random::<f64>();
+        let q1 = mult_quaternion(&around_axis(&axis, angle), &q0);
+        assert!((geodesic_angle(&q0, &q1) - angle).abs() < 1.0e-10);
+        assert!((geodesic_angle(&q0, &q1.map(|v| -v)) - angle).abs() < 1.0e-10);
+        assert!(are_close(&q0, &q1.map(|v| -v), angle + 1.0e-10));
+        assert!(!are_close(&q0, &q1, angle - 1.0e-6));
+        let c = canonicalize(&q0.map(|v| -v));
+        assert!(c[3] >= 0. && are_close(&c, &q0, 1.0e-12));
+        assert_eq!(canonicalize(&c), c);
+    }
+    assert_eq!(canonicalize(&[-1f64, 0., 0., 0.]), [1., 0., 0., 0.]);
+    let mut quats = [[0f64, 0., 0., 1.], [0., 0., 0.1, -1.], [0., 0., 0.2, 1.]];
+    make_hemisphere_consistent(&mut quats);
+    assert!(quats.iter().all(|q| q[3] > 0.));
+    assert_eq!(quats[1][2], -0.1);
+}
+
+/// quaternion from Euler angles `(a, b, c)` in the convention `order`
+pub fn from_euler<Real>(
+    order: crate::mat3_col_major::EulerOrder,
+    a: Real,
+    b: Real,
+    c: Real,
+) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    use crate::mat3_col_major::EulerOrder;
+    let (axes, is_extrinsic) = match order {
+        EulerOrder::Extrinsic(axes) => (axes.to_indices(), true),
+        EulerOrder::Intrinsic(axes) => (axes.to_indices(), false),
+    };
+    let half = Real::one() / (Real::one() + Real::one());
+    let around = |i_axis: usize, theta: Real| {
+        let mut q = [
+            Real::zero(),
+            Real::zero(),
+            Real::zero(),
+            (theta * half).cos(),
+        ];
+        q[i_axis] = (theta * half).sin();
+        q
+    };
+    let qa = around(axes[0], a);
+    let qb = around(axes[1], b);
+    let qc = around(axes[2], c);
+    if is_extrinsic {
+        mult_quaternion(&qc, &mult_quaternion(&qb, &qa))
+    } else {
+        mult_quaternion(&qa, &mult_quaternion(&qb, &qc))
+    }
+}
+
+/// Euler angles `[a, b, c]` in the convention `order` from a unit quaternion.
+///
+/// The first and last angles are in `[-pi, pi]`. The second angle is in `[0, pi]` for proper
+/// Euler angles and in `[-pi/2, pi/2]` for Tait-Bryan angles. In the gimbal lock
+/// configuration, the third (extrinsic) or the first (intrinsic) angle is set to zero.
+///
+/// # Reference
+/// Bernardes, E., & Viollet, S. (2022). "Quaternion to Euler angles conversion:
+/// A direct, general and computationally efficient method". PLoS ONE 17(11).
+pub fn to_euler<Real>(q: &[Real; 4], order: crate::mat3_col_major::EulerOrder) -> [Real; 3]
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    use crate::mat3_col_major::EulerOrder;
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    let (axes, is_extrinsic) = match order {
+        EulerOrder::Extrinsic(axes) => (axes.to_indices(), true),
+        EulerOrder::Intrinsic(axes) => (axes.to_indices(), false),
+    };
+    // the algorithm is for the extrinsic rotations. Reverse the sequence for the intrinsic ones.
+    let (i, j, k) = if is_extrinsic {
+        (axes[0], axes[1], axes[2])
+    } else {
+        (axes[2], axes[1], axes[0])
+    };
+    let is_proper = i == k;
+    let k = if is_proper { 3 - i - j } else { k };
+    // parity of the permutation (i,j,k)
+    let sign = {
+        let (i, j, k) = (i as i64, j as i64, k as i64);
+        Real::from((i - j) * (j - k) * (k - i) / 2).unwrap()
+    };
+    let (a, b, c, d) = if is_proper {
+        (q[3], q[i], q[j], q[k] * sign)
+    } else {
+        (
+            q[3] - q[j],
+            q[i] + q[k] * sign,
+            q[j] + q[3],
+            q[k] * sign - q[i],
+        )
+    };
+    let mut angles = [zero; 3];
+    angles[1] = two * c.hypot(d).atan2(a.hypot(b));
+    let eps = Real::from(1.0e-7).unwrap();
+    let half_sum = b.atan2(a);
+    let half_diff = d.atan2(c);
+    // in the gimbal lock configuration, the third (extrinsic) or the first (intrinsic) angle is zero
+    let i_free = if is_extrinsic { 0 } else { 2 };
+    if angles[1].abs() <= eps {
+        angles[i_free] = two * half_sum;
+    } else if (angles[1] - Real::PI()).abs() <= eps {
+        angles[i_free] = if is_extrinsic {
+            -two * half_diff
+        } else {
+            two * half_diff
+        };
+    } else {
+        angles[0] = half_sum - half_diff;
+        angles[2] = half_sum + half_diff;
+    }
+    if !is_proper {
+        angles[2] = angles[2] * sign;
+        angles[1] = angles[1] - Real::FRAC_PI_2();
+    }
+    if !is_extrinsic {
+        angles.swap(0, 2);
+    }
+    angles.map(|x| {
+        if x < -Real::PI() {
+            x + two * Real::PI()
+        } else if x > Real::PI() {
+            x - two * Real::PI()
+        } else {
+            x
+        }
+    })
+}
+
+#[test]
+fn test_euler() {
+    use crate::mat3_col_major::{EulerAxes, EulerOrder};
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for (axes, i_kind) in itertools::iproduct!(
+        [
+            EulerAxes::XYZ,
+            EulerAxes::ZYX,
+            EulerAxes::ZXZ,
+            EulerAxes::YXY
+        ],
+        0..2
+    ) {
+        let order = match i_kind {
+            0 => EulerOrder::Extrinsic(axes),
+            1 => EulerOrder::Intrinsic(axes),
+            _ => unreachable!(),
+        };
+        for _iter in 0..100 {
+            let angles: [f64; 3] = std::array::from_fn(|_| rng.random_range(-1.5f64..1.5f64));
+            let q0 = from_euler(order, angles[0], angles[1], angles[2]);
+            let m0 = crate::mat3_col_major::from_euler(order, angles[0], angles[1], angles[2]);
+            let m1 = q0.to_mat3_col_major();
+            assert!(
+                crate::mat3_col_major::sub(&m0, &m1)
+                    .iter()
+                    .all(|v| v.abs() < 1.0e-12)
+            );
+            let angles1 = to_euler(&q0, order);
+            let q1 = from_euler(order, angles1[0], angles1[1], angles1[2]);
+            let d = (0..4).fold(0f64, |acc, i| acc + (q0[i] - q1[i]).powi(2));
+            let e = (0..4).fold(0f64, |acc, i| acc + (q0[i] + q1[i]).powi(2));
+            assert!(d.min(e) < 1.0e-20, "{order:?} {angles:?} {angles1:?}");
+        }
+    }
+}
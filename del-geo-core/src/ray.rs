@@ -0,0 +1,239 @@
+//! ray `org + t * dir` for `t` in the range `[t_min, t_max]`.
+//! The functions taking `(ray_org, ray_dir)` (e.g., [`crate::aabb::intersections_against_ray`])
+//! can be called through the methods of [`Ray`] so that the origin and the direction are not mixed up.
+//! For the ray with the precomputed reciprocal direction, see [`crate::aabb::PrecomputedRay`]
+//! (aliased as [`crate::aabb3::PrecomputedRay3`]).
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Ray<Real, const NDIM: usize> {
+    pub org: [Real; NDIM],
+    /// direction (general non-zero vector, not necessarily unitary)
+    pub dir: [Real; NDIM],
+    pub t_min: Real,
+    pub t_max: Real,
+}
+
+pub type Ray2<Real> = Ray<Real, 2>;
+pub type Ray3<Real> = Ray<Real, 3>;
+
+// SAFETY: `repr(C)` with the fields of the same type without padding
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Zeroable, const NDIM: usize> bytemuck::Zeroable for Ray<Real, NDIM> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: bytemuck::Pod, const NDIM: usize> bytemuck::Pod for Ray<Real, NDIM> {}
+
+impl<Real, const NDIM: usize> Ray<Real, NDIM>
+where
+    Real: num_traits::Float,
+{
+    /// ray for `t` in `[0, inf)`
+    pub fn new(org: &[Real; NDIM], dir: &[Real; NDIM]) -> Self {
+        Self {
+            org: *org,
+            dir: *dir,
+            t_min: Real::zero(),
+            t_max: Real::infinity(),
+        }
+    }
+
+    /// the same ray with the range `[t_min, t_max]`
+    pub fn with_range(&self, t_min: Real, t_max: Real) -> Self {
+        Self {
+            t_min,
+            t_max,
+            ..*self
+        }
+    }
+
+    /// the point at the ray parameter `t`
+    pub fn at(&self, t: Real) -> [Real; NDIM] {
+        std::array::from_fn(|i| self.org[i] + self.dir[i] * t)
+    }
+
+    pub fn is_in_range(&self, t: Real) -> bool {
+        self.t_min <= t && t <= self.t_max
+    }
+
+    /// see [`crate::aabb::intersections_against_line`].
+    /// The returned range is clipped by the range of the ray.
+    pub fn intersections_against_aabb<const SIZE_AABB: usize>(
+        &self,
+        aabb: &[Real; SIZE_AABB],
+    ) -> Option<(Real, Real)> {
+        let (t0, t1) = crate::aabb::intersections_against_line::<Real, NDIM, SIZE_AABB>(
+            aabb, &self.org, &self.dir,
+        )?;
+        let (t0, t1) = (t0.max(self.t_min), t1.min(self.t_max));
+        if t0 <= t1 { Some((t0, t1)) } else { None }
+    }
+}
+
+impl<Real, const NDIM: usize> From<&Ray<Real, NDIM>> for crate::aabb::PrecomputedRay<Real, NDIM>
+where
+    Real: num_traits::Float,
+{
+    fn from(ray: &Ray<Real, NDIM>) -> Self {
+        crate::aabb::PrecomputedRay::new(&ray.org, &ray.dir)
+    }
+}
+
+impl<Real> Ray<Real, 2>
+where
+    Real: num_traits::Float,
+{
+    /// transform by the affine transformation of the column major 3x3 matrix.
+    /// The ray parameter is preserved.
+    pub fn transform_by_mat3(&self, m: &[Real; 9]) -> Option<Self> {
+        Some(Self {
+            org: crate::mat3_col_major::transform_homogeneous(m, &self.org)?,
+            // linear part only (`mat3_col_major::transform_direction` adds the translation)
+            dir: [
+                m[0] * self.dir[0] + m[3] * self.dir[1],
+                m[1] * self.dir[0] + m[4] * self.dir[1],
+            ],
+            ..*self
+        })
+    }
+}
+
+impl<Real> Ray<Real, 3>
+where
+    Real: num_traits::Float,
+{
+    /// transform by the affine transformation of the column major 4x4 matrix.
+    /// The ray parameter is preserved.
+    pub fn transform_by_mat4(&self, m: &[Real; 16]) -> Option<Self> {
+        Some(Self {
+            org: crate::mat4_col_major::transform_homogeneous(m, &self.org)?,
+            dir: crate::mat4_col_major::transform_direction(m, &self.dir),
+            ..*self
+        })
+    }
+
+    /// transform by the rigid transformation (see [`crate::isometry3`])
+    pub fn transform_by_isometry(&self, iso: &[Real; 7]) -> Self {
+        Self {
+            org: crate::isometry3::transform_point(iso, &self.org),
+            dir: crate::isometry3::transform_vector(iso, &self.dir),
+            ..*self
+        }
+    }
+
+    /// see [`crate::obb3::intersections_against_line`].
+    /// The returned range is clipped by the range of the ray.
+    pub fn intersections_against_obb3(&self, obb: &[Real; 12]) -> Option<(Real, Real)> {
+        let (t0, t1) = crate::obb3::intersections_against_line(obb, &self.org, &self.dir)?;
+        let (t0, t1) = (t0.max(self.t_min), t1.min(self.t_max));
+        if t0 <= t1 { Some((t0, t1)) } else { None }
+    }
+
+    /// the first intersection with the sphere in the range. See [`crate::sphere::intersection_ray`]
+    /// * `t_min` of the ray needs to be finite
+    pub fn intersection_against_sphere(&self, rad: Real, center: &[Real; 3]) -> Option<Real> {
+        let t = crate::sphere::intersection_ray(rad, center, &self.at(self.t_min), &self.dir)?;
+        Some(t + self.t_min).filter(|&t| t <= self.t_max)
+    }
+
+    /// see [`crate::tri3::intersection_against_line`]
+    pub fn intersection_against_tri3(
+        &self,
+        p0: &[Real; 3],
+        p1: &[Real; 3],
+        p2: &[Real; 3],
+    ) -> Option<Real> {
+        crate::tri3::intersection_against_line(p0, p1, p2, &self.org, &self.dir)
+            .filter(|&t| self.is_in_range(t))
+    }
+}
+
+#[test]
+fn test_ray3() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let (org, dir) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let ray = Ray3::new(&org, &dir);
+        assert_eq!(ray.at(0.), org);
+        // the same as the functions taking the pair of the origin and the direction
+        let aabb = [-0.5, -0.5, -0.5, 0.5, 0.5, 0.5];
+        assert_eq!(
+            ray.intersections_against_aabb(&aabb),
+            crate::aabb::intersections_against_ray::<f64, 3, 6>(&aabb, &org, &dir)
+                .map(|(t0, t1)| (t0.max(0.), t1))
+        );
+        assert_eq!(
+            ray.intersection_against_sphere(0.5, &[0.; 3]),
+            crate::sphere::intersection_ray(0.5, &[0.; 3], &org, &dir)
+        );
+        // the range of the ray
+        let ray = ray.with_range(1., 2.);
+        if let Some((t0, t1)) = ray.intersections_against_aabb(&aabb) {
+            assert!(1. <= t0 && t0 <= t1 && t1 <= 2.);
+            let p = ray.at((t0 + t1) * 0.5);
+            assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &p) < 1.0e-20);
+        }
+        if let Some(t) = ray.intersection_against_sphere(0.5, &[0.; 3]) {
+            assert!(ray.is_in_range(t));
+            assert!((ray.at(t).norm() - 0.5).abs() < 1.0e-10);
+        }
+        let obb = [0., 0., 0., 0.5, 0., 0., 0., 0.5, 0., 0., 0., 0.5];
+        let res0 = ray.intersections_against_obb3(&obb);
+        let res1 = ray.intersections_against_aabb(&aabb);
+        match (res0, res1) {
+            (Some((t0, t1)), Some((s0, s1))) => {
+                assert!((t0 - s0).abs() < 1.0e-10 && (t1 - s1).abs() < 1.0e-10)
+            }
+            (None, None) => {}
+            _ => panic!("{res0:?} {res1:?}"),
+        }
+        // transformation preserves the ray parameter
+        let v = crate::vec3::sample_centered_cube::<_, f64>(&mut rng);
+        let q = crate::quaternion::normalized(&[
+            v[0],
+            v[1],
+            v[2],
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0],
+        ]);
+        let iso = crate::isometry3::from_quaternion_and_translation(
+            &q,
+            &crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        let ray1 = ray.transform_by_isometry(&iso);
+        let p = crate::isometry3::transform_point(&iso, &ray.at(1.5));
+        assert!(ray1.at(1.5).sub(&p).norm() < 1.0e-10);
+        let ray2 = ray
+            .transform_by_mat4(&crate::isometry3::to_mat4_col_major(&iso))
+            .unwrap();
+        assert!(ray2.at(1.5).sub(&p).norm() < 1.0e-10);
+        assert_eq!((ray2.t_min, ray2.t_max), (1., 2.));
+    }
+    let ray = Ray3::new(&[0.2, 0.2, 1.], &[0., 0., -1.]);
+    let tri = ([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+    assert_eq!(
+        ray.intersection_against_tri3(&tri.0, &tri.1, &tri.2),
+        Some(1.)
+    );
+    let ray = ray.with_range(0., 0.5);
+    assert_eq!(ray.intersection_against_tri3(&tri.0, &tri.1, &tri.2), None);
+    let pray: crate::aabb3::PrecomputedRay3<f64> = (&ray).into();
+    assert_eq!(pray.inv_dir[2], -1.);
+}
+
+#[test]
+fn test_ray2() {
+    let ray = Ray2::new(&[-1., 0.5], &[2., 0.]);
+    assert_eq!(ray.at(0.5), [0., 0.5]);
+    assert_eq!(
+        ray.intersections_against_aabb(&[0., 0., 1., 1.]),
+        Some((0.5, 1.))
+    );
+    // translation by (1, 2)
+    let m = [1., 0., 0., 0., 1., 0., 1., 2., 1.];
+    let ray1 = ray.transform_by_mat3(&m).unwrap();
+    assert_eq!(ray1.at(0.5), [1., 2.5]);
+}
@@ -0,0 +1,74 @@
+//! eight 3D rays in the SoA (structure of arrays) layout for the batched computation.
+//! See [`crate::vec3x8`] for the layout.
+
+use crate::vec3x8::{LANE, Vec3x8};
+
+/// eight rays where the `i`-th lane is the ray `origin.lane(i) + t * direction.lane(i)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Ray3x8 {
+    pub origin: Vec3x8,
+    pub direction: Vec3x8,
+}
+
+// SAFETY: `repr(C)` with the fields of `Vec3x8` without padding
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Ray3x8 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Ray3x8 {}
+
+impl Ray3x8 {
+    pub fn new(origin: Vec3x8, direction: Vec3x8) -> Self {
+        Self { origin, direction }
+    }
+
+    /// the same ray in all the lanes
+    pub fn splat(origin: &[f32; 3], direction: &[f32; 3]) -> Self {
+        Self {
+            origin: Vec3x8::splat(origin),
+            direction: Vec3x8::splat(direction),
+        }
+    }
+
+    /// the point at the ray parameter `t` for each lane
+    pub fn position_at(&self, t: &[f32; LANE]) -> Vec3x8 {
+        self.direction.scale_add(t, &self.origin)
+    }
+
+    /// ray parameter of the point on the ray nearest to `p` for each lane.
+    /// The parameter is not clamped to be non-negative.
+    pub fn nearest_param_to_point3(&self, p: &Vec3x8) -> [f32; LANE] {
+        let a = self.direction.squared_norm();
+        let b = self.direction.dot(&p.sub(&self.origin));
+        std::array::from_fn(|i| b[i] / a[i])
+    }
+}
+
+#[test]
+fn test_ray3x8() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_vecs = || -> [[f32; 3]; LANE] {
+        std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.))
+    };
+    let (org, dir, p) = (random_vecs(), random_vecs(), random_vecs());
+    let ray = Ray3x8::new(Vec3x8::from_array(&org), Vec3x8::from_array(&dir));
+    let t = ray.nearest_param_to_point3(&Vec3x8::from_array(&p));
+    let q = ray.position_at(&t);
+    for i in 0..LANE {
+        assert!(q.lane(i).sub(&org[i]).cross(&dir[i]).norm() < 1.0e-5);
+        assert!(q.lane(i).sub(&p[i]).dot(&dir[i]).abs() < 1.0e-5);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck() {
+    let ray = Ray3x8::splat(&[1., 2., 3.], &[4., 5., 6.]);
+    assert_eq!(bytemuck::bytes_of(&ray).len(), 6 * LANE * 4);
+    let s: &[f32] = bytemuck::cast_slice(std::slice::from_ref(&ray));
+    assert_eq!(&s[LANE..LANE + 2], &[2., 2.]);
+    assert_eq!(s[5 * LANE], 6.);
+}
@@ -0,0 +1,134 @@
+//! methods for 2D rotation stored as the unit complex number `[cos, sin]`
+
+pub fn from_identity<Real>() -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [Real::one(), Real::zero()]
+}
+
+/// counter-clockwise rotation with the angle in radian
+pub fn from_angle<Real>(theta: Real) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let (s, c) = theta.sin_cos();
+    [c, s]
+}
+
+/// the rotation that rotates the direction of `a` to that of `b`.
+/// The vectors do not need to be normalized but need to be non-zero.
+pub fn from_two_vecs<Real>(a: &[Real; 2], b: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    // a^* * b
+    normalized(&[a[0] * b[0] + a[1] * b[1], a[0] * b[1] - a[1] * b[0]])
+}
+
+// above: from methods
+// ---------------------------------
+
+/// angle in `[-pi, pi]`
+pub fn to_angle<Real>(r: &[Real; 2]) -> Real
+where
+    Real: num_traits::Float,
+{
+    r[1].atan2(r[0])
+}
+
+pub fn to_mat2_col_major<Real>(r: &[Real; 2]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    [r[0], r[1], -r[1], r[0]]
+}
+
+/// 3x3 homogeneous matrix for the 2D transformation
+pub fn to_mat3_col_major<Real>(r: &[Real; 2]) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    [r[0], r[1], zero, -r[1], r[0], zero, zero, zero, Real::one()]
+}
+
+// above: to methods
+// ---------------------------------
+
+pub fn normalized<Real>(r: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let l = r[0].hypot(r[1]);
+    [r[0] / l, r[1] / l]
+}
+
+/// the rotation `a * b` that applies `b` first then `a`
+pub fn compose<Real>(a: &[Real; 2], b: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [a[0] * b[0] - a[1] * b[1], a[0] * b[1] + a[1] * b[0]]
+}
+
+pub fn inverse<Real>(r: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [r[0], -r[1]]
+}
+
+pub fn rotate_vec2<Real>(r: &[Real; 2], v: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    [r[0] * v[0] - r[1] * v[1], r[1] * v[0] + r[0] * v[1]]
+}
+
+/// interpolation with the constant angular velocity along the shorter arc
+pub fn slerp<Real>(a: &[Real; 2], b: &[Real; 2], t: Real) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let theta = to_angle(&compose(&inverse(a), b));
+    compose(a, &from_angle(theta * t))
+}
+
+#[test]
+fn test_rot2() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let dist = |a: &[f64; 2], b: &[f64; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+    for _itr in 0..100 {
+        let ta = 6. * rng.random::<f64>() - 3.;
+        let tb = 6. * rng.random::<f64>() - 3.;
+        let (a, b) = (from_angle(ta), from_angle(tb));
+        assert!((to_angle(&a) - ta).abs() < 1.0e-10);
+        let v = [0.3, -0.8];
+        {
+            let v0 = rotate_vec2(&a, &v);
+            let v1 = crate::mat2_col_major::mult_vec(&to_mat2_col_major(&a), &v);
+            let v2 = crate::mat3_col_major::transform_homogeneous(&to_mat3_col_major(&a), &v);
+            assert!(dist(&v0, &v1) < 1.0e-10);
+            assert!(dist(&v0, &v2.unwrap()) < 1.0e-10);
+            assert!(dist(&v0, &crate::vec2::rotate(&v, ta)) < 1.0e-10);
+        }
+        assert!(dist(&compose(&a, &b), &from_angle(ta + tb)) < 1.0e-10);
+        assert!(dist(&compose(&a, &inverse(&a)), &from_identity()) < 1.0e-10);
+        {
+            let r = from_two_vecs(&v, &rotate_vec2(&a, &v).map(|x| x * 2.));
+            assert!(dist(&r, &a) < 1.0e-10);
+        }
+        {
+            let r0 = slerp(&a, &b, 0.0);
+            let r1 = slerp(&a, &b, 1.0);
+            assert!(dist(&r0, &a) < 1.0e-10 && dist(&r1, &b) < 1.0e-10);
+            // the angle of the half-way rotation
+            let d = to_angle(&compose(&inverse(&a), &slerp(&a, &b, 0.5)));
+            let d0 = to_angle(&compose(&inverse(&a), &b));
+            assert!((2. * d - d0).abs() < 1.0e-10);
+        }
+    }
+}
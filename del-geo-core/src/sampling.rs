@@ -0,0 +1,130 @@
+//! mappings from the uniform random numbers in `[0,1)^2` to the samples on the primitives.
+//! Each function returns the sample and its probability density function (PDF)
+//! with respect to the area (or the solid angle for the directions).
+
+/// uniform sample on the unit sphere
+/// # Returns
+/// (direction, pdf) where the pdf is `1/(4pi)`
+pub fn unit_sphere_surface<T>(unirand: &[T; 2]) -> ([T; 3], T)
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let one = T::one();
+    let four = one + one + one + one;
+    (
+        crate::sphere::sample_surface_uniform(unirand),
+        one / (four * T::PI()),
+    )
+}
+
+/// uniform sample in the unit disk using the concentric mapping of Shirley and Chiu (1997),
+/// "A Low Distortion Map Between Disk and Square"
+/// # Returns
+/// (point, pdf) where the pdf is `1/pi`
+pub fn unit_disk_concentric<T>(unirand: &[T; 2]) -> ([T; 2], T)
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+    let four = two + two;
+    let pdf = one / T::PI();
+    let a = two * unirand[0] - one;
+    let b = two * unirand[1] - one;
+    if a == zero && b == zero {
+        return ([zero, zero], pdf);
+    }
+    let (r, theta) = if a.abs() > b.abs() {
+        (a, T::PI() / four * (b / a))
+    } else {
+        (b, T::PI() / two - T::PI() / four * (a / b))
+    };
+    ([r * theta.cos(), r * theta.sin()], pdf)
+}
+
+/// cosine-weighted sample on the unit hemisphere around the direction `n` (no need to be unit vector)
+/// # Returns
+/// (direction, pdf) where the pdf is `cos(theta)/pi`
+pub fn unit_hemisphere_cosine_weighted<T>(n: &[T; 3], unirand: &[T; 2]) -> ([T; 3], T)
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    // Malley's method: project the uniform sample in the disk onto the hemisphere
+    let (d, _) = unit_disk_concentric(unirand);
+    let cos_theta = (T::one() - d[0] * d[0] - d[1] * d[1]).max(T::zero()).sqrt();
+    let mat3 = crate::mat3_col_major::transform_lcl2world_given_local_z(n);
+    let dir = crate::mat3_col_major::mult_vec(&mat3, &[d[0], d[1], cos_theta]);
+    (dir, cos_theta / T::PI())
+}
+
+/// uniform sample in the triangle
+/// # Returns
+/// (point, pdf) where the pdf is `1/area`
+pub fn triangle_uniform<T>(p0: &[T; 3], p1: &[T; 3], p2: &[T; 3], unirand: &[T; 2]) -> ([T; 3], T)
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let s = unirand[0].sqrt();
+    let r1 = s * (one - unirand[1]);
+    let r2 = s * unirand[1];
+    let p = crate::tri3::position_from_barycentric_coords(p0, p1, p2, &[one - r1 - r2, r1, r2]);
+    (p, one / crate::tri3::area(p0, p1, p2))
+}
+
+#[test]
+fn test_sampling() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let num_sample = 100000;
+    let n = [0.3, -0.5, 0.8].normalize();
+    let (p0, p1, p2) = ([0.1, 0.2, 0.3], [1.1, -0.3, 0.4], [0.5, 0.9, -0.2]);
+    let mut sphere_zz = 0.;
+    let mut disk_rr = 0.;
+    let mut hemisphere_cos = 0.;
+    let mut tri_center = [0.; 3];
+    for _itr in 0..num_sample {
+        let unirand: [f64; 2] = [rng.random(), rng.random()];
+        {
+            let (v, pdf) = unit_sphere_surface(&unirand);
+            assert!((v.norm() - 1.).abs() < 1.0e-10);
+            assert!((pdf * 4. * std::f64::consts::PI - 1.).abs() < 1.0e-10);
+            sphere_zz += v[2] * v[2];
+        }
+        {
+            let (d, pdf) = unit_disk_concentric(&unirand);
+            let rr = d[0] * d[0] + d[1] * d[1];
+            assert!(rr <= 1. + 1.0e-10);
+            assert!((pdf * std::f64::consts::PI - 1.).abs() < 1.0e-10);
+            disk_rr += rr;
+        }
+        {
+            let (v, pdf) = unit_hemisphere_cosine_weighted(&n, &unirand);
+            assert!((v.norm() - 1.).abs() < 1.0e-10);
+            let cos = v.dot(&n);
+            assert!(cos >= -1.0e-10);
+            assert!((pdf * std::f64::consts::PI - cos).abs() < 1.0e-10);
+            hemisphere_cos += cos;
+        }
+        {
+            let (p, pdf) = triangle_uniform(&p0, &p1, &p2, &unirand);
+            let bc = crate::tri3::to_barycentric_coords(&p0, &p1, &p2, &p);
+            assert!(bc.iter().all(|&r| r > -1.0e-10));
+            assert!((pdf * crate::tri3::area(&p0, &p1, &p2) - 1.).abs() < 1.0e-10);
+            tri_center.add_in_place(&p);
+        }
+    }
+    let num_sample = num_sample as f64;
+    // E[z^2] = 1/3 on the sphere
+    assert!((sphere_zz / num_sample - 1. / 3.).abs() < 1.0e-2);
+    // E[r^2] = 1/2 in the disk
+    assert!((disk_rr / num_sample - 0.5).abs() < 1.0e-2);
+    // E[cos] = 2/3 for the cosine-weighted hemisphere
+    assert!((hemisphere_cos / num_sample - 2. / 3.).abs() < 1.0e-2);
+    // the mean is the centroid for the triangle
+    let c = crate::vecn::add_three(&p0, &p1, &p2).scale(1. / 3.);
+    assert!(tri_center.scale(1. / num_sample).sub(&c).norm() < 1.0e-2);
+}
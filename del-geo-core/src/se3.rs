@@ -0,0 +1,101 @@
+//! methods for the Lie group SE(3) (3D rigid transformation).
+//! The twist (an element of the Lie algebra se(3)) is stored as `[v, w]` where
+//! `v` is the translational part and `w` is the rotational part (axis-angle vector).
+//! The rigid transformation is the isometry `[T;7]` of [`crate::isometry3`].
+
+/// exponential map from the twist to the rigid transformation
+/// (the unit quaternion and the translation)
+pub fn exp<T>(twist: &[T; 6]) -> ([T; 4], [T; 3])
+where
+    T: num_traits::Float,
+{
+    let v = [twist[0], twist[1], twist[2]];
+    let w = [twist[3], twist[4], twist[5]];
+    let q = crate::quaternion::from_axisangle(&w);
+    let t = crate::mat3_col_major::mult_vec(&crate::so3::left_jacobian(&w), &v);
+    (q, t)
+}
+
+/// logarithm map from the rigid transformation to the twist. The rotation angle is in `[0, pi]`.
+pub fn log<T>(isometry: &[T; 7]) -> [T; 6]
+where
+    T: num_traits::Float + num_traits::FloatConst,
+{
+    let two = T::one() + T::one();
+    let q = crate::isometry3::to_quaternion(isometry);
+    // take the hemisphere with the non-negative real part for the angle in `[0, pi]`
+    let q = if q[3] < T::zero() { q.map(|v| -v) } else { q };
+    let w = crate::quaternion::log(&q).map(|v| v * two);
+    let t = crate::isometry3::to_translation(isometry);
+    let v = crate::mat3_col_major::mult_vec(&crate::so3::left_jacobian_inverse(&w), &t);
+    [v[0], v[1], v[2], w[0], w[1], w[2]]
+}
+
+/// 6x6 adjoint matrix (column major) of the rigid transformation such that
+/// `T * exp(twist) * T^{-1} = exp(Ad_T * twist)`.
+/// The matrix is `[[R, [t]x * R], [0, R]]`
+pub fn adjoint<T>(isometry: &[T; 7]) -> [T; 36]
+where
+    T: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(&crate::isometry3::to_quaternion(isometry));
+    let t = crate::isometry3::to_translation(isometry);
+    let tr = crate::mat3_col_major::mult_mat_col_major(
+        &crate::mat3_col_major::from_vec3_to_skew_mat(&t),
+        &r,
+    );
+    let mut m = [T::zero(); 36];
+    for i in 0..3 {
+        for j in 0..3 {
+            m[i + j * 6] = r[i + j * 3];
+            m[(i + 3) + (j + 3) * 6] = r[i + j * 3];
+            m[i + (j + 3) * 6] = tr[i + j * 3];
+        }
+    }
+    m
+}
+
+#[test]
+fn test_se3() {
+    use crate::isometry3::Isometry3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_twist = |scale: f64| -> [f64; 6] {
+        std::array::from_fn(|_| scale * (2. * rng.random::<f64>() - 1.))
+    };
+    for i_iter in 0..100 {
+        let xi = match i_iter {
+            0 => [0.1, 0.2, -0.3, 0., 0., 0.],
+            1 => [0.1, 0.2, -0.3, 1.0e-9, 0., -2.0e-9],
+            _ => random_twist(1.5),
+        };
+        let (q, t) = exp(&xi);
+        let a = crate::isometry3::from_quaternion_and_translation(&q, &t);
+        let xi1 = log(&a);
+        assert!(
+            (0..6).all(|i| (xi[i] - xi1[i]).abs() < 1.0e-10),
+            "{xi:?} {xi1:?}"
+        );
+        // exp of the twist is the integration of the constant velocity over unit time
+        let n = 1000;
+        let (dq, dt) = exp(&xi.map(|v| v / n as f64));
+        let da = crate::isometry3::from_quaternion_and_translation(&dq, &dt);
+        let b = (0..n).fold(crate::isometry3::from_identity(), |b, _| da.compose(&b));
+        let (ma, mb) = (a.to_mat4_col_major(), b.to_mat4_col_major());
+        assert!((0..16).all(|i| (ma[i] - mb[i]).abs() < 1.0e-8));
+        // adjoint
+        let eta = random_twist(0.5);
+        let ad = adjoint(&a);
+        let ad_eta: [f64; 6] =
+            std::array::from_fn(|i| (0..6).map(|j| ad[i + j * 6] * eta[j]).sum());
+        let (q0, t0) = exp(&eta);
+        let c0 = a
+            .compose(&crate::isometry3::from_quaternion_and_translation(&q0, &t0))
+            .compose(&a.inverse());
+        let (q1, t1) = exp(&ad_eta);
+        let c1 = crate::isometry3::from_quaternion_and_translation(&q1, &t1);
+        let (mc0, mc1) = (c0.to_mat4_col_major(), c1.to_mat4_col_major());
+        assert!((0..16).all(|i| (mc0[i] - mc1[i]).abs() < 1.0e-10));
+    }
+}
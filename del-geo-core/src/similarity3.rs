@@ -0,0 +1,208 @@
+//! methods for the 3D similarity transformation (uniform scale, rotation and translation)
+//! stored as `[T;8]`. The first element is the scale, the next four elements are
+//! the unit quaternion `[i,j,k,w]` for the rotation and the last three elements are the translation.
+//! The transformation maps the point `p` to `s * R * p + t`.
+
+/// trait for 3D similarity transformation
+pub trait Similarity3<Real>
+where
+    Self: Sized,
+{
+    fn to_scale(&self) -> Real;
+    fn to_quaternion(&self) -> [Real; 4];
+    fn to_translation(&self) -> [Real; 3];
+    fn compose(&self, other: &Self) -> Self;
+    fn inverse(&self) -> Self;
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3];
+    fn transform_vector(&self, v: &[Real; 3]) -> [Real; 3];
+    fn to_mat4_col_major(&self) -> [Real; 16];
+}
+
+impl<Real> Similarity3<Real> for [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    fn to_scale(&self) -> Real {
+        self[0]
+    }
+    fn to_quaternion(&self) -> [Real; 4] {
+        to_quaternion(self)
+    }
+    fn to_translation(&self) -> [Real; 3] {
+        to_translation(self)
+    }
+    fn compose(&self, other: &Self) -> Self {
+        compose(self, other)
+    }
+    fn inverse(&self) -> Self {
+        inverse(self)
+    }
+    fn transform_point(&self, p: &[Real; 3]) -> [Real; 3] {
+        transform_point(self, p)
+    }
+    fn transform_vector(&self, v: &[Real; 3]) -> [Real; 3] {
+        transform_vector(self, v)
+    }
+    fn to_mat4_col_major(&self) -> [Real; 16] {
+        to_mat4_col_major(self)
+    }
+}
+
+pub fn from_identity<Real>() -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    [one, zero, zero, zero, one, zero, zero, zero]
+}
+
+pub fn from_scale_quaternion_translation<Real>(s: Real, q: &[Real; 4], t: &[Real; 3]) -> [Real; 8]
+where
+    Real: Copy,
+{
+    [s, q[0], q[1], q[2], q[3], t[0], t[1], t[2]]
+}
+
+/// similarity transformation with the unit scale
+pub fn from_isometry3<Real>(a: &[Real; 7]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    [Real::one(), a[0], a[1], a[2], a[3], a[4], a[5], a[6]]
+}
+
+/// Returns `None` if the matrix is not the composition of a positive uniform scale,
+/// a rotation and a translation.
+pub fn from_mat4_col_major<Real>(m: &[Real; 16]) -> Option<[Real; 8]>
+where
+    Real: num_traits::Float + std::fmt::Debug,
+{
+    let (t, q, s) = crate::mat4_col_major::decompose_trs(m)?;
+    if s[0] <= Real::zero() {
+        return None;
+    }
+    let tol = Real::epsilon().sqrt() * s[0];
+    if (s[1] - s[0]).abs() > tol || (s[2] - s[0]).abs() > tol {
+        return None;
+    }
+    Some(from_scale_quaternion_translation(s[0], &q, &t))
+}
+
+// above: from methods
+// ---------------------------------
+
+pub fn to_quaternion<Real>(a: &[Real; 8]) -> [Real; 4]
+where
+    Real: Copy,
+{
+    [a[1], a[2], a[3], a[4]]
+}
+
+pub fn to_translation<Real>(a: &[Real; 8]) -> [Real; 3]
+where
+    Real: Copy,
+{
+    [a[5], a[6], a[7]]
+}
+
+pub fn to_mat4_col_major<Real>(a: &[Real; 8]) -> [Real; 16]
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(&to_quaternion(a));
+    let r = crate::mat3_col_major::scale(&r, a[0]);
+    let mut m = crate::mat4_col_major::from_mat3_col_major_adding_w(&r, Real::one());
+    m[12] = a[5];
+    m[13] = a[6];
+    m[14] = a[7];
+    m
+}
+
+// above: to methods
+// ---------------------------------
+
+/// the transformation `a * b` that applies `b` first then `a`
+pub fn compose<Real>(a: &[Real; 8], b: &[Real; 8]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let q = crate::quaternion::mult_quaternion(&to_quaternion(a), &to_quaternion(b));
+    let t = transform_point(a, &to_translation(b));
+    from_scale_quaternion_translation(a[0] * b[0], &crate::quaternion::normalized(&q), &t)
+}
+
+pub fn inverse<Real>(a: &[Real; 8]) -> [Real; 8]
+where
+    Real: num_traits::Float,
+{
+    let s = Real::one() / a[0];
+    let qi = crate::quaternion::inverse(to_quaternion(a));
+    let t = crate::quaternion::transform_vec(&qi, &to_translation(a));
+    from_scale_quaternion_translation(s, &qi, &[-t[0] * s, -t[1] * s, -t[2] * s])
+}
+
+pub fn transform_point<Real>(a: &[Real; 8], p: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let v = transform_vector(a, p);
+    [v[0] + a[5], v[1] + a[6], v[2] + a[7]]
+}
+
+/// scale and rotate the vector without the translation
+pub fn transform_vector<Real>(a: &[Real; 8], v: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let v = crate::quaternion::transform_vec(&to_quaternion(a), v);
+    [v[0] * a[0], v[1] * a[0], v[2] * a[0]]
+}
+
+#[test]
+fn test_similarity3() {
+    use crate::mat4_col_major::Mat4ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_similarity = || {
+        let s = rng.random::<f64>() + 0.5;
+        let aa: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let t: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        from_scale_quaternion_translation(s, &crate::quaternion::from_axisangle(&aa), &t)
+    };
+    for _itr in 0..100 {
+        let a = random_similarity();
+        let b = random_similarity();
+        let p = [0.3, -0.5, 0.8];
+        let ma = a.to_mat4_col_major();
+        let mb = b.to_mat4_col_major();
+        {
+            let p0 = a.transform_point(&p);
+            let p1 = ma.transform_homogeneous(&p).unwrap();
+            assert!(crate::vec3::distance(&p0, &p1) < 1.0e-10);
+            let v0 = a.transform_vector(&p);
+            let v1 = ma.transform_direction(&p);
+            assert!(crate::vec3::distance(&v0, &v1) < 1.0e-10);
+        }
+        {
+            let mab0 = a.compose(&b).to_mat4_col_major();
+            let mab1 = ma.mult_mat(&mb);
+            assert!((0..16).all(|i| (mab0[i] - mab1[i]).abs() < 1.0e-10));
+        }
+        {
+            let mai0 = a.inverse().to_mat4_col_major();
+            let mai1 = ma.try_inverse().unwrap();
+            assert!((0..16).all(|i| (mai0[i] - mai1[i]).abs() < 1.0e-10));
+        }
+        {
+            let m0 = from_mat4_col_major(&ma).unwrap().to_mat4_col_major();
+            assert!((0..16).all(|i| (m0[i] - ma[i]).abs() < 1.0e-10));
+            let m1 = crate::mat4_col_major::mult_mat_col_major(
+                &ma,
+                &crate::mat4_col_major::from_diagonal(1., 2., 1., 1.),
+            );
+            assert!(from_mat4_col_major(&m1).is_none());
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! methods for the Lie group SO(3) (3D rotation) where a rotation is parameterized by
+//! the axis-angle vector `w` as `R = exp([w]x)`.
+//! The matrices are stored in the column major order.
+
+/// coefficients `(a, b)` of `I + a * [w]x + b * [w]x^2` for the left Jacobian
+fn coeffs_jacobian<T>(w: &[T; 3]) -> (T, T)
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let sqt = w[0] * w[0] + w[1] * w[1] + w[2] * w[2];
+    if sqt < T::epsilon().sqrt() {
+        // Taylor expansion around zero
+        let c24 = T::from(24).unwrap();
+        let c120 = T::from(120).unwrap();
+        let c6 = T::from(6).unwrap();
+        return (one / two - sqt / c24, one / c6 - sqt / c120);
+    }
+    let t = sqt.sqrt();
+    ((one - t.cos()) / sqt, (t - t.sin()) / (sqt * t))
+}
+
+/// coefficient `b` of `I -/+ 1/2 * [w]x + b * [w]x^2` for the inverse Jacobians
+fn coeff_inverse_jacobian<T>(w: &[T; 3]) -> T
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let sqt = w[0] * w[0] + w[1] * w[1] + w[2] * w[2];
+    if sqt < T::epsilon().sqrt() {
+        // Taylor expansion around zero
+        let c12 = T::from(12).unwrap();
+        let c720 = T::from(720).unwrap();
+        return one / c12 + sqt / c720;
+    }
+    let t = sqt.sqrt();
+    one / sqt - (one + t.cos()) / (two * t * t.sin())
+}
+
+/// `I + a * [w]x + b * [w]x^2`
+fn polynomial_of_skew<T>(w: &[T; 3], a: T, b: T) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    use crate::mat3_col_major::Mat3ColMajor;
+    let k = crate::mat3_col_major::from_vec3_to_skew_mat(w);
+    let kk = k.mult_mat_col_major(&k);
+    crate::mat3_col_major::from_identity()
+        .add(&k.scale(a))
+        .add(&kk.scale(b))
+}
+
+/// left Jacobian of the exponential map such that
+/// `exp([w + dw]x) = exp([J_l * dw]x) * exp([w]x)` for small `dw`
+pub fn left_jacobian<T>(w: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    let (a, b) = coeffs_jacobian(w);
+    polynomial_of_skew(w, a, b)
+}
+
+/// right Jacobian of the exponential map such that
+/// `exp([w + dw]x) = exp([w]x) * exp([J_r * dw]x)` for small `dw`
+pub fn right_jacobian<T>(w: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    let (a, b) = coeffs_jacobian(w);
+    polynomial_of_skew(w, -a, b)
+}
+
+/// inverse of the left Jacobian of the exponential map
+pub fn left_jacobian_inverse<T>(w: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    polynomial_of_skew(w, -half, coeff_inverse_jacobian(w))
+}
+
+/// inverse of the right Jacobian of the exponential map
+pub fn right_jacobian_inverse<T>(w: &[T; 3]) -> [T; 9]
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    polynomial_of_skew(w, half, coeff_inverse_jacobian(w))
+}
+
+#[test]
+fn test_jacobian() {
+    use crate::mat3_col_major::Mat3ColMajor;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let eps = 1.0e-6;
+    for i_iter in 0..100 {
+        let w: [f64; 3] = match i_iter {
+            0 => [0., 0., 0.],
+            1 => [1.0e-5, -2.0e-5, 0.5e-5],
+            _ => std::array::from_fn(|_| rng.random_range(-1.5f64..1.5f64)),
+        };
+        let r0 = crate::vec3::to_mat3_from_axisangle_vec(&w);
+        let jl = left_jacobian(&w);
+        let jr = right_jacobian(&w);
+        {
+            let e = jl.mult_mat_col_major(&left_jacobian_inverse(&w));
+            assert!(e.sub(&Mat3ColMajor::from_identity()).norm() < 1.0e-10);
+            let e = jr.mult_mat_col_major(&right_jacobian_inverse(&w));
+            assert!(e.sub(&Mat3ColMajor::from_identity()).norm() < 1.0e-10);
+        }
+        for i_dim in 0..3 {
+            let w1 = {
+                let mut w1 = w;
+                w1[i_dim] += eps;
+                w1
+            };
+            let r1 = crate::vec3::to_mat3_from_axisangle_vec(&w1);
+            {
+                // left: r1 * r0^t = exp([J_l * dw]x)
+                let dr = r1.mult_mat_col_major(&r0.transpose());
+                let dw_num = crate::mat3_col_major::to_vec3_from_skew_mat(&dr);
+                let dw_ana = crate::mat3_col_major::to_vec3_column(&jl, i_dim);
+                for k in 0..3 {
+                    assert!((dw_num[k] / eps - dw_ana[k]).abs() < 1.0e-5);
+                }
+            }
+            {
+                // right: r0^t * r1 = exp([J_r * dw]x)
+                let dr = r0.transpose().mult_mat_col_major(&r1);
+                let dw_num = crate::mat3_col_major::to_vec3_from_skew_mat(&dr);
+                let dw_ana = crate::mat3_col_major::to_vec3_column(&jr, i_dim);
+                for k in 0..3 {
+                    assert!((dw_num[k] / eps - dw_ana[k]).abs() < 1.0e-5);
+                }
+            }
+        }
+    }
+}
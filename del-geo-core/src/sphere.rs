@@ -25,6 +25,57 @@ where
     }
 }
 
+/// the sphere and the AABB overlap (touching counts as overlap).
+/// The closest point in the AABB is found by clamping the center.
+pub fn overlaps_aabb3<T>(rad: T, center: &[T; 3], aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    crate::aabb::sq_distance_to_point::<T, 3, 6>(aabb, center) <= rad * rad
+}
+
+/// distance between the sphere and the AABB. Zero if they overlap.
+pub fn distance_to_aabb3<T>(rad: T, center: &[T; 3], aabb: &[T; 6]) -> T
+where
+    T: num_traits::Float,
+{
+    let d = crate::aabb::distance_to_point::<T, 3, 6>(aabb, center);
+    (d - rad).max(T::zero())
+}
+
+#[test]
+fn test_sphere_aabb3() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [0f64, 0., 0., 1., 1., 1.];
+    // near the corner, the sphere does not overlap although the expanded AABB includes the center
+    let c = [1.5, 1.5, 1.5];
+    assert!(crate::aabb::is_include_point::<f64, 3, 6>(
+        &crate::aabb::expand::<f64, 3, 6>(&aabb, 0.6),
+        &c
+    ));
+    assert!(!overlaps_aabb3(0.6, &c, &aabb));
+    assert!((distance_to_aabb3(0.6, &c, &aabb) - (0.75f64.sqrt() - 0.6)).abs() < 1.0e-10);
+    assert!(overlaps_aabb3(0.9, &c, &aabb));
+    assert_eq!(distance_to_aabb3(0.9, &c, &aabb), 0.);
+    for _iter in 0..100 {
+        let c: [f64; 3] = std::array::from_fn(|_| 4. * rng.random::<f64>() - 1.5);
+        let rad = rng.random::<f64>();
+        // compare with the sampling of the points in the AABB
+        let d_min = (0..1000)
+            .map(|_| {
+                let p: [f64; 3] = std::array::from_fn(|_| rng.random::<f64>());
+                crate::vec3::distance(&p, &c) - rad
+            })
+            .fold(f64::MAX, f64::min)
+            .max(0.);
+        let d = distance_to_aabb3(rad, &c, &aabb);
+        assert!(d <= d_min + 1.0e-10);
+        assert_eq!(overlaps_aabb3(rad, &c, &aabb), d == 0.);
+    }
+}
+
 pub fn area<T>(r: T) -> T
 where
     T: num_traits::Float + num_traits::FloatConst,
@@ -87,3 +138,223 @@ where
     let cos_theta_max = (one - sin_theta_max_squared).max(zero).sqrt();
     one / (two * Real::PI() * (one - cos_theta_max))
 }
+
+// ---------------------------------
+// below: bounding sphere of points
+
+/// approximate bounding sphere of the points by Ritter's method.
+/// The sphere is up to ~5% larger than the minimal one.
+/// Returns `None` if there is no point.
+/// * Return `(radius, center)`
+pub fn from_points_ritter<T>(points: &[[T; 3]]) -> Option<(T, [T; 3])>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let p0 = points.first()?;
+    let farthest = |p: &[T; 3]| {
+        points
+            .iter()
+            .max_by(|a, b| {
+                let da = a.sub(p).squared_norm();
+                let db = b.sub(p).squared_norm();
+                da.partial_cmp(&db).unwrap()
+            })
+            .unwrap()
+    };
+    let p1 = farthest(p0);
+    let p2 = farthest(p1);
+    let half = T::one() / (T::one() + T::one());
+    let mut center = p1.add(p2).scale(half);
+    let mut rad = crate::vec3::distance(p1, p2) * half;
+    for p in points {
+        let d = crate::vec3::distance(p, &center);
+        if d <= rad {
+            continue;
+        }
+        // grow the sphere to touch the point on the opposite side
+        let rad_new = (rad + d) * half;
+        center = center.add(&p.sub(&center).scale((rad_new - rad) / d));
+        rad = rad_new;
+    }
+    Some((rad, center))
+}
+
+/// smallest sphere passing through the points (at most four).
+/// The degenerate points (e.g., collinear three points) are handled
+/// by the smallest sphere among the subsets containing all the points.
+fn from_boundary_points<T>(ps: &[[T; 3]]) -> (T, [T; 3])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let half = T::one() / (T::one() + T::one());
+    let smallest_among_subsets = |ps: &[[T; 3]]| {
+        let mut best: Option<(T, [T; 3])> = None;
+        for i in 0..ps.len() {
+            let sub: Vec<[T; 3]> = [&ps[..i], &ps[i + 1..]].concat();
+            let (r, c) = from_boundary_points(&sub);
+            let tol = r * T::epsilon().sqrt();
+            if crate::vec3::distance(&ps[i], &c) > r + tol || best.is_some_and(|(r0, _)| r0 <= r) {
+                continue;
+            }
+            best = Some((r, c));
+        }
+        best.unwrap()
+    };
+    match ps.len() {
+        0 => (T::zero(), [T::zero(); 3]),
+        1 => (T::zero(), ps[0]),
+        2 => (
+            crate::vec3::distance(&ps[0], &ps[1]) * half,
+            ps[0].add(&ps[1]).scale(half),
+        ),
+        3 => {
+            let a = ps[1].sub(&ps[0]);
+            let b = ps[2].sub(&ps[0]);
+            let n = a.cross(&b);
+            let nn = n.squared_norm();
+            if nn <= T::epsilon() * a.squared_norm() * b.squared_norm() {
+                return smallest_among_subsets(ps);
+            }
+            let d = b
+                .cross(&n)
+                .scale(a.squared_norm())
+                .add(&n.cross(&a).scale(b.squared_norm()))
+                .scale(half / nn);
+            (d.norm(), ps[0].add(&d))
+        }
+        4 => {
+            use crate::mat3_col_major::Mat3ColMajor;
+            let a = ps[1].sub(&ps[0]);
+            let b = ps[2].sub(&ps[0]);
+            let c = ps[3].sub(&ps[0]);
+            // the rows are `a`, `b`, and `c`
+            let m = crate::mat3_col_major::from_columns(&a, &b, &c).transpose();
+            let vol = m.determinant().abs();
+            let tol = T::epsilon() * a.norm() * b.norm() * c.norm();
+            let Some(mi) = m.try_inverse().filter(|_| vol > tol) else {
+                return smallest_among_subsets(ps);
+            };
+            let rhs = [a.squared_norm(), b.squared_norm(), c.squared_norm()].scale(half);
+            let d = mi.mult_vec(&rhs);
+            (d.norm(), ps[0].add(&d))
+        }
+        _ => unreachable!("at most 4 boundary points"),
+    }
+}
+
+/// minimal enclosing sphere of the points by Welzl's algorithm.
+/// The recursion is only over the points on the boundary, so its depth is at most four.
+/// The expected time is linear if the points are in the random order,
+/// so shuffle them beforehand if they are sorted (e.g., the vertices of a grid).
+/// Returns `None` if there is no point.
+/// * Return `(radius, center)`
+pub fn from_points_welzl<T>(points: &[[T; 3]]) -> Option<(T, [T; 3])>
+where
+    T: num_traits::Float,
+{
+    welzl(points, 4, &from_boundary_points)
+}
+
+/// Welzl's algorithm for the minimal enclosing ball in any dimension.
+/// * `max_boundary` - maximum number of the points on the boundary (`N + 1`)
+/// * `from_boundary_points` - smallest ball passing through the boundary points
+pub(crate) fn welzl<T, const N: usize>(
+    points: &[[T; N]],
+    max_boundary: usize,
+    from_boundary_points: &impl Fn(&[[T; N]]) -> (T, [T; N]),
+) -> Option<(T, [T; N])>
+where
+    T: num_traits::Float,
+{
+    // the ball enclosing `points` with `boundary` on its boundary, starting from the ball `s`
+    fn update<T, const N: usize>(
+        points: &[[T; N]],
+        boundary: &mut Vec<[T; N]>,
+        mut s: (T, [T; N]),
+        max_boundary: usize,
+        from_boundary_points: &impl Fn(&[[T; N]]) -> (T, [T; N]),
+    ) -> (T, [T; N])
+    where
+        T: num_traits::Float,
+    {
+        for (i, p) in points.iter().enumerate() {
+            if crate::edge::length(p, &s.1) <= s.0 * (T::one() + T::epsilon().sqrt()) {
+                continue;
+            }
+            boundary.push(*p);
+            s = from_boundary_points(boundary);
+            if boundary.len() < max_boundary {
+                s = update(
+                    &points[..i],
+                    boundary,
+                    s,
+                    max_boundary,
+                    from_boundary_points,
+                );
+            }
+            boundary.pop();
+        }
+        s
+    }
+    let s = from_boundary_points(&[*points.first()?]);
+    Some(update(
+        points,
+        &mut vec![],
+        s,
+        max_boundary,
+        from_boundary_points,
+    ))
+}
+
+#[test]
+fn test_bounding_sphere() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _iter in 0..100 {
+        let num_point = rng.random_range(1..100);
+        let ps: Vec<[f64; 3]> = (0..num_point)
+            .map(|_| std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.))
+            .collect();
+        let (r0, c0) = from_points_welzl(&ps).unwrap();
+        let (r1, c1) = from_points_ritter(&ps).unwrap();
+        assert!(
+            ps.iter()
+                .all(|p| crate::vec3::distance(p, &c0) <= r0 * (1. + 1.0e-6))
+        );
+        assert!(
+            ps.iter()
+                .all(|p| crate::vec3::distance(p, &c1) <= r1 * (1. + 1.0e-6))
+        );
+        assert!(r0 <= r1 * (1. + 1.0e-10));
+        // minimal: moving the center does not make the sphere smaller
+        for _ in 0..10 {
+            let d: [f64; 3] = std::array::from_fn(|_| 2.0e-3 * rng.random::<f64>() - 1.0e-3);
+            let c2 = c0.add(&d);
+            let r2 = ps
+                .iter()
+                .fold(0f64, |r, p| r.max(crate::vec3::distance(p, &c2)));
+            assert!(r2 >= r0 * (1. - 1.0e-10));
+        }
+    }
+    // regular tetrahedron and degenerate inputs
+    let ps = [[1., 1., 1.], [1., -1., -1.], [-1., 1., -1.], [-1., -1., 1.]];
+    let (r, c) = from_points_welzl(&ps).unwrap();
+    assert!((r - 3f64.sqrt()).abs() < 1.0e-10 && c.norm() < 1.0e-10);
+    let ps = [[0., 0., 0.], [1., 0., 0.], [2., 0., 0.], [1., 0., 0.]];
+    let (r, c) = from_points_welzl(&ps).unwrap();
+    assert!((r - 1f64).abs() < 1.0e-10 && crate::vec3::distance(&c, &[1., 0., 0.]) < 1.0e-10);
+    let ps = [
+        [1., 0., 0.],
+        [0., 1., 0.],
+        [-1., 0., 0.],
+        [0., -1., 0.],
+        [0., 0., 0.5],
+    ];
+    let (r, c) = from_points_welzl(&ps).unwrap();
+    assert!((r - 1f64).abs() < 1.0e-10 && c.norm() < 1.0e-10);
+    assert_eq!(from_points_welzl::<f64>(&[]), None);
+}
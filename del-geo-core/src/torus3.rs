@@ -0,0 +1,168 @@
+//! methods for 3D torus.
+//! The torus is represented as `(rad_major: Real, rad_minor: Real, center: &[Real;3], axis: &[Real;3])`
+//! where the tube of `rad_minor` sweeps the circle of `rad_major` around the unit vector `axis`.
+
+use crate::vec3::Vec3;
+
+/// the nearest non-negative ray parameter at the intersection of the ray and the torus surface
+/// * `ray_dir` - direction of ray (general non-zero vector, not necessarily unitary)
+/// * Return `t` where the intersection is `ray_src + t * ray_dir`
+pub fn intersection_ray<T>(
+    rad_major: T,
+    rad_minor: T,
+    center: &[T; 3],
+    axis: &[T; 3],
+    ray_src: &[T; 3],
+    ray_dir: &[T; 3],
+) -> Option<T>
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let four = two + two;
+    // solve with the unit direction for the better conditioning
+    let len_dir = ray_dir.norm();
+    let d = ray_dir.scale(T::one() / len_dir);
+    let o = ray_src.sub(center);
+    // (|p|^2 + R^2 - r^2)^2 - 4 R^2 (|p|^2 - (p.n)^2) = 0 where p = o + t * d
+    // |p|^2 + R^2 - r^2 = t^2 + e1 * t + e0
+    let e1 = two * o.dot(&d);
+    let e0 = o.squared_norm() + rad_major * rad_major - rad_minor * rad_minor;
+    // |p|^2 - (p.n)^2 = g2 * t^2 + g1 * t + g0
+    let (on, dn) = (o.dot(axis), d.dot(axis));
+    let g2 = T::one() - dn * dn;
+    let g1 = two * (o.dot(&d) - on * dn);
+    let g0 = o.squared_norm() - on * on;
+    let rr4 = four * rad_major * rad_major;
+    let (ts, num_t) = crate::polynomial_root::quartic_roots_real(
+        e0 * e0 - rr4 * g0,
+        two * e1 * e0 - rr4 * g1,
+        e1 * e1 + two * e0 - rr4 * g2,
+        two * e1,
+        T::one(),
+    );
+    ts[..num_t]
+        .iter()
+        .find(|&&t| t >= T::zero())
+        .map(|&t| t / len_dir)
+}
+
+/// nearest point on the center circle of the tube. `None` if `p` is on the axis.
+fn nearest_on_center_circle<T>(
+    rad_major: T,
+    center: &[T; 3],
+    axis: &[T; 3],
+    p: &[T; 3],
+) -> Option<[T; 3]>
+where
+    T: num_traits::Float,
+{
+    let q = p.sub(center);
+    let radial = q.sub(&axis.scale(q.dot(axis)));
+    let len = radial.norm();
+    if len.is_zero() {
+        return None;
+    }
+    Some(center.add(&radial.scale(rad_major / len)))
+}
+
+/// unit outward normal of the torus surface at `p`.
+/// `p` does not need to be exactly on the surface.
+pub fn normal<T>(rad_major: T, center: &[T; 3], axis: &[T; 3], p: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let c = nearest_on_center_circle(rad_major, center, axis, p).unwrap_or_else(|| {
+        // any point on the center circle is equidistant
+        let u = crate::vec3::orthonormal_basis(axis).0;
+        center.add(&u.scale(rad_major))
+    });
+    p.sub(&c).normalize()
+}
+
+/// signed distance from the point to the torus surface (negative inside)
+pub fn signed_distance_to_point<T>(
+    rad_major: T,
+    rad_minor: T,
+    center: &[T; 3],
+    axis: &[T; 3],
+    p: &[T; 3],
+) -> T
+where
+    T: num_traits::Float,
+{
+    let q = p.sub(center);
+    let h = q.dot(axis);
+    let radial = q.sub(&axis.scale(h)).norm();
+    (h * h + (radial - rad_major) * (radial - rad_major)).sqrt() - rad_minor
+}
+
+/// tight AABB of the torus
+pub fn aabb3<T>(rad_major: T, rad_minor: T, center: &[T; 3], axis: &[T; 3]) -> [T; 6]
+where
+    T: num_traits::Float,
+{
+    let aabb = crate::cylinder3::aabb3_of_disk(rad_major, center, axis);
+    crate::aabb::expand::<T, 3, 6>(&aabb, rad_minor)
+}
+
+#[test]
+fn test_torus3() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..300 {
+        let center = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(0.2);
+        let axis = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).normalize();
+        let rad_major = 0.5 + 0.2 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0];
+        let rad_minor = 0.1 + 0.05 * crate::vec3::sample_centered_cube::<_, f64>(&mut rng)[0];
+        let org = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(2.);
+        let dir = crate::vec3::sample_centered_cube::<_, f64>(&mut rng)
+            .scale(0.5)
+            .sub(&org);
+        let sdf = |p: &[f64; 3]| signed_distance_to_point(rad_major, rad_minor, &center, &axis, p);
+        let res = intersection_ray(rad_major, rad_minor, &center, &axis, &org, &dir);
+        // march along the ray to find the first crossing of the surface
+        let ndiv = 10000;
+        let t_march = (0..ndiv)
+            .map(|i| i as f64 / ndiv as f64 * 2.)
+            .find(|&t| sdf(&org.add(&dir.scale(t))) <= 0.);
+        let Some(t) = res else {
+            assert!(t_march.is_none() || sdf(&org) <= 0.);
+            continue;
+        };
+        num_hit += 1;
+        let p = org.add(&dir.scale(t));
+        assert!(sdf(&p).abs() < 1.0e-8);
+        if sdf(&org) > 0. {
+            match t_march {
+                Some(t_march) => assert!((t - t_march).abs() < 1.0e-3, "{t} {t_march}"),
+                None => assert!(t > 2. - 1.0e-3),
+            }
+        }
+        // the normal is the gradient of the signed distance
+        let n = normal(rad_major, &center, &axis, &p);
+        let eps = 1.0e-5;
+        let grad: [f64; 3] = std::array::from_fn(|i| {
+            let mut pp = p;
+            pp[i] += eps;
+            (sdf(&pp) - sdf(&p)) / eps
+        });
+        assert!(n.sub(&grad).norm() < 1.0e-4);
+        // the AABB contains the surface point
+        let aabb = aabb3(rad_major, rad_minor, &center, &axis);
+        assert!(crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &p) < 1.0e-20);
+    }
+    assert!(num_hit > 30);
+    // ray along the axis through the hole misses
+    let (center, axis) = ([0., 0., 0.], [0., 0., 1.]);
+    let res = intersection_ray(1., 0.25, &center, &axis, &[0., 0., -2.], &[0., 0., 1.]);
+    assert_eq!(res, None);
+    // ray in the equatorial plane hits the outer side first
+    let t: f64 = intersection_ray(1., 0.25, &center, &axis, &[-2., 0., 0.], &[2., 0., 0.]).unwrap();
+    assert!((t - 0.375).abs() < 1.0e-10);
+    assert_eq!(
+        aabb3(1., 0.25, &center, &axis),
+        [-1.25, -1.25, -0.25, 1.25, 1.25, 0.25]
+    );
+}
@@ -154,7 +154,7 @@ where
 
 pub fn circumcenter<T>(p0: &[T; 2], p1: &[T; 2], p2: &[T; 2]) -> [T; 2]
 where
-    T: num_traits::Float + Copy + std::fmt::Debug,
+    T: num_traits::Float + Copy,
 {
     use crate::vec2::Vec2;
     let a0 = p1.sub(p2).squared_norm();
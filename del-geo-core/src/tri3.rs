@@ -93,13 +93,28 @@ where
     ([n[0] * invlen, n[1] * invlen, n[2] * invlen], a)
 }
 
+/// the same as [`cot`] but returns the error for the zero-area triangle instead of the infinite values
+pub fn try_cot<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+) -> Result<[T; 3], crate::error::DegenerateError>
+where
+    T: num_traits::Float,
+{
+    crate::error::check_finite(&[p0, p1, p2])?;
+    if area(p0, p1, p2).is_zero() {
+        return Err(crate::error::DegenerateError::ZeroAreaTriangle);
+    }
+    Ok(cot(p0, p1, p2))
+}
+
 /// compute cotangents of the three angles of a triangle
 pub fn cot<T>(p0: &[T; 3], p1: &[T; 3], p2: &[T; 3]) -> [T; 3]
 where
     T: num_traits::Float,
 {
     use crate::vec3::Vec3;
-    assert!(p0.len() == 3 && p1.len() == 3 && p2.len() == 3);
     let v0 = [p1[0] - p2[0], p1[1] - p2[1], p1[2] - p2[2]];
     let v1 = [p2[0] - p0[0], p2[1] - p0[1], p2[2] - p0[2]];
     let v2 = [p0[0] - p1[0], p0[1] - p1[1], p0[2] - p1[2]];
@@ -312,6 +327,24 @@ fn test_w_inverse_distance_cubic_integrated_over_wedge() {
     }
 }
 
+/// the same as [`nearest_to_point3`] but returns the error for the zero-area triangle
+/// or the non-finite coordinates instead of the NaN values
+pub fn try_nearest_to_point3<T>(
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+    ps: &[T; 3],
+) -> Result<([T; 3], T, T), crate::error::DegenerateError>
+where
+    T: num_traits::Float + std::fmt::Debug,
+{
+    crate::error::check_finite(&[q0, q1, q2, ps])?;
+    if area(q0, q1, q2).is_zero() {
+        return Err(crate::error::DegenerateError::ZeroAreaTriangle);
+    }
+    Ok(nearest_to_point3(q0, q1, q2, ps))
+}
+
 pub fn nearest_to_point3<T>(q0: &[T; 3], q1: &[T; 3], q2: &[T; 3], ps: &[T; 3]) -> ([T; 3], T, T)
 where
     T: num_traits::Float + std::fmt::Debug,
@@ -351,9 +384,266 @@ where
     (r01, r0, r1)
 }
 
+/// feature (Voronoi region) of the triangle that realizes the nearest point.
+/// See [`nearest_to_point3_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriFeature {
+    /// the vertex `q_i`
+    Vertex(usize),
+    /// the interior of the edge opposite to the vertex `q_i` (e.g., `Edge(0)` is the edge `q1`-`q2`)
+    Edge(usize),
+    /// the interior of the triangle
+    Face,
+}
+
+/// result of [`nearest_to_point3_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriPointNearest<T> {
+    pub dist: T,
+    /// nearest point on the triangle
+    pub point: [T; 3],
+    /// barycentric coordinates of the nearest point
+    pub bc: [T; 3],
+    pub feature: TriFeature,
+}
+
+/// nearest point on the triangle `q0`-`q1`-`q2` from the point `p` by classifying the Voronoi region
+/// of the triangle where `p` is (Ericson, "Real-Time Collision Detection", 5.1.5).
+/// Unlike [`nearest_to_point3`], this does not divide by the area of the triangle outside the face region,
+/// and the triangle with the coincident or collinear vertices falls back to its vertices and edges.
+pub fn nearest_to_point3_detailed<T>(
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+    p: &[T; 3],
+) -> TriPointNearest<T>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let (zero, one) = (T::zero(), T::one());
+    // coincident or collinear vertices: the nearest among the vertices and the edges
+    let degenerate = || {
+        let q = [q0, q1, q2];
+        let mut res = (T::infinity(), [one, zero, zero], TriFeature::Vertex(0));
+        for i in 0..3 {
+            let dist = q[i].sub(p).norm();
+            if dist < res.0 {
+                let mut bc = [zero; 3];
+                bc[i] = one;
+                res = (dist, bc, TriFeature::Vertex(i));
+            }
+        }
+        for i in 0..3 {
+            let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+            let (dist, r) = crate::edge::nearest_to_point(q[j], q[k], p);
+            if dist < res.0 && r > zero && r < one {
+                let mut bc = [zero; 3];
+                bc[j] = one - r;
+                bc[k] = r;
+                res = (dist, bc, TriFeature::Edge(i));
+            }
+        }
+        (res.1, res.2)
+    };
+    let (bc, feature) = 'region: {
+        let e01 = q1.sub(q0);
+        let e02 = q2.sub(q0);
+        let d1 = e01.dot(&p.sub(q0));
+        let d2 = e02.dot(&p.sub(q0));
+        if d1 <= zero && d2 <= zero {
+            break 'region ([one, zero, zero], TriFeature::Vertex(0));
+        }
+        let d3 = e01.dot(&p.sub(q1));
+        let d4 = e02.dot(&p.sub(q1));
+        if d3 >= zero && d4 <= d3 {
+            break 'region ([zero, one, zero], TriFeature::Vertex(1));
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= zero && d1 >= zero && d3 <= zero {
+            if d1 - d3 == zero {
+                break 'region degenerate();
+            }
+            let r = d1 / (d1 - d3);
+            break 'region ([one - r, r, zero], TriFeature::Edge(2));
+        }
+        let d5 = e01.dot(&p.sub(q2));
+        let d6 = e02.dot(&p.sub(q2));
+        if d6 >= zero && d5 <= d6 {
+            break 'region ([zero, zero, one], TriFeature::Vertex(2));
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= zero && d2 >= zero && d6 <= zero {
+            if d2 - d6 == zero {
+                break 'region degenerate();
+            }
+            let r = d2 / (d2 - d6);
+            break 'region ([one - r, zero, r], TriFeature::Edge(1));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+            if (d4 - d3) + (d5 - d6) == zero {
+                break 'region degenerate();
+            }
+            let r = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+            break 'region ([zero, one - r, r], TriFeature::Edge(0));
+        }
+        if va + vb + vc == zero {
+            break 'region degenerate();
+        }
+        let inv = one / (va + vb + vc);
+        ([va * inv, vb * inv, vc * inv], TriFeature::Face)
+    };
+    let point = position_from_barycentric_coords(q0, q1, q2, &bc);
+    TriPointNearest {
+        dist: point.sub(p).norm(),
+        point,
+        bc,
+        feature,
+    }
+}
+
+/// distance between the triangle `q0`-`q1`-`q2` and the point `p`, and its gradient
+/// * Return `(dist, [d(dist)/d(q0), d(dist)/d(q1), d(dist)/d(q2), d(dist)/d(p)])`
+///
+/// As the barycentric coordinates minimize the distance, their derivatives do not contribute
+/// in any Voronoi region (see [`crate::edge3::wdw_nearest_to_point3`]).
+/// The gradient is zero if the point is on the triangle.
+pub fn wdw_nearest_to_point3<T>(
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+    p: &[T; 3],
+) -> (T, [[T; 3]; 4])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let res = nearest_to_point3_detailed(q0, q1, q2, p);
+    if res.dist.is_zero() {
+        return (res.dist, [[T::zero(); 3]; 4]);
+    }
+    let u = res.point.sub(p).scale(T::one() / res.dist);
+    let bc = res.bc;
+    (
+        res.dist,
+        [
+            u.scale(bc[0]),
+            u.scale(bc[1]),
+            u.scale(bc[2]),
+            u.scale(-T::one()),
+        ],
+    )
+}
+
+#[test]
+fn test_nearest_to_point3_detailed() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_feature = [0; 3];
+    for _iter in 0..1000 {
+        let q = [
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        ];
+        let p = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(1.5);
+        if area(&q[0], &q[1], &q[2]) < 0.1 {
+            continue;
+        }
+        let res = nearest_to_point3_detailed(&q[0], &q[1], &q[2], &p);
+        // the same distance as the existing implementation
+        let (pos0, _, _) = nearest_to_point3(&q[0], &q[1], &q[2], &p);
+        assert!((res.dist - pos0.sub(&p).norm()).abs() < 1.0e-10);
+        assert!((res.bc[0] + res.bc[1] + res.bc[2] - 1.).abs() < 1.0e-10);
+        assert!(res.bc.iter().all(|&r| r >= 0.));
+        match res.feature {
+            TriFeature::Vertex(i) => {
+                num_feature[0] += 1;
+                assert_eq!(res.bc[i], 1.);
+            }
+            TriFeature::Edge(i) => {
+                num_feature[1] += 1;
+                assert_eq!(res.bc[i], 0.);
+                assert!(res.bc[(i + 1) % 3] > 0. && res.bc[(i + 2) % 3] > 0.);
+            }
+            TriFeature::Face => {
+                num_feature[2] += 1;
+                // the displacement is normal to the triangle
+                let n = normal(&q[0], &q[1], &q[2]).normalize();
+                assert!(n.cross(&res.point.sub(&p)).norm() < 1.0e-10);
+            }
+        }
+        // gradient by the finite difference
+        let (dist0, dw) = wdw_nearest_to_point3(&q[0], &q[1], &q[2], &p);
+        assert_eq!(dist0, res.dist);
+        let eps = 1.0e-5;
+        for (i_node, i_dim) in itertools::iproduct!(0..4, 0..3) {
+            let mut ps = [q[0], q[1], q[2], p];
+            ps[i_node][i_dim] += eps;
+            let (dist1, _) = wdw_nearest_to_point3(&ps[0], &ps[1], &ps[2], &ps[3]);
+            let v_num = (dist1 - dist0) / eps;
+            assert!((v_num - dw[i_node][i_dim]).abs() < 1.0e-3 * (1. + 1. / dist0));
+        }
+        // translation invariance
+        let sum = dw[0].add(&dw[1]).add(&dw[2]).add(&dw[3]);
+        assert!(sum.norm() < 1.0e-10);
+    }
+    assert!(num_feature.iter().all(|&n| n > 50), "{num_feature:?}");
+    // coincident or collinear vertices
+    let p = [0.5, 1., 0.];
+    let qs: [[[f64; 3]; 3]; 5] = [
+        [[0., 0., 0.], [0., 0., 0.], [1., 0., 0.]],
+        [[1., 0., 0.], [0., 0., 0.], [0., 0., 0.]],
+        [[0., 0., 0.], [1., 0., 0.], [0., 0., 0.]],
+        [[0., 0., 0.], [1., 0., 0.], [2., 0., 0.]],
+        [[2., 0., 0.], [0., 0., 0.], [1., 0., 0.]],
+    ];
+    for q in qs {
+        let res = nearest_to_point3_detailed(&q[0], &q[1], &q[2], &p);
+        assert_eq!(res.dist, 1., "{q:?}");
+        assert_eq!(res.point, [0.5, 0., 0.], "{q:?}");
+        assert!((res.bc[0] + res.bc[1] + res.bc[2] - 1.).abs() < 1.0e-10);
+        let (_, dw) = wdw_nearest_to_point3(&q[0], &q[1], &q[2], &p);
+        assert!(dw.iter().flatten().all(|v| v.is_finite()), "{q:?}");
+    }
+    let q = [[0f64; 3]; 3];
+    let res = nearest_to_point3_detailed(&q[0], &q[1], &q[2], &p);
+    assert_eq!(res.feature, TriFeature::Vertex(0));
+    assert_eq!(res.dist, 1.25f64.sqrt());
+}
+
 // -------------------------------------
 // below: intersection
 
+/// the same as [`intersection_plane_of_tri3_against_line`] but returns the error
+/// for the zero-area triangle or the line parallel to the plane instead of the non-finite values
+#[allow(clippy::type_complexity)]
+pub fn try_intersection_plane_of_tri3_against_line<T>(
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+    src: &[T; 3],
+    dir: &[T; 3],
+) -> Result<([T; 3], [T; 3]), crate::error::DegenerateError>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    crate::error::check_finite(&[q0, q1, q2, src, dir])?;
+    let n = normal(q0, q1, q2);
+    if n.squared_norm().is_zero() {
+        return Err(crate::error::DegenerateError::ZeroAreaTriangle);
+    }
+    if n.dot(dir).is_zero() {
+        return Err(crate::error::DegenerateError::ParallelToPlane);
+    }
+    Ok(intersection_plane_of_tri3_against_line(
+        q0, q1, q2, src, dir,
+    ))
+}
+
 /// # Returns
 /// position and the barycentric coordinate
 pub fn intersection_plane_of_tri3_against_line<T>(
@@ -418,6 +708,252 @@ where
     Some(t)
 }
 
+/// ray transformed for the watertight ray-triangle intersection. See [`intersection_against_ray_watertight`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecomputedRayWatertight<T> {
+    pub org: [T; 3],
+    /// permutation of the axes so that the `kz`-th axis is the dominant axis of the direction
+    pub k: [usize; 3],
+    /// shear and scale of the ray direction onto the `z` axis
+    pub shear: [T; 3],
+}
+
+impl<T> PrecomputedRayWatertight<T>
+where
+    T: num_traits::Float,
+{
+    pub fn new(org: &[T; 3], dir: &[T; 3]) -> Self {
+        let abs = dir.map(|v| v.abs());
+        let kz = if abs[0] > abs[1] {
+            if abs[0] > abs[2] { 0 } else { 2 }
+        } else if abs[1] > abs[2] {
+            1
+        } else {
+            2
+        };
+        let (mut kx, mut ky) = ((kz + 1) % 3, (kz + 2) % 3);
+        // preserve the winding of the triangle
+        if dir[kz] < T::zero() {
+            std::mem::swap(&mut kx, &mut ky);
+        }
+        Self {
+            org: *org,
+            k: [kx, ky, kz],
+            shear: [dir[kx] / dir[kz], dir[ky] / dir[kz], T::one() / dir[kz]],
+        }
+    }
+}
+
+/// watertight ray-triangle intersection by Woop, Benthin and Wald (2013),
+/// "Watertight Ray/Triangle Intersection", JCGT.
+/// The ray hitting the shared edge or vertex of adjacent triangles hits at least one of them,
+/// unlike [`intersection_against_line`].
+/// * Return `(t, barycentric coordinates)` where `org + t * dir = r0 * p0 + r1 * p1 + r2 * p2` and `t >= 0`
+pub fn intersection_against_ray_watertight<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    ray: &PrecomputedRayWatertight<T>,
+) -> Option<(T, [T; 3])>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let zero = T::zero();
+    let [kx, ky, kz] = ray.k;
+    let [sx, sy, sz] = ray.shear;
+    // vertices in the coordinate where the ray is the `z` axis
+    let transform = |p: &[T; 3]| {
+        let a = p.sub(&ray.org);
+        [a[kx] - sx * a[kz], a[ky] - sy * a[kz], sz * a[kz]]
+    };
+    let (a, b, c) = (transform(p0), transform(p1), transform(p2));
+    // scaled barycentric coordinates
+    let u = c[0] * b[1] - c[1] * b[0];
+    let v = a[0] * c[1] - a[1] * c[0];
+    let w = b[0] * a[1] - b[1] * a[0];
+    if (u < zero || v < zero || w < zero) && (u > zero || v > zero || w > zero) {
+        return None;
+    }
+    let det = u + v + w;
+    if det == zero {
+        return None;
+    }
+    let t = (u * a[2] + v * b[2] + w * c[2]) / det;
+    if t < zero {
+        return None;
+    }
+    Some((t, [u / det, v / det, w / det]))
+}
+
+#[test]
+fn test_intersection_against_ray_watertight() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..1000 {
+        let (p0, p1, p2) = (
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        );
+        // toward the point around the triangle
+        let w = crate::vec3::sample_centered_cube::<_, f64>(&mut rng).map(|v| v + 0.8);
+        let target = position_from_barycentric_coords(&p0, &p1, &p2, &w);
+        let org = crate::vec3::sample_centered_cube::<_, f64>(&mut rng);
+        let dir = target.sub(&org);
+        let ray = PrecomputedRayWatertight::new(&org, &dir);
+        let res = intersection_against_ray_watertight(&p0, &p1, &p2, &ray);
+        // the same as Möller–Trumbore for the generic configurations
+        let t0 = intersection_against_line(&p0, &p1, &p2, &org, &dir).filter(|&t| t >= 0.);
+        match (res, t0) {
+            (Some((t, bc)), Some(t0)) => {
+                num_hit += 1;
+                assert!((t - t0).abs() < 1.0e-8 * (1. + t0.abs()));
+                let p = position_from_barycentric_coords(&p0, &p1, &p2, &bc);
+                assert!(p.sub(&org.add(&dir.scale(t))).norm() < 1.0e-8);
+            }
+            (None, None) => {}
+            _ => panic!("{res:?} {t0:?}"),
+        }
+    }
+    assert!(num_hit > 50);
+    // the rays toward the shared edge and the shared vertex of the fan of triangles
+    let center = [0.1f64, 0.2, 0.3];
+    let ring: Vec<[f64; 3]> = (0..7)
+        .map(|i| {
+            let a = i as f64 * 2. * std::f64::consts::PI / 7.;
+            [a.cos(), a.sin(), 0.3 * a.cos()]
+        })
+        .collect();
+    for _iter in 0..1000 {
+        let org = [
+            3. * rng.random::<f64>() - 1.5,
+            3. * rng.random::<f64>() - 1.5,
+            2.,
+        ];
+        let i_edge = rng.random_range(0..7);
+        let r = if rng.random::<f64>() < 0.1 {
+            0.0
+        } else {
+            rng.random::<f64>()
+        };
+        let target = center.add(&ring[i_edge].sub(&center).scale(r));
+        let ray = PrecomputedRayWatertight::new(&org, &target.sub(&org));
+        let num_tri_hit = (0..7)
+            .filter(|&i| {
+                let (q1, q2) = (&ring[i], &ring[(i + 1) % 7]);
+                intersection_against_ray_watertight(&center, q1, q2, &ray).is_some()
+            })
+            .count();
+        assert!(num_tri_hit >= 1);
+    }
+}
+
+/// ray-triangle intersection and its derivatives with respect to the vertices of the triangle.
+/// The derivatives are computed by the implicit function theorem on
+/// `org + t * dir = r0 * p0 + r1 * p1 + r2 * p2` (see [`intersection_against_line_bwd_wrt_tri`] for the reverse mode).
+/// * Return `(t, [r0, r1, r2], dt_dp, dr_dp)` where `dt_dp[i_vtx]` is the gradient of `t` w.r.t. the vertex
+///   and `dr_dp[i_bc][i_vtx]` is the gradient of the barycentric coordinate `r_{i_bc}` w.r.t. the vertex,
+///   or `None` if the ray misses the triangle
+#[allow(clippy::type_complexity)]
+pub fn dw_intersection_against_ray<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    org: &[T; 3],
+    dir: &[T; 3],
+) -> Option<(T, [T; 3], [[T; 3]; 3], [[[T; 3]; 3]; 3])>
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let zero = T::zero();
+    // `[dir, -e1, -e2] * [t, r1, r2]^T = p0 - org`
+    let (a, b, c) = (*dir, p0.sub(p1), p0.sub(p2));
+    let det = a.dot(&b.cross(&c));
+    // relative to the scale, as `det` is proportional to the length of `dir` times the area
+    if det.abs() <= T::epsilon() * a.norm() * b.cross(&c).norm() {
+        return None;
+    }
+    let inv_det = T::one() / det;
+    // rows of the inverse matrix
+    let jinv = [
+        b.cross(&c).scale(inv_det),
+        c.cross(&a).scale(inv_det),
+        a.cross(&b).scale(inv_det),
+    ];
+    let rhs = p0.sub(org);
+    let (t, r1, r2) = (jinv[0].dot(&rhs), jinv[1].dot(&rhs), jinv[2].dot(&rhs));
+    let r0 = T::one() - r1 - r2;
+    if t < zero || r0 < zero || r1 < zero || r2 < zero {
+        return None;
+    }
+    let bc = [r0, r1, r2];
+    // `d[t, r1, r2]/dp_i = r_i * jinv`
+    let dt_dp = bc.map(|r| jinv[0].scale(r));
+    let dr1_dp = bc.map(|r| jinv[1].scale(r));
+    let dr2_dp = bc.map(|r| jinv[2].scale(r));
+    let dr0_dp = std::array::from_fn(|i| dr1_dp[i].add(&dr2_dp[i]).scale(-T::one()));
+    Some((t, bc, dt_dp, [dr0_dp, dr1_dp, dr2_dp]))
+}
+
+#[test]
+fn test_dw_intersection_against_ray() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..1000 {
+        let ps = [
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        ];
+        let org = crate::vec3::sample_centered_cube::<_, f64>(&mut rng);
+        let target = position_from_barycentric_coords(&ps[0], &ps[1], &ps[2], &[0.2, 0.3, 0.5]);
+        let dir = target
+            .sub(&org)
+            .add(&crate::vec3::sample_centered_cube::<_, f64>(&mut rng).scale(0.2));
+        let res = dw_intersection_against_ray(&ps[0], &ps[1], &ps[2], &org, &dir);
+        let t0 = intersection_against_line(&ps[0], &ps[1], &ps[2], &org, &dir).filter(|&t| t >= 0.);
+        assert_eq!(res.is_some(), t0.is_some());
+        let Some((t, bc, dt_dp, dr_dp)) = res else {
+            continue;
+        };
+        if area(&ps[0], &ps[1], &ps[2]) < 0.1 || bc.iter().any(|&r| r < 0.01) {
+            continue;
+        }
+        num_hit += 1;
+        assert!((t - t0.unwrap()).abs() < 1.0e-8);
+        let eps = 1.0e-5;
+        for (i_vtx, i_dim) in itertools::iproduct!(0..3, 0..3) {
+            let mut ps1 = ps;
+            ps1[i_vtx][i_dim] += eps;
+            let (t1, bc1, _, _) =
+                dw_intersection_against_ray(&ps1[0], &ps1[1], &ps1[2], &org, &dir).unwrap();
+            let v_num = (t1 - t) / eps;
+            assert!((v_num - dt_dp[i_vtx][i_dim]).abs() < 1.0e-3 * (1. + v_num.abs()));
+            for i_bc in 0..3 {
+                let v_num = (bc1[i_bc] - bc[i_bc]) / eps;
+                let v_ana = dr_dp[i_bc][i_vtx][i_dim];
+                assert!((v_num - v_ana).abs() < 1.0e-3 * (1. + v_num.abs()));
+            }
+        }
+    }
+    assert!(num_hit > 50, "{num_hit}");
+    // small geometry (1mm) in f32 is not parallel
+    let ps = [[0f32, 0., 0.], [1.0e-3, 0., 0.], [0., 1.0e-3, 0.]];
+    let (org, dir) = ([2.0e-4, 2.0e-4, 5.0e-4], [0., 0., -1.0e-3]);
+    let (t, bc, _, _) = dw_intersection_against_ray(&ps[0], &ps[1], &ps[2], &org, &dir).unwrap();
+    assert!((t - 0.5).abs() < 1.0e-5);
+    assert!(crate::vec3::distance(&bc, &[0.6, 0.2, 0.2]) < 1.0e-5);
+    let dir = [1.0e-3, 0., 0.];
+    assert!(dw_intersection_against_ray(&ps[0], &ps[1], &ps[2], &org, &dir).is_none());
+}
+
 /// ray triangle intersection.
 /// * `dir` - any nonzero vector (not necessary to be a unit vector)
 /// * `t` - ratio of `dir` vector from
@@ -558,6 +1094,9 @@ fn test_dw_ray_triangle_intersection() {
     }
 }
 
+/// intersection segment between the triangle `p0`-`p1`-`p2` and the plane passing through `q0` with the normal `nq`
+/// * Return `None` also if the input or the intermediate values are non-finite.
+///   See [`try_intersection_against_plane3`] to distinguish the cases.
 pub fn intersection_against_plane3<T>(
     p0: &[T; 3],
     p1: &[T; 3],
@@ -565,6 +1104,22 @@ pub fn intersection_against_plane3<T>(
     q0: &[T; 3],
     nq: &[T; 3],
 ) -> Option<([T; 3], [T; 3])>
+where
+    T: num_traits::Float,
+{
+    try_intersection_against_plane3(p0, p1, p2, q0, nq).unwrap_or(None)
+}
+
+/// the same as [`intersection_against_plane3`] but returns the error for the non-finite values
+/// including the ones by the overflow in the intermediate computation
+#[allow(clippy::type_complexity)]
+pub fn try_intersection_against_plane3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    q0: &[T; 3],
+    nq: &[T; 3],
+) -> Result<Option<([T; 3], [T; 3])>, crate::error::DegenerateError>
 where
     T: num_traits::Float,
 {
@@ -588,18 +1143,21 @@ where
     let dp0 = p0.sub(q0).dot(nq);
     let dp1 = p1.sub(q0).dot(nq);
     let dp2 = p2.sub(q0).dot(nq);
+    if !(dp0.is_finite() && dp1.is_finite() && dp2.is_finite()) {
+        return Err(crate::error::DegenerateError::NonFinite);
+    }
     let (sp0, sp1, sp2) = (sgn(dp0), sgn(dp1), sgn(dp2));
     if sp0 == 0 && sp1 == 0 && sp2 == 0 {
-        return None;
+        return Ok(None);
     } // all negative side
     if sp0 == 2 && sp1 == 2 && sp2 == 2 {
-        return None;
+        return Ok(None);
     } // all positive side
     if sp0 + sp1 + sp2 == 1 || sp0 + sp1 + sp2 == 5 {
-        return None;
+        return Ok(None);
     } // sharing point but not intersecting
     if sp0 == 1 && sp1 == 1 && sp2 == 1 {
-        return None;
+        return Ok(None);
     } // degenerate case inside same plane
     // intersection of the lines connecting (p0,p1),(p1,p2),(p2,p0) and the plane span by (q0,q1,q2)
     let mut ap = Vec::<[T; 3]>::with_capacity(2);
@@ -621,11 +1179,15 @@ where
     if sp2 == 1 {
         ap.push(*p2);
     }
-    assert_eq!(ap.len(), 2);
-    Some((ap[0], ap[1]))
+    if ap.len() != 2 || !ap.iter().all(|p| p.iter().all(|v| v.is_finite())) {
+        return Err(crate::error::DegenerateError::NonFinite);
+    }
+    Ok(Some((ap[0], ap[1])))
 }
 
 /// if the triangle share a point, set the point as `p0` and `q0`
+/// * Return `None` also for the zero-area triangle or the input with NaN or infinity.
+///   See [`try_intersection_against_tri3`] to distinguish the cases.
 pub fn intersection_against_tri3<T>(
     p0: &[T; 3],
     p1: &[T; 3],
@@ -634,16 +1196,42 @@ pub fn intersection_against_tri3<T>(
     q1: &[T; 3],
     q2: &[T; 3],
 ) -> Option<([T; 3], [T; 3])>
+where
+    T: num_traits::Float,
+{
+    try_intersection_against_tri3(p0, p1, p2, q0, q1, q2).unwrap_or(None)
+}
+
+/// the same as [`intersection_against_tri3`] but returns the error for the zero-area triangle
+/// and for the non-finite values including the ones by the overflow in the intermediate computation
+#[allow(clippy::type_complexity)]
+pub fn try_intersection_against_tri3<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+) -> Result<Option<([T; 3], [T; 3])>, crate::error::DegenerateError>
 where
     T: num_traits::Float,
 {
     use crate::vec3::Vec3;
+    crate::error::check_finite(&[p0, p1, p2, q0, q1, q2])?;
     let np = normal(p0, p1, p2);
     let nq = normal(q0, q1, q2);
-    let (ps, pe) = intersection_against_plane3(p0, p1, p2, q0, &nq)?;
-    let (qs, qe) = intersection_against_plane3(q0, q1, q2, p0, &np)?;
     // the line direction intersection of the plane (p0,p1,p2) and the plane (q0,q1,q2)
     let vz = np.cross(&nq);
+    crate::error::check_finite(&[&np, &nq, &vz])?;
+    if np.squared_norm().is_zero() || nq.squared_norm().is_zero() {
+        return Err(crate::error::DegenerateError::ZeroAreaTriangle);
+    }
+    let Some((ps, pe)) = try_intersection_against_plane3(p0, p1, p2, q0, &nq)? else {
+        return Ok(None);
+    };
+    let Some((qs, qe)) = try_intersection_against_plane3(q0, q1, q2, p0, &np)? else {
+        return Ok(None);
+    };
     //
     let zps = ps.dot(&vz);
     let zpe = pe.dot(&vz);
@@ -652,7 +1240,9 @@ where
     } else {
         (ps, pe, zps, zpe)
     };
-    assert!(zps <= zpe);
+    if !(zps.is_finite() && zpe.is_finite()) {
+        return Err(crate::error::DegenerateError::NonFinite);
+    }
     //
     let zqs = qs.dot(&vz);
     let zqe = qe.dot(&vz);
@@ -661,15 +1251,174 @@ where
     } else {
         (qs, qe, zqs, zqe)
     };
-    assert!(zqs <= zqe);
+    if !(zqs.is_finite() && zqe.is_finite()) {
+        return Err(crate::error::DegenerateError::NonFinite);
+    }
     //
     if zps >= zqe || zqs >= zpe {
         // the intersection does not overlap
-        return None;
+        return Ok(None);
     } // no overlap or overlap at point
     let s = if zps < zqs { qs } else { ps };
     let e = if zpe < zqe { pe } else { qe };
-    Some((s, e))
+    Ok(Some((s, e)))
+}
+
+/// segment shared by two triangles. See [`intersection_against_tri3_detailed`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriTriIntersection<T> {
+    /// end points of the segment
+    pub points: [[T; 3]; 2],
+    /// barycentric coordinates of the end points on the triangle `p0`-`p1`-`p2`
+    pub bcs_p: [[T; 3]; 2],
+    /// barycentric coordinates of the end points on the triangle `q0`-`q1`-`q2`
+    pub bcs_q: [[T; 3]; 2],
+}
+
+/// the same as [`intersection_against_tri3`] (Möller's interval overlap test)
+/// but also returns the barycentric coordinates of the end points of the segment on both triangles
+pub fn intersection_against_tri3_detailed<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    q0: &[T; 3],
+    q1: &[T; 3],
+    q2: &[T; 3],
+) -> Option<TriTriIntersection<T>>
+where
+    T: num_traits::Float,
+{
+    let (s, e) = intersection_against_tri3(p0, p1, p2, q0, q1, q2)?;
+    Some(TriTriIntersection {
+        points: [s, e],
+        bcs_p: [s, e].map(|x| to_barycentric_coords(p0, p1, p2, &x)),
+        bcs_q: [s, e].map(|x| to_barycentric_coords(q0, q1, q2, &x)),
+    })
+}
+
+#[test]
+fn test_intersection_against_tri3_detailed() {
+    use crate::vec3::Vec3;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _iter in 0..1000 {
+        let p = [
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        ];
+        let q = [
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+            crate::vec3::sample_centered_cube::<_, f64>(&mut rng),
+        ];
+        if area(&p[0], &p[1], &p[2]) < 0.1 || area(&q[0], &q[1], &q[2]) < 0.1 {
+            continue;
+        }
+        let res = intersection_against_tri3_detailed(&p[0], &p[1], &p[2], &q[0], &q[1], &q[2]);
+        // an edge of one triangle crosses the other triangle if they intersect
+        let num_edge_hit = (0..3)
+            .filter(|&i| {
+                let (a, b) = (&p[i], &p[(i + 1) % 3]);
+                crate::edge3::intersection_against_tri3(a, b, &q[0], &q[1], &q[2]).is_some()
+            })
+            .count()
+            + (0..3)
+                .filter(|&i| {
+                    let (a, b) = (&q[i], &q[(i + 1) % 3]);
+                    crate::edge3::intersection_against_tri3(a, b, &p[0], &p[1], &p[2]).is_some()
+                })
+                .count();
+        let Some(res) = res else {
+            assert_eq!(num_edge_hit, 0);
+            continue;
+        };
+        assert_eq!(num_edge_hit, 2);
+        num_hit += 1;
+        for i in 0..2 {
+            let x = res.points[i];
+            let xp = position_from_barycentric_coords(&p[0], &p[1], &p[2], &res.bcs_p[i]);
+            let xq = position_from_barycentric_coords(&q[0], &q[1], &q[2], &res.bcs_q[i]);
+            assert!(x.sub(&xp).norm() < 1.0e-8 && x.sub(&xq).norm() < 1.0e-8);
+            for bc in [res.bcs_p[i], res.bcs_q[i]] {
+                assert!(bc.iter().all(|&r| r > -1.0e-8));
+                assert!((bc[0] + bc[1] + bc[2] - 1.).abs() < 1.0e-8);
+            }
+            // on the boundary of at least one triangle
+            let r_min = res.bcs_p[i]
+                .iter()
+                .chain(res.bcs_q[i].iter())
+                .fold(f64::MAX, |a, &b| a.min(b.abs()));
+            assert!(r_min < 1.0e-8);
+        }
+    }
+    assert!(num_hit > 20, "{num_hit}");
+}
+
+#[test]
+fn test_try_degenerate() {
+    use crate::error::DegenerateError;
+    let (p0, p1, p2) = ([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+    let (q0, q1) = ([0., 0., 0.], [1., 1., 0.]);
+    // collinear triangle
+    assert_eq!(
+        try_cot(&q0, &q1, &[2., 2., 0.]),
+        Err(DegenerateError::ZeroAreaTriangle)
+    );
+    assert!(try_cot(&p0, &p1, &p2).is_ok());
+    assert_eq!(
+        try_nearest_to_point3(&q0, &q1, &[2., 2., 0.], &[0., 0., 1.]),
+        Err(DegenerateError::ZeroAreaTriangle)
+    );
+    let (q, r0, r1) = try_nearest_to_point3(&p0, &p1, &p2, &[0.2, 0.3, 1.]).unwrap();
+    assert_eq!(
+        (q, r0, r1),
+        nearest_to_point3(&p0, &p1, &p2, &[0.2, 0.3, 1.])
+    );
+    // line parallel to the plane
+    assert_eq!(
+        try_intersection_plane_of_tri3_against_line(&p0, &p1, &p2, &[0., 0., 1.], &[1., 0., 0.]),
+        Err(DegenerateError::ParallelToPlane)
+    );
+    let (pos, _bc) =
+        try_intersection_plane_of_tri3_against_line(&p0, &p1, &p2, &[0.2, 0.2, 1.], &[0., 0., -1.])
+            .unwrap();
+    assert!((pos[0] - 0.2f64).abs() < 1.0e-10 && pos[2].abs() < 1.0e-10);
+    // NaN does not panic
+    let nan = [f64::NAN, 0., 0.];
+    assert_eq!(
+        try_intersection_against_tri3(&p0, &p1, &p2, &nan, &q1, &[0., 0., 1.]),
+        Err(DegenerateError::NonFinite)
+    );
+    assert_eq!(
+        try_intersection_against_tri3(&p0, &p1, &p2, &q0, &q1, &[2., 2., 0.]),
+        Err(DegenerateError::ZeroAreaTriangle)
+    );
+    assert_eq!(
+        intersection_against_tri3(&p0, &p1, &p2, &q0, &q1, &[2., 2., 0.]),
+        None
+    );
+    // finite input whose cross product overflows
+    let big = [1.0e200, 0., 0.];
+    assert_eq!(
+        try_intersection_against_tri3(&p0, &big, &[0., 1.0e200, 0.], &q0, &big, &[0., 0., 1.0e200]),
+        Err(DegenerateError::NonFinite)
+    );
+    // the non-panicking versions return `None` for the dirty input
+    assert_eq!(
+        intersection_against_tri3(&p0, &big, &[0., 1.0e200, 0.], &q0, &big, &[0., 0., 1.0e200]),
+        None
+    );
+    assert_eq!(
+        intersection_against_tri3(&p0, &p1, &p2, &nan, &q1, &[0., 0., 1.]),
+        None
+    );
+    let nq = [0., 0., 1.];
+    assert_eq!(
+        intersection_against_plane3(&p0, &p1, &[0., 1., f64::NAN], &q0, &nq),
+        None
+    );
 }
 
 #[allow(unused_variables)]
@@ -24,6 +24,18 @@ where
     fn area_quadrilateral(&self, other: &Self) -> Real;
     fn wdw_angle_between_two_vecs(&self, other: &Self) -> (Real, [Self; 2]);
     fn rot90(&self) -> Self;
+    fn perp(&self) -> Self;
+    fn perp_dot(&self, other: &Self) -> Real;
+    fn rotate_around(&self, pivot: &Self, theta: Real) -> Self;
+    fn normalize_checked(&self, eps: Real) -> Option<Self>;
+    fn signed_angle_to(&self, other: &Self) -> Real;
+    fn angle(&self) -> Real;
+    fn to_polar(&self) -> (Real, Real);
+    fn element_min(&self, other: &Self) -> Self;
+    fn element_max(&self, other: &Self) -> Self;
+    fn abs(&self) -> Self;
+    fn clamp(&self, min: &Self, max: &Self) -> Self;
+    fn lerp(&self, other: &Self, t: Real) -> Self;
 }
 
 impl<Real> Vec2<Real> for [Real; 2]
@@ -89,6 +101,42 @@ where
     fn rot90(&self) -> Self {
         rotate90(self)
     }
+    fn perp(&self) -> Self {
+        rotate90(self)
+    }
+    fn perp_dot(&self, other: &Self) -> Real {
+        perp_dot(self, other)
+    }
+    fn rotate_around(&self, pivot: &Self, theta: Real) -> Self {
+        rotate_around(self, pivot, theta)
+    }
+    fn normalize_checked(&self, eps: Real) -> Option<Self> {
+        normalize_checked(self, eps)
+    }
+    fn signed_angle_to(&self, other: &Self) -> Real {
+        angle_between_two_vecs(self, other)
+    }
+    fn angle(&self) -> Real {
+        angle(self)
+    }
+    fn to_polar(&self) -> (Real, Real) {
+        to_polar(self)
+    }
+    fn element_min(&self, other: &Self) -> Self {
+        element_min(self, other)
+    }
+    fn element_max(&self, other: &Self) -> Self {
+        element_max(self, other)
+    }
+    fn abs(&self) -> Self {
+        abs(self)
+    }
+    fn clamp(&self, min: &Self, max: &Self) -> Self {
+        clamp(self, min, max)
+    }
+    fn lerp(&self, other: &Self, t: Real) -> Self {
+        lerp(self, other, t)
+    }
 }
 
 pub fn basis<T>(i_dim: usize, eps: T) -> [T; 2]
@@ -135,6 +183,7 @@ where
     *a = a.add(b)
 }
 
+/// rotate 90 degrees counter-clockwise (i.e., the perpendicular vector)
 pub fn rotate90<T>(v: &[T; 2]) -> [T; 2]
 where
     T: num_traits::Float,
@@ -142,6 +191,14 @@ where
     [-v[1], v[0]]
 }
 
+/// the perpendicular dot product `perp(a).dot(b)`, which is the same as [`cross`]
+pub fn perp_dot<T>(a: &[T; 2], b: &[T; 2]) -> T
+where
+    T: num_traits::Float,
+{
+    rotate90(a).dot(b)
+}
+
 pub fn add_three<T>(a: &[T; 2], b: &[T; 2], c: &[T; 2]) -> [T; 2]
 where
     T: num_traits::Float,
@@ -177,6 +234,7 @@ where
     a[0] * b[1] - a[1] * b[0]
 }
 
+/// signed angle from `a` to `b` in `[-pi, pi]`, which is positive if `b` is counter-clockwise from `a`
 pub fn angle_between_two_vecs<T>(a: &[T; 2], b: &[T; 2]) -> T
 where
     T: num_traits::Float,
@@ -193,6 +251,54 @@ fn test_angle_between_two_vecs() {
     let theta0 = a.angle_between_two_vecs(&b);
     let theta1 = 7f64 / 12f64 * std::f64::consts::PI;
     assert!((theta0 - theta1).abs() < 1.0e-10);
+    assert!((b.signed_angle_to(&a) + theta1).abs() < 1.0e-10);
+}
+
+/// angle of the vector from the x-axis in `[-pi, pi]` measured counter-clockwise
+pub fn angle<T>(a: &[T; 2]) -> T
+where
+    T: num_traits::Float,
+{
+    a[1].atan2(a[0])
+}
+
+/// vector from the polar coordinate
+/// # Argument
+/// - theta: radian measured counter-clockwise from the x-axis
+pub fn from_polar<T>(r: T, theta: T) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    let (s, c) = theta.sin_cos();
+    [r * c, r * s]
+}
+
+/// polar coordinate `(r, theta)` of the vector where `theta` is in `[-pi, pi]` (see [`angle`])
+pub fn to_polar<T>(a: &[T; 2]) -> (T, T)
+where
+    T: num_traits::Float,
+{
+    (length(a), angle(a))
+}
+
+#[test]
+fn test_polar() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let b: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let (r, theta) = a.to_polar();
+        assert!((-std::f64::consts::PI..=std::f64::consts::PI).contains(&theta));
+        assert!(from_polar(r, theta).sub(&a).norm() < 1.0e-10);
+        assert!((a.angle() - theta).abs() < 1.0e-10);
+        // rotating `a` by the signed angle aligns it with `b`
+        let t = a.signed_angle_to(&b);
+        assert!(rotate(&a, t).normalize().sub(&b.normalize()).norm() < 1.0e-10);
+        let k = (b.angle() - a.angle() - t) / (2. * std::f64::consts::PI);
+        assert!((k - k.round()).abs() < 1.0e-10);
+    }
 }
 
 pub fn wdw_angle_between_two_vecs<T>(u: &[T; 2], v: &[T; 2]) -> (T, [[T; 2]; 2])
@@ -236,7 +342,14 @@ where
     if v[2].is_zero() {
         return None;
     }
-    Some([v[0] / v[2], v[0] / v[2]])
+    Some([v[0] / v[2], v[1] / v[2]])
+}
+
+#[test]
+fn test_from_homogeneous() {
+    let p = from_homogeneous(&[1.0f64, -3.0, 2.0]).unwrap();
+    assert_eq!(p, [0.5, -1.5]);
+    assert!(from_homogeneous(&[1.0f64, 2.0, 0.0]).is_none());
 }
 
 /// rotate around the origin
@@ -251,6 +364,16 @@ where
     [c * p[0] - s * p[1], s * p[0] + c * p[1]]
 }
 
+/// rotate around the point `pivot`
+/// # Argument
+/// - theta: radian
+pub fn rotate_around<Real>(p: &[Real; 2], pivot: &[Real; 2], theta: Real) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    rotate(&p.sub(pivot), theta).add(pivot)
+}
+
 pub fn normalize<Real>(p: &[Real; 2]) -> [Real; 2]
 where
     Real: num_traits::Float,
@@ -259,6 +382,18 @@ where
     p.scale(invl)
 }
 
+/// Returns `None` if the length of the vector is not larger than `eps` or not finite
+pub fn normalize_checked<Real>(p: &[Real; 2], eps: Real) -> Option<[Real; 2]>
+where
+    Real: num_traits::Float,
+{
+    let l = length(p);
+    if l <= eps || !l.is_finite() {
+        return None;
+    }
+    Some(p.scale(Real::one() / l))
+}
+
 pub fn normalize_in_place<Real>(p: &mut [Real; 2])
 where
     Real: num_traits::Float,
@@ -297,6 +432,98 @@ where
     a[0] * b[1] - b[0] * a[1]
 }
 
+/// component-wise minimum. A NaN component is ignored as in [`f64::min`]
+pub fn element_min<T>(a: &[T; 2], b: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].min(b[i]))
+}
+
+/// component-wise maximum. A NaN component is ignored as in [`f64::max`]
+pub fn element_max<T>(a: &[T; 2], b: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].max(b[i]))
+}
+
+/// component-wise absolute value
+pub fn abs<T>(a: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    a.map(|v| v.abs())
+}
+
+/// clamp each component into the range `[min[i], max[i]]`
+pub fn clamp<T>(a: &[T; 2], min: &[T; 2], max: &[T; 2]) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].max(min[i]).min(max[i]))
+}
+
+/// linear interpolation `(1-t) * a + t * b`
+pub fn lerp<T>(a: &[T; 2], b: &[T; 2], t: T) -> [T; 2]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+#[test]
+fn test_element_wise_min_max() {
+    // NaN component is ignored
+    let a = [f64::NAN, 1.];
+    let b = [2., f64::NAN];
+    assert_eq!(a.element_min(&b), [2., 1.]);
+    assert_eq!(a.element_max(&b), [2., 1.]);
+    assert!(
+        element_min(&[f64::NAN; 2], &[f64::NAN; 2])
+            .iter()
+            .all(|v| v.is_nan())
+    );
+    // ties
+    let a = [-1., 3.];
+    assert_eq!(a.element_min(&a), a);
+    assert_eq!(a.element_max(&a), a);
+    assert_eq!(a.abs(), [1., 3.]);
+    // clamp at the boundary and into the degenerate range
+    assert_eq!([-2., 0.5].clamp(&[-1., 0.], &[1., 0.5]), [-1., 0.5]);
+    assert_eq!([0.3, 0.7].clamp(&[0.5, 0.5], &[0.5, 0.5]), [0.5, 0.5]);
+    // lerp at the ends and outside
+    let (a, b) = ([1., -2.], [3., 4.]);
+    assert_eq!(a.lerp(&b, 0.), a);
+    assert_eq!(a.lerp(&b, 1.), b);
+    assert_eq!(a.lerp(&b, 2.), [5., 10.]);
+}
+
+#[test]
+fn test_perp() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let b: [f64; 2] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        assert!(a.perp().dot(&a).abs() < 1.0e-12);
+        assert!(a.cross(&a.perp()) > 0.); // counter-clockwise
+        assert!((a.perp_dot(&b) - a.cross(&b)).abs() < 1.0e-12);
+        let theta = 6. * rng.random::<f64>() - 3.;
+        let c = a.rotate_around(&b, theta);
+        assert!((c.sub(&b).norm() - a.sub(&b).norm()).abs() < 1.0e-12);
+        let d = rotate(&a.sub(&b), theta).add(&b);
+        assert!(c.sub(&d).norm() < 1.0e-12);
+        assert!(b.rotate_around(&b, theta).sub(&b).norm() < 1.0e-12);
+        let n = a.normalize_checked(0.).unwrap();
+        assert!((n.norm() - 1.).abs() < 1.0e-12 && n.cross(&a).abs() < 1.0e-12);
+    }
+    assert!([0f64, 0.].normalize_checked(0.).is_none());
+    assert!([1.0e-12f64, 0.].normalize_checked(1.0e-10).is_none());
+    assert!([f64::NAN, 0.].normalize_checked(0.).is_none());
+}
+
 // -------------------------------
 // below: about the Vec2 class
 #[derive(Debug, Clone, Copy)]
@@ -6,6 +6,7 @@ where
     Self: Sized,
 {
     fn normalize(&self) -> Self;
+    fn normalize_checked(&self, eps: Real) -> Option<Self>;
     fn scale(&self, s: Real) -> Self;
     fn scale_in_place(&mut self, s: Real);
     fn norm(&self) -> Real;
@@ -24,6 +25,14 @@ where
     fn cross_mut(&mut self, v1: &Self, v2: &Self);
     fn mult_mat3_col_major(&self, a: &[Real; 9]) -> Self;
     fn mult_mat3_array_of_array(&self, a: &[[Real; 3]; 3]) -> Self;
+    fn reflect(&self, n: &Self) -> Self;
+    fn refract(&self, n: &Self, eta: Real) -> Option<Self>;
+    fn orthonormal_basis(&self) -> (Self, Self);
+    fn element_min(&self, other: &Self) -> Self;
+    fn element_max(&self, other: &Self) -> Self;
+    fn abs(&self) -> Self;
+    fn clamp(&self, min: &Self, max: &Self) -> Self;
+    fn lerp(&self, other: &Self, t: Real) -> Self;
 }
 
 impl<Real> Vec3<Real> for [Real; 3]
@@ -33,6 +42,9 @@ where
     fn normalize(&self) -> Self {
         normalize(self)
     }
+    fn normalize_checked(&self, eps: Real) -> Option<Self> {
+        normalize_checked(self, eps)
+    }
     fn scale(&self, s: Real) -> Self {
         scale(self, s)
     }
@@ -87,6 +99,30 @@ where
     fn mult_mat3_array_of_array(&self, a: &[[Real; 3]; 3]) -> Self {
         mult_mat3_array_of_array(self, a)
     }
+    fn reflect(&self, n: &Self) -> Self {
+        mirror_reflection(self, n)
+    }
+    fn refract(&self, n: &Self, eta: Real) -> Option<Self> {
+        refract(self, n, eta)
+    }
+    fn orthonormal_basis(&self) -> (Self, Self) {
+        orthonormal_basis(self)
+    }
+    fn element_min(&self, other: &Self) -> Self {
+        element_min(self, other)
+    }
+    fn element_max(&self, other: &Self) -> Self {
+        element_max(self, other)
+    }
+    fn abs(&self) -> Self {
+        abs(self)
+    }
+    fn clamp(&self, min: &Self, max: &Self) -> Self {
+        clamp(self, min, max)
+    }
+    fn lerp(&self, other: &Self, t: Real) -> Self {
+        lerp(self, other, t)
+    }
 }
 
 /// orthogonalize v against u, remove u component from v
@@ -315,6 +351,28 @@ where
     v.scale(linv)
 }
 
+/// return normalized 3D vector.
+/// Returns `None` if the norm is not larger than `eps` or not finite
+pub fn normalize_checked<T>(v: &[T; 3], eps: T) -> Option<[T; 3]>
+where
+    T: num_traits::Float,
+{
+    let l = v.norm();
+    if l <= eps || !l.is_finite() {
+        return None;
+    }
+    Some(v.scale(T::one() / l))
+}
+
+#[test]
+fn test_normalize_checked() {
+    let n = [3f64, 0., -4.].normalize_checked(1.0e-10).unwrap();
+    assert!(distance(&n, &[0.6, 0., -0.8]) < 1.0e-15);
+    assert!([0f64; 3].normalize_checked(0.).is_none());
+    assert!([1.0e-12f64, 0., 0.].normalize_checked(1.0e-10).is_none());
+    assert!([f64::INFINITY, 0., 0.].normalize_checked(0.).is_none());
+}
+
 pub fn cast<T, U>(v: &[T; 3]) -> [U; 3]
 where
     T: num_traits::AsPrimitive<U>,
@@ -435,6 +493,78 @@ where
     std::array::from_fn(|i| v[i] - nrm[i] * Real::from(2).unwrap() * a)
 }
 
+/// refraction of the unit direction `v` at the surface with the unit normal `nrm`
+/// facing against `v` (i.e., `v.dot(nrm) < 0`) by the Snell's law.
+/// * `eta` - ratio of the refractive indices (incident / transmitted)
+///
+/// Returns `None` for the total internal reflection.
+pub fn refract<Real>(v: &[Real; 3], nrm: &[Real; 3], eta: Real) -> Option<[Real; 3]>
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let cos_i = -nrm.dot(v);
+    let k = one - eta * eta * (one - cos_i * cos_i);
+    if k < Real::zero() {
+        return None;
+    }
+    let a = eta * cos_i - k.sqrt();
+    Some(std::array::from_fn(|i| v[i] * eta + nrm[i] * a))
+}
+
+#[test]
+fn test_refract() {
+    let nrm = [0f64, 0., 1.];
+    let theta_i = 0.5f64;
+    let v = [theta_i.sin(), 0., -theta_i.cos()];
+    let eta = 1.0 / 1.5;
+    let t = refract(&v, &nrm, eta).unwrap();
+    assert!((t.norm() - 1.).abs() < 1.0e-12);
+    assert!((t[0] - eta * theta_i.sin()).abs() < 1.0e-12); // Snell's law
+    assert!(t[2] < 0. && t[1] == 0.);
+    // total internal reflection
+    assert!(refract(&v, &nrm, 3.0).is_none());
+    let r = v.reflect(&nrm);
+    assert!((r[0] - v[0]).abs() < 1.0e-12 && (r[2] + v[2]).abs() < 1.0e-12);
+}
+
+/// two unit vectors that form a right-handed orthonormal basis `(b1, b2, n)` with the unit vector `n`
+/// by the branchless construction of Duff et al. (2017),
+/// "Building an Orthonormal Basis, Revisited"
+pub fn orthonormal_basis<Real>(n: &[Real; 3]) -> ([Real; 3], [Real; 3])
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let sign = one.copysign(n[2]);
+    let a = -one / (sign + n[2]);
+    let b = n[0] * n[1] * a;
+    (
+        [one + sign * n[0] * n[0] * a, sign * b, -sign * n[0]],
+        [b, sign + n[1] * n[1] * a, -n[1]],
+    )
+}
+
+#[test]
+fn test_orthonormal_basis() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..100 {
+        let n: [f64; 3] = match i_iter {
+            0 => [0., 0., 1.],
+            1 => [0., 0., -1.],
+            2 => [1.0e-10, 0., -1.],
+            _ => std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.),
+        };
+        let n = n.normalize();
+        let (b1, b2) = n.orthonormal_basis();
+        assert!((b1.norm() - 1.).abs() < 1.0e-10 && (b2.norm() - 1.).abs() < 1.0e-10);
+        assert!(b1.dot(&b2).abs() < 1.0e-10 && b1.dot(&n).abs() < 1.0e-10);
+        assert!((scalar_triple_product(&b1, &b2, &n) - 1.).abs() < 1.0e-10);
+    }
+}
+
 pub fn element_wise_mult<Real>(a: &[Real; 3], b: &[Real; 3]) -> [Real; 3]
 where
     Real: num_traits::Float,
@@ -442,6 +572,121 @@ where
     std::array::from_fn(|i| a[i] * b[i])
 }
 
+/// component-wise minimum. A NaN component is ignored as in [`f64::min`]
+pub fn element_min<T>(a: &[T; 3], b: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].min(b[i]))
+}
+
+/// component-wise maximum. A NaN component is ignored as in [`f64::max`]
+pub fn element_max<T>(a: &[T; 3], b: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].max(b[i]))
+}
+
+/// component-wise absolute value
+pub fn abs<T>(a: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    a.map(|v| v.abs())
+}
+
+/// clamp each component into the range `[min[i], max[i]]`
+pub fn clamp<T>(a: &[T; 3], min: &[T; 3], max: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i].max(min[i]).min(max[i]))
+}
+
+/// linear interpolation `(1-t) * a + t * b`
+pub fn lerp<T>(a: &[T; 3], b: &[T; 3], t: T) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+#[test]
+fn test_element_wise_min_max() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let b: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        // the corners of the box spanned by two points
+        let (lo, hi) = (a.element_min(&b), a.element_max(&b));
+        assert_eq!(lo.add(&hi), a.add(&b));
+        let aabb = [lo[0], lo[1], lo[2], hi[0], hi[1], hi[2]];
+        // the clamped point is the nearest point in the box
+        let c: [f64; 3] = std::array::from_fn(|_| 3. * rng.random::<f64>() - 1.5);
+        let d = c.clamp(&lo, &hi);
+        let dist = crate::aabb::sq_distance_to_point::<f64, 3, 6>(&aabb, &c);
+        assert!((d.sub(&c).squared_norm() - dist).abs() < 1.0e-12);
+        // the midpoint is the center of the box
+        let m = a.lerp(&b, 0.5);
+        assert!(m.sub(&lo.add(&hi).scale(0.5)).norm() < 1.0e-12);
+    }
+    // NaN component is ignored
+    let a = [f64::NAN, 1., -1.];
+    assert_eq!(a.element_min(&[0.; 3]), [0., 0., -1.]);
+    assert_eq!(a.element_max(&[0.; 3]), [0., 1., 0.]);
+    // the signed zeros compare equal
+    assert_eq!([-0f64; 3].element_min(&[0.; 3]), [0.; 3]);
+    assert_eq!([-2., 0., 2.].abs(), [2., 0., 2.]);
+}
+
+/// spherical linear interpolation of the unit vectors with the constant angular velocity.
+/// The result is undefined if `a` and `b` are opposite.
+pub fn slerp<T>(a: &[T; 3], b: &[T; 3], t: T) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let sin_theta = a.cross(b).norm();
+    let theta = sin_theta.atan2(a.dot(b));
+    if theta < T::epsilon().sqrt() {
+        // nearly parallel. linear interpolation is accurate enough
+        return lerp(a, b, t).normalize();
+    }
+    let w0 = ((T::one() - t) * theta).sin() / sin_theta;
+    let w1 = (t * theta).sin() / sin_theta;
+    std::array::from_fn(|i| a[i] * w0 + b[i] * w1)
+}
+
+#[test]
+fn test_slerp() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..100 {
+        let a: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let a = a.normalize();
+        let b = match i_iter {
+            0 => a,
+            1 => [a[0] + 1.0e-9, a[1], a[2]].normalize(),
+            _ => {
+                let b: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+                b.normalize()
+            }
+        };
+        assert!(distance(&slerp(&a, &b, 0.), &a) < 1.0e-10);
+        assert!(distance(&slerp(&a, &b, 1.), &b) < 1.0e-10);
+        let theta = a.cross(&b).norm().atan2(a.dot(&b));
+        let t = rng.random::<f64>();
+        let c = slerp(&a, &b, t);
+        assert!((c.norm() - 1.).abs() < 1.0e-10);
+        // constant angular velocity along the great circle
+        assert!((c.cross(&a).norm().atan2(c.dot(&a)) - t * theta).abs() < 1.0e-8);
+        assert!(scalar_triple_product(&a, &b, &c).abs() < 1.0e-10);
+    }
+}
+
 pub fn add_three<T>(a: &[T; 3], b: &[T; 3], c: &[T; 3]) -> [T; 3]
 where
     T: num_traits::Float,
@@ -458,6 +703,79 @@ where
     std::array::from_fn(|_i| rng.random())
 }
 
+/// sample a point uniformly from the cube `[-1,1]^3`
+pub fn sample_centered_cube<Reng, T>(rng: &mut Reng) -> [T; 3]
+where
+    Reng: rand::Rng,
+    T: num_traits::Float,
+    rand::distr::StandardUniform: rand::distr::Distribution<T>,
+{
+    let one = T::one();
+    std::array::from_fn(|_i| (one + one) * rng.random::<T>() - one)
+}
+
+/// encode the unit vector into the 2D point in `[-1,1]^2` by the octahedral mapping.
+/// See Cigolle et al. (2014), "A Survey of Efficient Representations for Independent Unit Vectors"
+pub fn encode_octahedral(n: &[f32; 3]) -> [f32; 2] {
+    let l1 = n[0].abs() + n[1].abs() + n[2].abs();
+    let p = [n[0] / l1, n[1] / l1];
+    if n[2] >= 0. {
+        return p;
+    }
+    // fold the lower hemisphere over the diagonals
+    let sign_not_zero = |v: f32| if v >= 0. { 1f32 } else { -1f32 };
+    [
+        (1. - p[1].abs()) * sign_not_zero(p[0]),
+        (1. - p[0].abs()) * sign_not_zero(p[1]),
+    ]
+}
+
+/// decode the unit vector from the 2D point in `[-1,1]^2` by the octahedral mapping
+pub fn decode_octahedral(e: &[f32; 2]) -> [f32; 3] {
+    let z = 1. - e[0].abs() - e[1].abs();
+    let t = (-z).max(0.);
+    let x = e[0] + if e[0] >= 0. { -t } else { t };
+    let y = e[1] + if e[1] >= 0. { -t } else { t };
+    normalize(&[x, y, z])
+}
+
+/// encode the unit vector into `u32` by the octahedral mapping with 16 bits per component.
+/// The first component is stored in the lower 16 bits.
+pub fn encode_octahedral_u32(n: &[f32; 3]) -> u32 {
+    let e = encode_octahedral(n);
+    let quantize = |v: f32| ((v.clamp(-1., 1.) * 0.5 + 0.5) * 65535.).round() as u32;
+    quantize(e[0]) | (quantize(e[1]) << 16)
+}
+
+/// decode the unit vector from `u32` encoded by [`encode_octahedral_u32`]
+pub fn decode_octahedral_u32(e: u32) -> [f32; 3] {
+    let dequantize = |v: u32| (v & 0xffff) as f32 / 65535. * 2. - 1.;
+    decode_octahedral(&[dequantize(e), dequantize(e >> 16)])
+}
+
+#[test]
+fn test_octahedral() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for i_iter in 0..1000 {
+        let n: [f32; 3] = match i_iter {
+            0 => [0., 0., 1.],
+            1 => [0., 0., -1.],
+            2 => [1., 0., 0.],
+            3 => [0., -1., 0.],
+            _ => std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.),
+        };
+        let n = n.normalize();
+        let e = encode_octahedral(&n);
+        assert!(e[0].abs() <= 1. && e[1].abs() <= 1.);
+        let m = decode_octahedral(&e);
+        assert!(distance(&n, &m) < 1.0e-6, "{n:?} {m:?}");
+        let m = decode_octahedral_u32(encode_octahedral_u32(&n));
+        assert!(distance(&n, &m) < 1.0e-4, "{n:?} {m:?}");
+    }
+}
+
 pub fn mult_mat3_array_of_array<T>(a: &[T; 3], m: &[[T; 3]; 3]) -> [T; 3]
 where
     T: num_traits::Float,
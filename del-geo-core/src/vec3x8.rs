@@ -0,0 +1,144 @@
+//! eight 3D vectors stored in the SoA (structure of arrays) layout for the batched computation.
+//! The operations are lane-wise so that the compiler can vectorize them with 8-wide SIMD.
+
+/// number of the lanes
+pub const LANE: usize = 8;
+
+/// eight 3D vectors where `(x[i], y[i], z[i])` is the vector in the `i`-th lane
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Vec3x8 {
+    pub x: [f32; LANE],
+    pub y: [f32; LANE],
+    pub z: [f32; LANE],
+}
+
+// SAFETY: `repr(C)` with the fields of `[f32; LANE]` without padding
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vec3x8 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vec3x8 {}
+
+impl Vec3x8 {
+    /// the same vector in all the lanes
+    pub fn splat(v: &[f32; 3]) -> Self {
+        Self {
+            x: [v[0]; LANE],
+            y: [v[1]; LANE],
+            z: [v[2]; LANE],
+        }
+    }
+
+    pub fn from_array(vs: &[[f32; 3]; LANE]) -> Self {
+        Self {
+            x: std::array::from_fn(|i| vs[i][0]),
+            y: std::array::from_fn(|i| vs[i][1]),
+            z: std::array::from_fn(|i| vs[i][2]),
+        }
+    }
+
+    /// load from the slice of the vectors.
+    /// The lanes beyond the length of the slice are filled with `pad`.
+    pub fn from_slice(vs: &[[f32; 3]], pad: &[f32; 3]) -> Self {
+        Self::from_array(&std::array::from_fn(|i| *vs.get(i).unwrap_or(pad)))
+    }
+
+    pub fn to_array(&self) -> [[f32; 3]; LANE] {
+        std::array::from_fn(|i| [self.x[i], self.y[i], self.z[i]])
+    }
+
+    pub fn lane(&self, i: usize) -> [f32; 3] {
+        [self.x[i], self.y[i], self.z[i]]
+    }
+
+    // above: from and to methods
+    // ---------------------------------
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            x: std::array::from_fn(|i| self.x[i] + other.x[i]),
+            y: std::array::from_fn(|i| self.y[i] + other.y[i]),
+            z: std::array::from_fn(|i| self.z[i] + other.z[i]),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self {
+            x: std::array::from_fn(|i| self.x[i] - other.x[i]),
+            y: std::array::from_fn(|i| self.y[i] - other.y[i]),
+            z: std::array::from_fn(|i| self.z[i] - other.z[i]),
+        }
+    }
+
+    /// scale each lane with the different scalar
+    pub fn scale(&self, s: &[f32; LANE]) -> Self {
+        Self {
+            x: std::array::from_fn(|i| self.x[i] * s[i]),
+            y: std::array::from_fn(|i| self.y[i] * s[i]),
+            z: std::array::from_fn(|i| self.z[i] * s[i]),
+        }
+    }
+
+    /// `self * s + other` for each lane
+    pub fn scale_add(&self, s: &[f32; LANE], other: &Self) -> Self {
+        Self {
+            x: std::array::from_fn(|i| self.x[i] * s[i] + other.x[i]),
+            y: std::array::from_fn(|i| self.y[i] * s[i] + other.y[i]),
+            z: std::array::from_fn(|i| self.z[i] * s[i] + other.z[i]),
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> [f32; LANE] {
+        std::array::from_fn(|i| {
+            self.x[i] * other.x[i] + self.y[i] * other.y[i] + self.z[i] * other.z[i]
+        })
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: std::array::from_fn(|i| self.y[i] * other.z[i] - self.z[i] * other.y[i]),
+            y: std::array::from_fn(|i| self.z[i] * other.x[i] - self.x[i] * other.z[i]),
+            z: std::array::from_fn(|i| self.x[i] * other.y[i] - self.y[i] * other.x[i]),
+        }
+    }
+
+    pub fn squared_norm(&self) -> [f32; LANE] {
+        self.dot(self)
+    }
+
+    pub fn norm(&self) -> [f32; LANE] {
+        self.squared_norm().map(|v| v.sqrt())
+    }
+}
+
+#[test]
+fn test_vec3x8() {
+    use crate::vec3::Vec3;
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut random_vecs = || -> [[f32; 3]; LANE] {
+        std::array::from_fn(|_| std::array::from_fn(|_| 2. * rng.random::<f32>() - 1.))
+    };
+    let (a, b) = (random_vecs(), random_vecs());
+    let (va, vb) = (Vec3x8::from_array(&a), Vec3x8::from_array(&b));
+    assert_eq!(va.to_array(), a);
+    let s: [f32; LANE] = std::array::from_fn(|i| i as f32 * 0.3 - 1.);
+    let (add, sub, cross) = (va.add(&vb), va.sub(&vb), va.cross(&vb));
+    let (scale, scale_add) = (va.scale(&s), va.scale_add(&s, &vb));
+    let (dot, norm) = (va.dot(&vb), va.norm());
+    for i in 0..LANE {
+        let eq = |p: &[f32; 3], q: &[f32; 3]| crate::vec3::distance(p, q) < 1.0e-6;
+        assert!(eq(&add.lane(i), &a[i].add(&b[i])));
+        assert!(eq(&sub.lane(i), &a[i].sub(&b[i])));
+        assert!(eq(&cross.lane(i), &a[i].cross(&b[i])));
+        assert!(eq(&scale.lane(i), &a[i].scale(s[i])));
+        assert!(eq(&scale_add.lane(i), &a[i].scale(s[i]).add(&b[i])));
+        assert!((dot[i] - a[i].dot(&b[i])).abs() < 1.0e-6);
+        assert!((norm[i] - a[i].norm()).abs() < 1.0e-6);
+    }
+    let c = Vec3x8::from_slice(&a[..3], &[9., 9., 9.]);
+    assert_eq!(c.lane(2), a[2]);
+    assert_eq!(c.lane(3), [9., 9., 9.]);
+    assert_eq!(Vec3x8::splat(&a[0]).lane(7), a[0]);
+}
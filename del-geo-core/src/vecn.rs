@@ -1,6 +1,9 @@
 //! methods for N dimensional vector
 
-pub trait VecN<T, const N: usize> {
+pub trait VecN<T, const N: usize>
+where
+    Self: Sized,
+{
     fn add(&self, other: &[T; N]) -> Self;
     fn add_in_place(&mut self, other: &[T; N]);
     fn sub(&self, other: &[T; N]) -> Self;
@@ -8,6 +11,17 @@ pub trait VecN<T, const N: usize> {
     fn scale(&self, scalar: T) -> Self;
     fn scale_in_place(&mut self, scale: T);
     fn norm(&self) -> T;
+    fn squared_norm(&self) -> T;
+    fn dot(&self, other: &[T; N]) -> T;
+    fn normalize(&self) -> Self;
+    /// Returns `None` if the norm is not larger than `eps` or not finite
+    fn normalize_checked(&self, eps: T) -> Option<Self>;
+    fn distance(&self, other: &[T; N]) -> T;
+    fn lerp(&self, other: &[T; N], t: T) -> Self;
+    fn element_min(&self, other: &[T; N]) -> Self;
+    fn element_max(&self, other: &[T; N]) -> Self;
+    /// `alpha * self + other` (axpy)
+    fn scale_add(&self, alpha: T, other: &[T; N]) -> Self;
 }
 
 impl<T, const N: usize> VecN<T, N> for [T; N]
@@ -40,6 +54,37 @@ where
     fn scale_in_place(&mut self, scale: T) {
         *self = self.scale(scale);
     }
+    fn squared_norm(&self) -> T {
+        dot(self, self)
+    }
+    fn dot(&self, other: &[T; N]) -> T {
+        dot(self, other)
+    }
+    fn normalize(&self) -> Self {
+        self.scale(T::one() / self.norm())
+    }
+    fn normalize_checked(&self, eps: T) -> Option<Self> {
+        let l = self.norm();
+        if l <= eps || !l.is_finite() {
+            return None;
+        }
+        Some(self.scale(T::one() / l))
+    }
+    fn distance(&self, other: &[T; N]) -> T {
+        distance(self, other)
+    }
+    fn lerp(&self, other: &[T; N], t: T) -> Self {
+        std::array::from_fn(|i| self[i] + (other[i] - self[i]) * t)
+    }
+    fn element_min(&self, other: &[T; N]) -> Self {
+        std::array::from_fn(|i| self[i].min(other[i]))
+    }
+    fn element_max(&self, other: &[T; N]) -> Self {
+        std::array::from_fn(|i| self[i].max(other[i]))
+    }
+    fn scale_add(&self, alpha: T, other: &[T; N]) -> Self {
+        std::array::from_fn(|i| alpha * self[i] + other[i])
+    }
 }
 
 #[test]
@@ -52,6 +97,22 @@ fn test_add() {
     // assert_eq!([1, 2, 3, 4].add(&[2, 3, 4, 5]), [3, 5, 7, 9]);
 }
 
+#[test]
+fn test_vecn_ops() {
+    let a = [1., -2., 2., 0., 4., 0.];
+    let b = [3., 1., -1., 2., 0., 1.];
+    assert_eq!(a.squared_norm(), 25.);
+    assert_eq!(a.dot(&b), -1.);
+    assert_eq!(a.normalize(), [0.2, -0.4, 0.4, 0., 0.8, 0.]);
+    assert_eq!([1., 2., 3., 4.].distance(&[1., 5., 3., 0.]), 5.);
+    assert_eq!(a.lerp(&b, 0.5), [2., -0.5, 0.5, 1., 2., 0.5]);
+    assert_eq!(a.element_min(&b), [1., -2., -1., 0., 0., 0.]);
+    assert_eq!(a.element_max(&b), [3., 1., 2., 2., 4., 1.]);
+    assert_eq!(a.scale_add(2., &b), [5., -3., 3., 2., 8., 1.]);
+    assert_eq!(a.normalize_checked(1.0e-10), Some(a.normalize()));
+    assert!([0.; 6].normalize_checked(1.0e-10).is_none());
+}
+
 pub fn add_three<T, const N: usize>(a: &[T; N], b: &[T; N], c: &[T; N]) -> [T; N]
 where
     T: num_traits::Float,
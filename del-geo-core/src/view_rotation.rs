@@ -2,6 +2,7 @@
 
 use crate::quaternion::Quaternion;
 #[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
 pub struct Trackball<Real>
 where
     Real: num_traits::Float,
@@ -9,6 +10,12 @@ where
     pub quaternion: [Real; 4],
 }
 
+// SAFETY: transparent wrapper of an array of `Pod`
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: num_traits::Float + bytemuck::Zeroable> bytemuck::Zeroable for Trackball<Real> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<Real: num_traits::Float + bytemuck::Pod> bytemuck::Pod for Trackball<Real> {}
+
 impl<Real> Trackball<Real>
 where
     Real: num_traits::Float,
@@ -35,6 +42,32 @@ where
         let dq = crate::quaternion::from_axisangle(&[-dy, dx, zero]).normalized();
         self.quaternion = dq.mult_quaternion(&self.quaternion);
     }
+
+    /// the same as [`Self::camera_rotation`] but the rotation is limited to the one
+    /// around the unit vector `axis` in the world coordinate (e.g., the up axis for the turntable)
+    pub fn camera_rotation_around_axis(
+        &mut self,
+        cursor_dx: Real,
+        cursor_dy: Real,
+        axis: &[Real; 3],
+    ) {
+        let dx = cursor_dx;
+        let dy = cursor_dy;
+        let a = (dx * dx + dy * dy).sqrt();
+        let zero = Real::zero();
+        if a.is_zero() {
+            return;
+        }
+        let dq = crate::quaternion::from_axisangle(&[-dy, dx, zero]).normalized();
+        // rotation in the world coordinate such that `dq * q = q * dq_world`
+        let dq_world = self
+            .quaternion
+            .inverse()
+            .mult_quaternion(&dq)
+            .mult_quaternion(&self.quaternion);
+        let dq_world = crate::quaternion::constrain_to_axis(&dq_world, axis);
+        self.quaternion = self.quaternion.mult_quaternion(&dq_world).normalized();
+    }
 }
 
 impl<Real> Default for Trackball<Real>
@@ -45,3 +78,22 @@ where
         Self::new()
     }
 }
+
+#[test]
+fn test_camera_rotation_around_axis() {
+    let mut trackball = Trackball::<f64>::new();
+    trackball.camera_rotation(0.3, 0.2);
+    let up = [0., 1., 0.];
+    let r0 = trackball.quaternion.to_mat3_col_major();
+    for (dx, dy) in [(0.1, 0.0), (0.0, 0.2), (-0.3, 0.4)] {
+        trackball.camera_rotation_around_axis(dx, dy, &up);
+    }
+    // the world up axis is seen from the camera in the same direction
+    let r1 = trackball.quaternion.to_mat3_col_major();
+    let u0 = crate::mat3_col_major::mult_vec(&r0, &up);
+    let u1 = crate::mat3_col_major::mult_vec(&r1, &up);
+    assert!(crate::vec3::distance(&u0, &u1) < 1.0e-10);
+    assert!(
+        crate::vec3::distance(&r0[0..3].try_into().unwrap(), &r1[0..3].try_into().unwrap()) > 0.1
+    );
+}
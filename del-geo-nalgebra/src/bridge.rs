@@ -0,0 +1,120 @@
+//! conversions between the `nalgebra` types and the fixed-size arrays of `del_geo_core`.
+//! The matrices are converted to/from the column-major arrays (the storage order of `nalgebra`).
+//! Use `mat.transpose().to_array()` for the functions expecting the row-major arrays.
+
+/// conversion to the fixed-size array (column major for the matrices)
+pub trait ToArray<T, const N: usize> {
+    fn to_array(&self) -> [T; N];
+}
+
+/// conversion from the fixed-size array (column major for the matrices)
+pub trait FromArray<T, const N: usize> {
+    fn from_array(a: &[T; N]) -> Self;
+}
+
+impl<T, const R: usize, const C: usize, const N: usize> ToArray<T, N> for nalgebra::SMatrix<T, R, C>
+where
+    T: nalgebra::Scalar + Copy,
+{
+    fn to_array(&self) -> [T; N] {
+        const { assert!(R * C == N) };
+        std::array::from_fn(|i| self.as_slice()[i])
+    }
+}
+
+impl<T, const R: usize, const C: usize, const N: usize> FromArray<T, N>
+    for nalgebra::SMatrix<T, R, C>
+where
+    T: nalgebra::Scalar + Copy,
+{
+    fn from_array(a: &[T; N]) -> Self {
+        const { assert!(R * C == N) };
+        Self::from_column_slice(a)
+    }
+}
+
+/// call the function of `del_geo_core` taking an array with the `nalgebra` type
+/// and convert the returned array back to the `nalgebra` type
+/// ```
+/// use del_geo_nalgebra::bridge::map1;
+/// let v = nalgebra::Vector3::new(3.0f64, 0.0, 4.0);
+/// let n: nalgebra::Vector3<f64> = map1(&v, del_geo_core::vec3::normalize);
+/// assert!((n - v.normalize()).norm() < 1.0e-10);
+/// ```
+pub fn map1<T, A, B, const N: usize, const M: usize>(a: &A, f: impl Fn(&[T; N]) -> [T; M]) -> B
+where
+    A: ToArray<T, N>,
+    B: FromArray<T, M>,
+{
+    B::from_array(&f(&a.to_array()))
+}
+
+/// two argument version of [`map1`]
+pub fn map2<T, A0, A1, B, const N0: usize, const N1: usize, const M: usize>(
+    a0: &A0,
+    a1: &A1,
+    f: impl Fn(&[T; N0], &[T; N1]) -> [T; M],
+) -> B
+where
+    A0: ToArray<T, N0>,
+    A1: ToArray<T, N1>,
+    B: FromArray<T, M>,
+{
+    B::from_array(&f(&a0.to_array(), &a1.to_array()))
+}
+
+/// eigen decomposition of the symmetric matrix using [`del_geo_core::mat3_sym::eigen_decomposition`].
+/// The input is symmetrized.
+/// # Returns
+/// `(U, l)` such that `A = U * diag(l) * U^t`
+pub fn mat3_sym_eigen_decomposition<T>(
+    a: &nalgebra::Matrix3<T>,
+    mode: del_geo_core::mat3_sym::EigenDecompositionModes,
+) -> Option<(nalgebra::Matrix3<T>, nalgebra::Vector3<T>)>
+where
+    T: nalgebra::RealField + num_traits::Float + num_traits::FloatConst,
+{
+    use del_geo_core::mat3_sym;
+    let sm = mat3_sym::from_mat3_by_symmetrization(&a.to_array());
+    let (u, l) = mat3_sym::eigen_decomposition(&sm, mode)?;
+    // `u` is row major
+    let u = nalgebra::Matrix3::<T>::from_array(&u).transpose();
+    Some((u, nalgebra::Vector3::<T>::from_array(&l)))
+}
+
+#[test]
+fn test_bridge() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let m = nalgebra::Matrix3::<f64>::from_fn(|_, _| 2. * rng.random::<f64>() - 1.);
+        let v = nalgebra::Vector3::<f64>::from_fn(|_, _| 2. * rng.random::<f64>() - 1.);
+        {
+            // column major
+            let a: [f64; 9] = m.to_array();
+            assert_eq!(a[1], m[(1, 0)]);
+            assert_eq!(nalgebra::Matrix3::from_array(&a), m);
+            let a: [f64; 3] = v.to_array();
+            assert_eq!(nalgebra::Vector3::from_array(&a), v);
+        }
+        {
+            let mv: nalgebra::Vector3<f64> = map2(&m, &v, del_geo_core::mat3_col_major::mult_vec);
+            assert!((mv - m * v).norm() < 1.0e-10);
+            let mi: nalgebra::Matrix3<f64> = map1(&m, |a| {
+                del_geo_core::mat3_col_major::try_inverse(a).unwrap()
+            });
+            assert!((mi * m - nalgebra::Matrix3::identity()).norm() < 1.0e-6);
+        }
+        {
+            let a = m + m.transpose();
+            let (u, l) = mat3_sym_eigen_decomposition(
+                &a,
+                del_geo_core::mat3_sym::EigenDecompositionModes::JacobiNumIter(20),
+            )
+            .unwrap();
+            let a1 = u * nalgebra::Matrix3::from_diagonal(&l) * u.transpose();
+            assert!((a - a1).norm() < 1.0e-6);
+        }
+    }
+}
@@ -8,6 +8,7 @@ tet
 pub mod aabb2;
 pub mod bezier_cubic;
 pub mod bezier_quadratic;
+pub mod bridge;
 pub mod ccd2;
 pub mod ccd3;
 pub mod edge;
@@ -20,6 +21,7 @@ pub mod mat2;
 pub mod mat3;
 pub mod mat4;
 pub mod plane;
+pub mod quaternion;
 pub mod sphere;
 pub mod tet;
 pub mod tri2;
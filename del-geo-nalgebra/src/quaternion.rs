@@ -0,0 +1,74 @@
+//! methods for quaternion using `nalgebra::UnitQuaternion`.
+//! The conversions to the array use the `[i,j,k,w]` storage of `del_geo_core::quaternion`.
+
+/// unit quaternion from the array `[i,j,k,w]`. The input is normalized.
+pub fn from_array<T>(q: &[T; 4]) -> nalgebra::UnitQuaternion<T>
+where
+    T: nalgebra::RealField + Copy,
+{
+    nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(q[3], q[0], q[1], q[2]))
+}
+
+/// array `[i,j,k,w]` of the unit quaternion
+pub fn to_array<T>(q: &nalgebra::UnitQuaternion<T>) -> [T; 4]
+where
+    T: nalgebra::RealField + Copy,
+{
+    [q.i, q.j, q.k, q.w]
+}
+
+/// rotation around the axis `a` by the angle `|a|`
+pub fn from_axisangle<T>(a: &nalgebra::Vector3<T>) -> nalgebra::UnitQuaternion<T>
+where
+    T: nalgebra::RealField + Copy,
+{
+    nalgebra::UnitQuaternion::from_scaled_axis(*a)
+}
+
+pub fn to_mat3<T>(q: &nalgebra::UnitQuaternion<T>) -> nalgebra::Matrix3<T>
+where
+    T: nalgebra::RealField + Copy,
+{
+    q.to_rotation_matrix().into_inner()
+}
+
+pub fn mult<T>(
+    p: &nalgebra::UnitQuaternion<T>,
+    q: &nalgebra::UnitQuaternion<T>,
+) -> nalgebra::UnitQuaternion<T>
+where
+    T: nalgebra::RealField + Copy,
+{
+    p * q
+}
+
+#[test]
+fn test_parity_with_core() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..100 {
+        let a0: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let a1: [f64; 3] = std::array::from_fn(|_| 2. * rng.random::<f64>() - 1.);
+        let q0 = del_geo_core::quaternion::from_axisangle(&a0);
+        let q1 = del_geo_core::quaternion::from_axisangle(&a1);
+        let p0 = from_axisangle(&nalgebra::Vector3::from(a0));
+        let p1 = from_axisangle(&nalgebra::Vector3::from(a1));
+        {
+            let p = to_array(&p0);
+            assert!((0..4).all(|i| (p[i] - q0[i]).abs() < 1.0e-10));
+            let p = to_array(&from_array(&q0.map(|v| v * 3.)));
+            assert!((0..4).all(|i| (p[i] - q0[i]).abs() < 1.0e-10));
+        }
+        {
+            let m0 = del_geo_core::quaternion::to_mat3_col_major(&q0);
+            let m1 = to_mat3(&p0);
+            assert!((0..9).all(|i| (m0[i] - m1.as_slice()[i]).abs() < 1.0e-10));
+        }
+        {
+            let q = del_geo_core::quaternion::mult_quaternion(&q0, &q1);
+            let p = to_array(&mult(&p0, &p1));
+            assert!((0..4).all(|i| (p[i] - q[i]).abs() < 1.0e-10));
+        }
+    }
+}